@@ -1,19 +1,108 @@
-// Unix implementation (uses termion)
-#[cfg(unix)]
+// Interactive CLI; crossterm handles raw mode and key input on every platform
 #[deny(clippy::all)]
-mod unix_cli {
+mod cli {
     use clap::{Parser, Subcommand};
     use colored::*;
-    use preflop_trainer_core::{AnswerResult, Game, UserAction, check_answer, load_config};
-    use std::io::{Write, stdin, stdout};
+    use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+    use crossterm::{cursor, execute, terminal};
+    use preflop_trainer_core::{
+        AnswerResult, CumulativeStats, Game, GameConfig, HandNotation, Position, RangeTable,
+        SessionStats, SpotKind, TrainerSession, UserAction, all_action_frequencies, answer_credit,
+        blocker_feedback, check_answer_detailed, config_json_schema, export_worksheet,
+        get_action_frequencies, get_correct_action, load_config, parse_spot_type_with_hint,
+        percent_to_range, range_combo_count, tighten_range, to_range_string, villain_range,
+    };
+    use std::io::{Write, stdout};
     use std::str::FromStr;
-    use termion::{input::TermRead, raw::IntoRawMode};
+
+    // Raw mode for as long as the guard lives. Dropping it, on any return from the game
+    // loop including Ctrl-C, leaves raw mode and shows the cursor again.
+    struct RawModeGuard;
+
+    impl RawModeGuard {
+        fn new() -> std::io::Result<Self> {
+            terminal::enable_raw_mode()?;
+            Ok(RawModeGuard)
+        }
+    }
+
+    impl Drop for RawModeGuard {
+        fn drop(&mut self) {
+            let _ = terminal::disable_raw_mode();
+            let _ = execute!(stdout(), cursor::Show);
+        }
+    }
+
+    // Blocks for the next key press. Windows also reports key releases, which are
+    // skipped so every key counts once.
+    fn read_key() -> Option<KeyEvent> {
+        match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => Some(key),
+            _ => None,
+        }
+    }
+
+    // q, or Ctrl-C and Ctrl-D as in other terminal programs. Raw mode turns off the
+    // terminal's own Ctrl-C handling, so those arrive here as key presses.
+    fn is_quit_key(key: &KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Char('Q') => true,
+            KeyCode::Char('c') | KeyCode::Char('d') => {
+                key.modifiers.contains(KeyModifiers::CONTROL)
+            }
+            _ => false,
+        }
+    }
 
     #[derive(Parser)]
     #[command(author, version, about, long_about = None)]
     struct Cli {
         #[command(subcommand)]
         command: Option<Commands>,
+        /// Seed for the random number generator, to replay an earlier session
+        #[arg(long, global = true)]
+        seed: Option<u64>,
+        /// Drop the weakest fraction of combos from every range, e.g. 0.1 plays 10% tighter
+        #[arg(long, global = true)]
+        tighten: Option<f32>,
+        /// Only practice these spot types, e.g. --spot Open_BTN --spot BBDefense_CO
+        #[arg(long = "spot", global = true)]
+        spots: Vec<String>,
+        /// Only practice spots with hero in this seat, e.g. --position BTN
+        #[arg(long, value_parser = Position::from_str, global = true)]
+        position: Option<Position>,
+        /// Only practice open spots or only spots facing a raise
+        #[arg(long, value_name = "open|defense", value_parser = SpotKind::from_str, global = true)]
+        spot_type: Option<SpotKind>,
+        /// Don't load or update the all-time stats file
+        #[arg(long, global = true)]
+        no_lifetime_stats: bool,
+        /// Hide the RNG value until the question is answered
+        #[arg(long, global = true)]
+        hide_rng: bool,
+        /// Give no credit for frequency mistakes in the score
+        #[arg(long, global = true)]
+        strict: bool,
+        /// Play a timed round of this many seconds and report hands per minute
+        #[arg(long, value_name = "SECONDS", global = true)]
+        speed_round: Option<u64>,
+        /// Deal exactly N spots, then print the final stats and exit
+        #[arg(long, value_name = "N", global = true, value_parser = clap::value_parser!(u32).range(1..))]
+        num_hands: Option<u32>,
+        /// Show every spot's frequencies and the RNG's action up front; nothing is scored
+        #[arg(long, global = true)]
+        study: bool,
+        /// Continue the stats of the last session instead of starting from zero
+        #[arg(long, global = true)]
+        resume: bool,
+        /// Only deal the spots not mastered yet, counting the all-time stats, until every
+        /// spot is
+        #[arg(long, global = true)]
+        auto_focus: bool,
+        /// Write every answer of the session to this CSV file when the game ends, and the
+        /// per-spot and most-missed-hand breakdown next to it as PATH.breakdown.csv
+        #[arg(long, value_name = "PATH", global = true)]
+        export_csv: Option<std::path::PathBuf>,
     }
 
     #[derive(Subcommand, Default)]
@@ -26,34 +115,67 @@ mod unix_cli {
         },
         #[default]
         Game,
+        /// Write a markdown worksheet of random spots with an answer key
+        Worksheet {
+            #[arg(short = 'n', long, default_value_t = 20)]
+            questions: usize,
+            /// File to write; prints to stdout when omitted
+            #[arg(short = 'o', long)]
+            output: Option<std::path::PathBuf>,
+        },
+        /// Print a JSON Schema for ranges.toml, for editor validation
+        Schema,
+        /// Print the range string for the strongest PERCENT of all hands, e.g. 15
+        RangeFromPercent {
+            #[arg(value_name = "PERCENT")]
+            percent: f32,
+        },
+        /// Print a spot's configured range as the 13x13 grid, e.g. Open_BTN or BBDefense_CO,
+        /// or a range string such as "22+,A2s+,KQo:0.5"
+        ShowRange {
+            #[arg(value_name = "SPOT_OR_RANGE")]
+            spot_or_range: String,
+        },
+        /// Print the accuracy per spot and the most missed hands of the last session
+        Stats,
     }
 
     pub fn run() {
-        let cli = Cli::parse();
+        let mut cli = Cli::parse();
 
-        match cli.command.unwrap_or_default() {
+        match cli.command.take().unwrap_or_default() {
             Commands::CheckRange {
                 range_str,
                 hand_str,
             } => handle_check_range_command(&range_str, &hand_str),
-            Commands::Game => run_game_loop(),
+            Commands::Game => run_game_loop(&cli),
+            Commands::Worksheet { questions, output } => {
+                handle_worksheet_command(questions, cli.seed, output.as_deref())
+            }
+            Commands::Schema => print!("{}", config_json_schema()),
+            Commands::RangeFromPercent { percent } => handle_range_from_percent_command(percent),
+            Commands::ShowRange { spot_or_range } => handle_show_range_command(&spot_or_range),
+            Commands::Stats => handle_stats_command(),
         }
     }
 
-    fn run_game_loop() {
-        let mut stdout = stdout().into_raw_mode().unwrap();
-        let stdin = stdin();
+    fn run_game_loop(cli: &Cli) {
+        let spots = &cli.spots;
+        let mut use_lifetime_stats = !cli.no_lifetime_stats;
+        let hide_rng = cli.hide_rng;
+        let speed_round = cli.speed_round;
+        let _raw_mode = RawModeGuard::new().unwrap();
+        let mut stdout = stdout();
 
         write!(stdout, "--- Poker Preflop Trainer ---\r\n").unwrap();
         stdout.flush().unwrap();
 
-        let game_config = match load_config() {
+        let mut game_config = match load_config() {
             Ok(config) => config,
             Err(e) => {
                 write!(
                     stdout,
-                    "{}\r\n{}",
-                    termion::cursor::Show,
+                    "\r\n{}",
                     format!("Error loading configuration: {}", e).red()
                 )
                 .unwrap();
@@ -62,17 +184,149 @@ mod unix_cli {
             }
         };
 
+        if !spots.is_empty() {
+            match spots
+                .iter()
+                .map(|s| parse_spot_type_with_hint(s))
+                .collect::<Result<Vec<_>, String>>()
+            {
+                Ok(spot_types) => game_config.allowed_spot_types = spot_types,
+                Err(e) => {
+                    write!(
+                        stdout,
+                        "\r\n{}",
+                        format!("Error parsing --spot: {}", e).red()
+                    )
+                    .unwrap();
+                    stdout.flush().unwrap();
+                    return;
+                }
+            }
+        }
+
+        if cli.position.is_some() || cli.spot_type.is_some() {
+            let spot_types = game_config.filtered_spot_types(cli.position, cli.spot_type);
+            if spot_types.is_empty() {
+                write!(
+                    stdout,
+                    "\r\n{}\r\n",
+                    "No configured spot matches --position and --spot-type; check allowed_spot_types in ranges.toml"
+                        .red()
+                )
+                .unwrap();
+                stdout.flush().unwrap();
+                return;
+            }
+            game_config.allowed_spot_types = spot_types;
+        }
+
+        if let Some(fraction) = cli.tighten {
+            tighten_config(&mut game_config, fraction);
+            write!(
+                stdout,
+                "Playing {:.0}% tighter than the configured ranges.\r\n",
+                fraction * 100.0
+            )
+            .unwrap();
+        }
+
         write!(
             stdout,
-            "Configuration loaded successfully. Starting game...\r\n\r\n"
+            "Configuration loaded successfully. Starting game...\r\n(press ? during a question to explain the spot, h for a hint that skips scoring it)\r\n\r\n"
         )
         .unwrap();
         stdout.flush().unwrap();
 
-        let mut game = Game::new(game_config.clone());
+        game_config.strict_scoring |= cli.strict;
+        let strict = game_config.strict_scoring;
+        let seed = cli.seed.unwrap_or_else(rand::random);
+        let game = Game::new_with_seed(game_config.clone(), seed);
+        let mut session = match CumulativeStats::default_path() {
+            Some(path) if use_lifetime_stats => {
+                match TrainerSession::with_lifetime_stats(
+                    game.clone(),
+                    game_config.clone(),
+                    path.clone(),
+                ) {
+                    Ok(session) => session,
+                    Err(e) => {
+                        // Leave the file alone rather than replace it with this session
+                        write!(
+                            stdout,
+                            "{}\r\n",
+                            format!(
+                                "Could not load lifetime stats from {}, so they won't be updated: {}",
+                                path.display(),
+                                e
+                            )
+                            .red()
+                        )
+                        .unwrap();
+                        use_lifetime_stats = false;
+                        TrainerSession::new(game, game_config.clone())
+                    }
+                }
+            }
+            _ => TrainerSession::new(game, game_config.clone()),
+        };
+        session.set_auto_focus(cli.auto_focus);
+        let session_path = SessionStats::default_path();
+        if cli.resume {
+            match session_path.as_deref().map(SessionStats::load_json) {
+                Some(Ok(mut stats)) => {
+                    let dropped = stats.retain_config_spots(&game_config);
+                    if !dropped.is_empty() {
+                        let names: Vec<String> =
+                            dropped.iter().map(|s| s.to_config_string()).collect();
+                        write!(
+                            stdout,
+                            "{}\r\n",
+                            format!(
+                                "Skipping saved stats for spots not in the current config: {}",
+                                names.join(", ")
+                            )
+                            .yellow()
+                        )
+                        .unwrap();
+                    }
+                    write!(
+                        stdout,
+                        "Resuming the last session: {} answered so far.\r\n",
+                        stats.totals.overall.answered
+                    )
+                    .unwrap();
+                    for line in stats.breakdown_report() {
+                        write!(stdout, "{}\r\n", line).unwrap();
+                    }
+                    write!(stdout, "\r\n").unwrap();
+                    session.resume(stats);
+                }
+                Some(Err(e)) => {
+                    write!(
+                        stdout,
+                        "{}\r\n",
+                        format!("Could not resume the last session: {}", e).red()
+                    )
+                    .unwrap();
+                }
+                None => {}
+            }
+        }
+        if let Some(seconds) = speed_round {
+            write!(
+                stdout,
+                "Speed round: answer as many spots as you can in {}s.\r\n\r\n",
+                seconds
+            )
+            .unwrap();
+            session.speed_round(std::time::Duration::from_secs(seconds));
+        }
         let mut correct_answers = 0.0_f32;
         let mut total_questions = 0;
+        // Answered after a hint, so left out of the score
+        let mut hinted_questions = 0;
         let mut current_question_answered = true;
+        let mut current_question_hinted = false;
         let mut current_spot_details: Option<(
             preflop_trainer_core::SpotType,
             preflop_trainer_core::Hand,
@@ -81,60 +335,107 @@ mod unix_cli {
 
         loop {
             if current_question_answered {
+                if session.speed_round_over() {
+                    write!(stdout, "{}\r\n", "Time's up!".yellow()).unwrap();
+                    break;
+                }
+                if cli.num_hands == Some(total_questions) {
+                    write!(stdout, "{}\r\n", "All hands played!".yellow()).unwrap();
+                    break;
+                }
                 total_questions += 1;
-                match game.generate_random_spot() {
-                    Some((spot_type, hand, mixed_strategy_rng_value)) => {
-                        write!(stdout, "Question {}:\r\n", total_questions).unwrap();
+                match session.next_spot() {
+                    Ok((spot_type, hand, mixed_strategy_rng_value)) => {
+                        match cli.num_hands {
+                            Some(num_hands) => {
+                                write!(stdout, "Question {} of {}:\r\n", total_questions, num_hands)
+                                    .unwrap()
+                            }
+                            None => write!(stdout, "Question {}:\r\n", total_questions).unwrap(),
+                        }
+                        if let Some(remaining) = session.speed_round_remaining() {
+                            write!(stdout, "Time left: {}s\r\n", remaining.as_secs()).unwrap();
+                        }
                         write!(stdout, "Position: {}\r\n", format!("{}", spot_type).cyan())
                             .unwrap();
                         write!(stdout, "Hole Cards: {}\r\n", format!("{}", hand).yellow()).unwrap();
-                        write!(stdout, "RNG: {}\r\n", mixed_strategy_rng_value).unwrap();
+                        if !hide_rng {
+                            write!(stdout, "RNG: {}\r\n", mixed_strategy_rng_value).unwrap();
+                        }
 
-                        let actions_prompt = match spot_type {
-                            preflop_trainer_core::SpotType::Open { .. } => "(R)aise or (F)old? ",
-                            preflop_trainer_core::SpotType::BBDefense { .. } => {
-                                "(R)aise, (C)all, or (F)old? "
-                            }
-                        };
-                        write!(stdout, "{}", actions_prompt).unwrap();
+                        if cli.study {
+                            write!(
+                                stdout,
+                                "{}\r\n",
+                                hint_line(&game_config, spot_type, hand, mixed_strategy_rng_value)
+                            )
+                            .unwrap();
+                        }
+
+                        write!(stdout, "{}", actions_prompt(spot_type)).unwrap();
 
                         stdout.flush().unwrap();
                         current_spot_details = Some((spot_type, hand, mixed_strategy_rng_value));
                         current_question_answered = false;
+                        current_question_hinted = cli.study;
                     }
-                    None => {
-                        write!(stdout, "Reshuffling deck...\r\n").unwrap();
+                    Err(e) => {
+                        write!(
+                            stdout,
+                            "{}\r\n",
+                            format!("Could not generate a spot: {}", e).red()
+                        )
+                        .unwrap();
                         stdout.flush().unwrap();
                         total_questions -= 1;
-                        continue;
+                        break;
                     }
                 }
             }
 
-            if let Some(Ok(key)) = stdin.lock().keys().next() {
-                let user_action = match key {
-                    termion::event::Key::Char('r') | termion::event::Key::Char('R') => {
-                        Some(UserAction::Raise)
-                    }
-                    termion::event::Key::Char('f') | termion::event::Key::Char('F') => {
-                        Some(UserAction::Fold)
-                    }
-                    termion::event::Key::Char('c') | termion::event::Key::Char('C') => {
-                        Some(UserAction::Call)
-                    }
-                    termion::event::Key::Char('q') | termion::event::Key::Char('Q') => {
+            if let Some(key) = read_key() {
+                let user_action = match key.code {
+                    // Before the plain letters, so Ctrl-C isn't read as Call
+                    _ if is_quit_key(&key) => {
                         write!(stdout, "\r\nQuitting game.\r\n").unwrap();
                         if !current_question_answered {
                             total_questions -= 1;
                         }
                         break;
                     }
-                    termion::event::Key::Ctrl('c') | termion::event::Key::Ctrl('d') => {
-                        write!(stdout, "\r\nQuitting game.\r\n").unwrap();
-                        if !current_question_answered {
-                            total_questions -= 1;
+                    KeyCode::Char('r') | KeyCode::Char('R') => Some(UserAction::Raise),
+                    KeyCode::Char('f') | KeyCode::Char('F') => Some(UserAction::Fold),
+                    KeyCode::Char('c') | KeyCode::Char('C') => Some(UserAction::Call),
+                    KeyCode::Char('l') | KeyCode::Char('L') => Some(UserAction::Limp),
+                    KeyCode::Char('?') => {
+                        if let Some((spot_type, _, _)) = current_spot_details {
+                            write!(
+                                stdout,
+                                "\r\n{}\r\n{}",
+                                spot_type.description(),
+                                actions_prompt(spot_type)
+                            )
+                            .unwrap();
+                            stdout.flush().unwrap();
                         }
-                        break;
+                        None
+                    }
+                    KeyCode::Char('h') | KeyCode::Char('H') => {
+                        if !current_question_answered
+                            && let Some((spot_type, hand, mixed_strategy_rng_value)) =
+                                current_spot_details
+                        {
+                            write!(
+                                stdout,
+                                "\r\n{}\r\n{}",
+                                hint_line(&game_config, spot_type, hand, mixed_strategy_rng_value),
+                                actions_prompt(spot_type)
+                            )
+                            .unwrap();
+                            stdout.flush().unwrap();
+                            current_question_hinted = true;
+                        }
+                        None
                     }
                     _ => None,
                 };
@@ -143,37 +444,62 @@ mod unix_cli {
                     && !current_question_answered
                     && let Some((spot_type, hand, mixed_strategy_rng_value)) = current_spot_details
                 {
-                    let result = check_answer(
-                        &game_config,
-                        spot_type,
-                        hand,
-                        action,
-                        mixed_strategy_rng_value,
-                    );
-
+                    // A hinted answer gets feedback but stays out of the score and the stats
+                    let result = if current_question_hinted {
+                        hinted_questions += 1;
+                        check_answer_detailed(
+                            &game_config,
+                            spot_type,
+                            hand,
+                            action,
+                            mixed_strategy_rng_value,
+                        )
+                        .result
+                    } else {
+                        let result =
+                            session.answer(spot_type, hand, action, mixed_strategy_rng_value);
+                        correct_answers += answer_credit(result, strict);
+                        result
+                    };
                     match result {
                         AnswerResult::Correct => {
-                            correct_answers += 1.0;
                             write!(stdout, "{}\r\n", "Correct!".green()).unwrap();
                         }
                         AnswerResult::Wrong => {
                             write!(stdout, "{}\r\n", "Wrong.".red()).unwrap();
                         }
                         AnswerResult::FrequencyMistake => {
-                            correct_answers += 0.5;
                             write!(stdout, "{}\r\n", "Frequency mistake.".yellow()).unwrap();
                         }
                     }
 
-                    let percentage = if total_questions > 0 {
-                        (correct_answers / total_questions as f32) * 100.0
+                    if hide_rng {
+                        write!(stdout, "RNG was: {}\r\n", mixed_strategy_rng_value).unwrap();
+                    }
+
+                    if let Some(note) = game_config.note_for(HandNotation::from_hand(hand)) {
+                        write!(stdout, "Note: {}\r\n", note.cyan()).unwrap();
+                    }
+
+                    if let Some(feedback) = villain_range(&game_config, spot_type)
+                        .and_then(|villain| blocker_feedback(hand, villain))
+                    {
+                        write!(stdout, "{}\r\n", feedback).unwrap();
+                    }
+
+                    let scored_questions = total_questions - hinted_questions;
+                    let percentage = if scored_questions > 0 {
+                        (correct_answers / scored_questions as f32) * 100.0
                     } else {
                         0.0
                     };
+                    if current_question_hinted {
+                        write!(stdout, "{}\r\n", "Not scored (hinted).".dimmed()).unwrap();
+                    }
                     write!(
                         stdout,
                         "Score: {}/{} ({:.2}%)\r\n\r\n",
-                        correct_answers, total_questions, percentage
+                        correct_answers, scored_questions, percentage
                     )
                     .unwrap();
                     stdout.flush().unwrap();
@@ -185,33 +511,340 @@ mod unix_cli {
         }
 
         write!(stdout, "--- Game Over ---\r\n").unwrap();
+        let scored_questions = total_questions - hinted_questions;
         write!(
             stdout,
             "Final Score: {}/{} ({:.2}%)\r\n",
             correct_answers,
-            total_questions,
-            if total_questions > 0 {
-                (correct_answers / total_questions as f32) * 100.0
+            scored_questions,
+            if scored_questions > 0 {
+                (correct_answers / scored_questions as f32) * 100.0
             } else {
                 0.0
             }
         )
         .unwrap();
-        write!(stdout, "{}", termion::cursor::Show).unwrap();
+        if hinted_questions > 0 {
+            write!(stdout, "{} hinted hands not scored\r\n", hinted_questions).unwrap();
+        }
+        write!(
+            stdout,
+            "Seed: {} (replay this session with --seed {})\r\n",
+            seed, seed
+        )
+        .unwrap();
+        if session.stats().totals.overall.answered > 0 {
+            write!(stdout, "\r\nYour action vs the RNG's action:\r\n").unwrap();
+            for line in session.stats().confusion_report() {
+                write!(stdout, "{}\r\n", line).unwrap();
+            }
+            write!(stdout, "\r\n").unwrap();
+            for line in session.stats().breakdown_report() {
+                write!(stdout, "{}\r\n", line).unwrap();
+            }
+            write!(stdout, "\r\n").unwrap();
+        }
+        if let Some(report) = session.end_speed_round() {
+            write!(
+                stdout,
+                "Speed round: {} ({:.1} correct/min)\r\n",
+                report.summary(),
+                report.score_per_minute()
+            )
+            .unwrap();
+        }
+        if let Some(path) = &cli.export_csv {
+            let mut breakdown_path = path.clone().into_os_string();
+            breakdown_path.push(".breakdown.csv");
+            let breakdown_path = std::path::PathBuf::from(breakdown_path);
+            for (path, contents, what) in [
+                (path, session.stats().to_csv(), "Answers"),
+                (
+                    &breakdown_path,
+                    session.stats().breakdown_to_csv(),
+                    "Breakdown",
+                ),
+            ] {
+                match std::fs::write(path, contents) {
+                    Ok(()) => write!(stdout, "{} written to {}\r\n", what, path.display()).unwrap(),
+                    Err(e) => write!(
+                        stdout,
+                        "{}\r\n",
+                        format!("Could not write {}: {}", path.display(), e).red()
+                    )
+                    .unwrap(),
+                }
+            }
+        }
+        if let Some(path) = &session_path
+            && let Err(e) = session.stats().save_json(path)
+        {
+            write!(
+                stdout,
+                "{}\r\n",
+                format!("Could not save the session for --resume: {}", e).red()
+            )
+            .unwrap();
+        }
+        if use_lifetime_stats {
+            match session.finish() {
+                Ok(lifetime) => {
+                    write!(stdout, "Lifetime: {}\r\n", lifetime.summary_with(strict)).unwrap();
+                }
+                Err(e) => {
+                    write!(
+                        stdout,
+                        "{}\r\n",
+                        format!("Could not save lifetime stats: {}", e).red()
+                    )
+                    .unwrap();
+                }
+            }
+        }
         stdout.flush().unwrap();
     }
 
+    // The spot's frequencies and the action the RNG value dictates, e.g.
+    // "Hint: Raise 50%, Call 0%, Fold 50% (RNG 41: Raise)".
+    fn hint_line(
+        config: &GameConfig,
+        spot_type: preflop_trainer_core::SpotType,
+        hand: preflop_trainer_core::Hand,
+        mixed_strategy_rng_value: u8,
+    ) -> String {
+        let (raise_freq, call_freq, fold_freq) = get_action_frequencies(config, spot_type, hand);
+        let correct_action = get_correct_action(config, spot_type, hand, mixed_strategy_rng_value);
+        let mut frequencies = vec![format!("Raise {:.0}%", raise_freq * 100.0)];
+        if spot_type.is_facing_raise() || spot_type.passive_action() == UserAction::Limp {
+            frequencies.push(format!(
+                "{:?} {:.0}%",
+                spot_type.passive_action(),
+                call_freq * 100.0
+            ));
+        }
+        frequencies.push(format!("Fold {:.0}%", fold_freq * 100.0));
+        format!(
+            "Hint: {} (RNG {}: {:?})",
+            frequencies.join(", "),
+            mixed_strategy_rng_value,
+            correct_action
+        )
+        .cyan()
+        .to_string()
+    }
+
+    fn actions_prompt(spot_type: preflop_trainer_core::SpotType) -> &'static str {
+        if spot_type.is_facing_raise() {
+            "(R)aise, (C)all, or (F)old? "
+        } else if spot_type == preflop_trainer_core::SpotType::SbLimp {
+            "(R)aise, (L)imp, or (F)old? "
+        } else if matches!(spot_type, preflop_trainer_core::SpotType::PushFold { .. }) {
+            "(R)aise all in or (F)old? "
+        } else {
+            "(R)aise or (F)old? "
+        }
+    }
+
+    fn tighten_config(config: &mut GameConfig, fraction: f32) {
+        for range in config.ranges_mut() {
+            *range = tighten_range(range, fraction);
+        }
+    }
+
+    fn handle_worksheet_command(
+        questions: usize,
+        seed: Option<u64>,
+        output: Option<&std::path::Path>,
+    ) {
+        let config = match load_config() {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("{}", format!("Error loading configuration: {}", e).red());
+                return;
+            }
+        };
+
+        let seed = seed.unwrap_or_else(rand::random);
+        let mut game = Game::new_with_seed(config.clone(), seed);
+        let mut spots = Vec::with_capacity(questions);
+        for _ in 0..questions {
+            match game.generate_random_spot() {
+                Ok((spot_type, hand, _)) => spots.push((spot_type, hand)),
+                Err(e) => {
+                    eprintln!("{}", format!("Could not generate a spot: {}", e).red());
+                    return;
+                }
+            }
+        }
+
+        let worksheet = export_worksheet(&config, &spots);
+        match output {
+            Some(path) => match std::fs::write(path, worksheet) {
+                Ok(()) => println!(
+                    "Wrote {} questions to {} (seed {})",
+                    questions,
+                    path.display(),
+                    seed
+                ),
+                Err(e) => eprintln!(
+                    "{}",
+                    format!("Could not write {}: {}", path.display(), e).red()
+                ),
+            },
+            None => print!("{}", worksheet),
+        }
+    }
+
+    fn handle_range_from_percent_command(percent: f32) {
+        if !(0.0..=100.0).contains(&percent) {
+            eprintln!("{}", "Percent must be between 0 and 100".red());
+            return;
+        }
+        let range = percent_to_range(percent);
+        println!("{}", to_range_string(&range));
+        println!(
+            "({:.0} combos, {:.1}% of hands)",
+            range_combo_count(&range),
+            range_combo_count(&range) / 1326.0 * 100.0
+        );
+    }
+
+    fn handle_show_range_command(spot_or_range: &str) {
+        // Spot strings always have an underscore and range strings never do
+        if spot_or_range.contains('_') {
+            handle_show_spot_range(spot_or_range);
+            return;
+        }
+        let range = match RangeTable::from_str(spot_or_range) {
+            Ok(range) => range,
+            Err(e) => {
+                eprintln!("{}", format!("Invalid range: {}", e).red());
+                return;
+            }
+        };
+        print!("{}", range.to_grid_string());
+        println!(
+            "({:.0} combos, {:.1}% of hands)",
+            range.combo_count(),
+            range.combo_count() / 1326.0 * 100.0
+        );
+    }
+
+    // Raise in red and call (or limp) in blue, as separate grids
+    const RAISE_COLOR: (u8, u8, u8) = (230, 60, 60);
+    const CALL_COLOR: (u8, u8, u8) = (70, 130, 240);
+
+    fn handle_show_spot_range(spot_str: &str) {
+        let spot_type = match parse_spot_type_with_hint(spot_str) {
+            Ok(spot_type) => spot_type,
+            Err(e) => {
+                eprintln!("{}", format!("Unknown spot: {}", e).red());
+                return;
+            }
+        };
+        let config = match load_config() {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("{}", format!("Error loading configuration: {}", e).red());
+                return;
+            }
+        };
+
+        let mut raise_range = RangeTable::default();
+        let mut call_range = RangeTable::default();
+        for (hand_notation, frequencies) in all_action_frequencies(&config, spot_type) {
+            if frequencies.raise > 0.0 {
+                raise_range.insert(hand_notation, frequencies.raise);
+            }
+            if frequencies.call > 0.0 {
+                call_range.insert(hand_notation, frequencies.call);
+            }
+        }
+        if raise_range.is_empty() && call_range.is_empty() {
+            eprintln!(
+                "{}",
+                format!("No range configured for {} ({})", spot_str, spot_type).red()
+            );
+            return;
+        }
+
+        println!("{}", spot_type.to_string().cyan());
+        let passive_name = match spot_type.passive_action() {
+            UserAction::Limp => "Limp",
+            _ => "Call",
+        };
+        let mut grids = vec![("Raise", &raise_range, RAISE_COLOR)];
+        if spot_type.is_facing_raise() || spot_type.passive_action() == UserAction::Limp {
+            grids.push((passive_name, &call_range, CALL_COLOR));
+        }
+        for (name, range, color) in grids {
+            println!(
+                "\n{} ({:.0} combos, {:.1}% of hands):",
+                name,
+                range.combo_count(),
+                range.combo_count() / 1326.0 * 100.0
+            );
+            print!(
+                "{}",
+                range.to_grid_string_with(|_, frequency, cell| frequency_colored(
+                    cell, frequency, color
+                ))
+            );
+        }
+    }
+
+    // The cell in `color` at full strength for a 100% hand, fading towards grey as the
+    // frequency drops; hands that aren't played are dimmed.
+    fn frequency_colored(cell: String, frequency: f32, (r, g, b): (u8, u8, u8)) -> String {
+        if frequency <= 0.0 {
+            return cell.dimmed().to_string();
+        }
+        let fade = |channel: u8| (96.0 + (channel as f32 - 96.0) * frequency).round() as u8;
+        cell.truecolor(fade(r), fade(g), fade(b)).to_string()
+    }
+
+    fn handle_stats_command() {
+        let stats = match SessionStats::default_path()
+            .as_deref()
+            .map(SessionStats::load_json)
+        {
+            Some(Ok(stats)) => stats,
+            Some(Err(e)) => {
+                eprintln!(
+                    "{}",
+                    format!("Could not load the last session: {}", e).red()
+                );
+                return;
+            }
+            None => {
+                eprintln!(
+                    "{}",
+                    "No config directory to load the last session from".red()
+                );
+                return;
+            }
+        };
+        let overall = stats.totals.overall;
+        println!(
+            "Last session: {}/{} ({:.2}%)",
+            overall.score,
+            overall.answered,
+            overall.accuracy()
+        );
+        for line in stats.breakdown_report() {
+            println!("{}", line);
+        }
+    }
+
     fn handle_check_range_command(range_str: &str, hand_str: &str) {
-        let mut stdout = stdout().into_raw_mode().unwrap();
-        let _stdin = stdin();
+        let mut stdout = stdout();
 
         let _game_config = match load_config() {
             Ok(config) => config,
             Err(e) => {
                 write!(
                     stdout,
-                    "{}\r\n{}",
-                    termion::cursor::Show,
+                    "\r\n{}",
                     format!("Error loading configuration: {}", e).red()
                 )
                 .unwrap();
@@ -234,7 +867,11 @@ mod unix_cli {
             }
         };
 
-        let hand_notation = match preflop_trainer_core::HandNotation::from_str(hand_str) {
+        // Either a notation ("AKs") or concrete cards ("AsKs")
+        let hand_notation = match preflop_trainer_core::Hand::from_str(hand_str)
+            .map(preflop_trainer_core::HandNotation::from_hand)
+            .or_else(|_| preflop_trainer_core::HandNotation::from_str(hand_str))
+        {
             Ok(hn) => hn,
             Err(e) => {
                 write!(
@@ -248,35 +885,27 @@ mod unix_cli {
             }
         };
 
-        match range_map.get(&hand_notation) {
-            Some(&frequency) => write!(
+        if range_map.contains(hand_notation) {
+            write!(
                 stdout,
                 "Hand {} is in range with frequency: {:.2}%\r\n",
                 hand_str.yellow(),
-                frequency * 100.0
+                range_map.frequency(hand_notation) * 100.0
             )
-            .unwrap(),
-            None => write!(
+            .unwrap();
+        } else {
+            write!(
                 stdout,
                 "Hand {} is {} in range.\r\n",
                 hand_str.yellow(),
                 "NOT".red()
             )
-            .unwrap(),
+            .unwrap();
         }
-        write!(stdout, "{}", termion::cursor::Show).unwrap();
         stdout.flush().unwrap();
     }
 }
 
-// Non-Unix stub so the crate builds on Windows for workspace checks
-#[cfg(not(unix))]
-fn main() {
-    println!("preflop-trainer-cli: CLI is only supported on Unix-like systems. Skipping CLI.");
-}
-
-// On Unix, call the unix runner
-#[cfg(unix)]
 fn main() {
-    unix_cli::run();
+    cli::run();
 }
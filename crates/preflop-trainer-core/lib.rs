@@ -4,18 +4,299 @@
 #[macro_use]
 extern crate lazy_static;
 
-use rand::Rng;
 use rand::prelude::IndexedRandom; // Needed for .choose() method
-use rand::rngs::ThreadRng;
+use rand::rngs::{StdRng, ThreadRng};
 use rand::seq::SliceRandom;
-use serde::Deserialize;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::collections::HashMap; // Add HashMap for uniqueness checks in tests
+use std::collections::VecDeque;
 use std::fmt;
 use std::fs;
 use std::str::FromStr;
+use std::time::{Duration, Instant};
 
 lazy_static! {
-    static ref EMPTY_HAND_RANGE: HashMap<HandNotation, f32> = HashMap::new();
+    static ref EMPTY_HAND_RANGE: RangeTable = RangeTable::default();
+    static ref HANDS_BY_STRENGTH: Vec<HandNotation> = HAND_STRENGTH_ORDER
+        .iter()
+        .map(|s| HandNotation::from_str(s).expect("Invalid hand in HAND_STRENGTH_ORDER"))
+        .collect();
+    static ref HAND_STRENGTH_RANKS: HashMap<HandNotation, usize> = HANDS_BY_STRENGTH
+        .iter()
+        .enumerate()
+        .map(|(rank, &hn)| (hn, rank))
+        .collect();
+}
+
+// All 169 starting hands from strongest to weakest, ordered by all-in equity against a
+// random hand.
+const HAND_STRENGTH_ORDER: [&str; 169] = [
+    "AA", "KK", "QQ", "JJ", "TT", "99", "88", "AKs", "77", "AQs", "AJs", "AKo", "ATs", "AQo",
+    "AJo", "KQs", "66", "A9s", "ATo", "KJs", "A8s", "KTs", "KQo", "A7s", "A9o", "KJo", "55", "QJs",
+    "K9s", "A5s", "A6s", "A8o", "KTo", "QTs", "A4s", "A7o", "K8s", "A3s", "QJo", "K9o", "A5o",
+    "A6o", "Q9s", "K7s", "JTs", "A2s", "QTo", "44", "A4o", "K6s", "K8o", "Q8s", "A3o", "K5s",
+    "J9s", "Q9o", "JTo", "K7o", "A2o", "K4s", "Q7s", "K6o", "K3s", "T9s", "J8s", "33", "Q6s",
+    "Q8o", "K5o", "J9o", "K2s", "Q5s", "T8s", "K4o", "J7s", "Q4s", "Q7o", "T9o", "J8o", "K3o",
+    "Q6o", "Q3s", "98s", "T7s", "J6s", "K2o", "22", "Q2s", "Q5o", "J5s", "T8o", "J7o", "Q4o",
+    "97s", "J4s", "T6s", "J3s", "Q3o", "98o", "87s", "T7o", "J6o", "96s", "J2s", "Q2o", "T5s",
+    "J5o", "T4s", "97o", "86s", "J4o", "T6o", "95s", "T3s", "76s", "J3o", "87o", "T2s", "85s",
+    "96o", "J2o", "T5o", "94s", "75s", "T4o", "93s", "86o", "65s", "84s", "95o", "T3o", "92s",
+    "76o", "74s", "T2o", "54s", "85o", "64s", "83s", "94o", "75o", "82s", "73s", "93o", "65o",
+    "53s", "63s", "84o", "92o", "43s", "74o", "72s", "54o", "64o", "52s", "62s", "83o", "42s",
+    "82o", "73o", "53o", "63o", "32s", "43o", "72o", "52o", "62o", "42o", "32o",
+];
+
+// Tolerance used when comparing frequencies, e.g. raise 0.5 + call 0.5 may come out
+// as 0.99999994 or 1.0000001 depending on how the values were produced, and an
+// imported 0.999 is a pure raise. Half a percent, below what a 0-99 RNG value resolves.
+const FREQUENCY_EPSILON: f32 = 0.005;
+
+// A frequency within FREQUENCY_EPSILON of 0 or 1 is treated as exactly that.
+fn snap_frequency(freq: f32) -> f32 {
+    if freq <= FREQUENCY_EPSILON {
+        0.0
+    } else if freq >= 1.0 - FREQUENCY_EPSILON {
+        1.0
+    } else {
+        freq
+    }
+}
+
+// --- Errors ---
+
+// Functions with a narrower error type, like `load_config` (ConfigError) and
+// `parse_range_str` (RangeParseError), keep it; `?` converts them into a CoreError.
+#[derive(Debug)]
+pub enum CoreError {
+    // The config file is malformed or names unknown positions or spot types
+    Config(ConfigError),
+    // A range string could not be parsed
+    Parse(RangeParseError),
+    Io(std::io::Error),
+    // The config can't produce a spot, e.g. no allowed spot types
+    SpotGeneration(String),
+}
+
+impl fmt::Display for CoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CoreError::Config(e) => write!(f, "{}", e),
+            CoreError::Parse(e) => write!(f, "{}", e),
+            CoreError::Io(e) => write!(f, "{}", e),
+            CoreError::SpotGeneration(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for CoreError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CoreError::Config(e) => Some(e),
+            CoreError::Parse(e) => Some(e),
+            CoreError::Io(e) => Some(e),
+            CoreError::SpotGeneration(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for CoreError {
+    fn from(e: std::io::Error) -> Self {
+        CoreError::Io(e)
+    }
+}
+
+impl From<toml::de::Error> for CoreError {
+    fn from(e: toml::de::Error) -> Self {
+        CoreError::Config(ConfigError::TomlParse(e))
+    }
+}
+
+impl From<ConfigError> for CoreError {
+    fn from(e: ConfigError) -> Self {
+        CoreError::Config(e)
+    }
+}
+
+// Why `load_config` or `parse_config_str` couldn't build a GameConfig.
+#[derive(Debug)]
+pub enum ConfigError {
+    // ranges.toml couldn't be found, created or read
+    Io(std::io::Error),
+    // Not valid TOML, or a table is missing a field such as `range`
+    TomlParse(toml::de::Error),
+    // The config couldn't be written back out as TOML
+    TomlSerialize(toml::ser::Error),
+    // A table key names a seat that doesn't exist, e.g. "XX"
+    UnknownPosition(String),
+    // An `allowed_spot_types` entry isn't a spot type; the message lists the valid ones
+    UnknownSpotType(String),
+    InvalidRange(RangeParseError),
+    // Any other bad value, e.g. a malformed key or a listed spot without a range
+    Invalid(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io(e) => write!(f, "{}", e),
+            ConfigError::TomlParse(e) => write!(f, "{}", e),
+            ConfigError::TomlSerialize(e) => write!(f, "{}", e),
+            ConfigError::UnknownPosition(position) => write!(f, "Invalid position: {}", position),
+            ConfigError::UnknownSpotType(msg) | ConfigError::Invalid(msg) => write!(f, "{}", msg),
+            ConfigError::InvalidRange(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Io(e) => Some(e),
+            ConfigError::TomlParse(e) => Some(e),
+            ConfigError::TomlSerialize(e) => Some(e),
+            ConfigError::InvalidRange(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ConfigError {
+    fn from(e: std::io::Error) -> Self {
+        ConfigError::Io(e)
+    }
+}
+
+impl From<toml::de::Error> for ConfigError {
+    fn from(e: toml::de::Error) -> Self {
+        ConfigError::TomlParse(e)
+    }
+}
+
+impl From<toml::ser::Error> for ConfigError {
+    fn from(e: toml::ser::Error) -> Self {
+        ConfigError::TomlSerialize(e)
+    }
+}
+
+impl From<RangeParseError> for ConfigError {
+    fn from(e: RangeParseError) -> Self {
+        ConfigError::InvalidRange(e)
+    }
+}
+
+impl From<RangeParseError> for CoreError {
+    fn from(e: RangeParseError) -> Self {
+        CoreError::Parse(e)
+    }
+}
+
+// Why `parse_range_str` rejected a range string, with the offending token.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RangeParseError {
+    // The token isn't a hand, run or group, e.g. "AKx" or "A5s-K2s"
+    InvalidHand {
+        token: String,
+        reason: String,
+    },
+    // The text after ':' isn't a number
+    InvalidFrequency {
+        token: String,
+        source: std::num::ParseFloatError,
+    },
+    // A frequency outside [0, 1], e.g. "AA:1.5" or "AA:150%"
+    FrequencyOutOfRange {
+        token: String,
+        frequency: f32,
+    },
+    // The same hand listed twice with different frequencies
+    DuplicateHand {
+        hand: HandNotation,
+        first: f32,
+        second: f32,
+    },
+    // Nothing between two separators, e.g. "AA,,KK" or a trailing '&'
+    EmptyToken {
+        field: String,
+    },
+    // A '-' removal field with a frequency, e.g. "-22:0.5"
+    RemovalWithFrequency {
+        field: String,
+    },
+    // A "top:" field without a percentage from 0 to 100, e.g. "top:lots"
+    InvalidPercent {
+        field: String,
+    },
+    // A pasted 13x13 grid with a different number of rows
+    GridRowCount {
+        rows: usize,
+    },
+    // A grid row (numbered from 1) without 13 cells, or with a cell labelled as another
+    // hand than its position holds
+    InvalidGridRow {
+        row: usize,
+        reason: String,
+    },
+}
+
+impl fmt::Display for RangeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RangeParseError::InvalidHand { token, reason } => {
+                write!(f, "{} (in \"{}\")", reason, token)
+            }
+            RangeParseError::InvalidFrequency { token, source } => {
+                write!(f, "Invalid frequency in \"{}\": {}", token, source)
+            }
+            RangeParseError::FrequencyOutOfRange { token, frequency } => write!(
+                f,
+                "Frequency {} in \"{}\" is outside 0 to 1 (0% to 100%)",
+                frequency, token
+            ),
+            RangeParseError::DuplicateHand {
+                hand,
+                first,
+                second,
+            } => write!(
+                f,
+                "{} is listed with two frequencies: {} and {}",
+                hand, first, second
+            ),
+            RangeParseError::EmptyToken { field } if field.is_empty() => write!(
+                f,
+                "Empty range entry (two commas in a row, or a leading or trailing comma)"
+            ),
+            RangeParseError::EmptyToken { field } => {
+                write!(f, "Empty hand token in \"{}\"", field)
+            }
+            RangeParseError::RemovalWithFrequency { field } => {
+                write!(f, "Removal takes no frequency: {}", field)
+            }
+            RangeParseError::InvalidPercent { field } => {
+                write!(
+                    f,
+                    "Invalid percentage in \"{}\" (expected e.g. top:15%)",
+                    field
+                )
+            }
+            RangeParseError::GridRowCount { rows } => {
+                write!(f, "A range grid has 13 rows, found {}", rows)
+            }
+            RangeParseError::InvalidGridRow { row, reason } => {
+                write!(f, "Grid row {}: {}", row, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RangeParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RangeParseError::InvalidFrequency { source, .. } => Some(source),
+            _ => None,
+        }
+    }
 }
 
 // --- Data Structures for Poker Concepts ---
@@ -113,6 +394,33 @@ impl fmt::Display for Rank {
     }
 }
 
+impl FromStr for Rank {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Rank::from_char(c),
+            _ => Err(format!("Invalid rank: {}", s)),
+        }
+    }
+}
+
+// Serialized as the rank character, "T"
+impl Serialize for Rank {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Rank {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Suit {
     Spades,
@@ -124,6 +432,16 @@ pub enum Suit {
 impl Suit {
     pub const VALUES: [Self; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
 
+    pub fn from_char(c: char) -> Result<Self, String> {
+        match c {
+            's' => Ok(Suit::Spades),
+            'h' => Ok(Suit::Hearts),
+            'd' => Ok(Suit::Diamonds),
+            'c' => Ok(Suit::Clubs),
+            _ => Err(format!("Invalid suit character: {}", c)),
+        }
+    }
+
     pub fn to_char_lower(&self) -> char {
         match self {
             Suit::Spades => 's',
@@ -136,6 +454,16 @@ impl Suit {
     pub fn to_asset_string(&self) -> String {
         format!("suit_{}", self.to_char_lower())
     }
+
+    // Unicode pip, used when a suit image can't be displayed.
+    pub fn to_symbol(&self) -> char {
+        match self {
+            Suit::Spades => '♠',
+            Suit::Hearts => '♥',
+            Suit::Diamonds => '♦',
+            Suit::Clubs => '♣',
+        }
+    }
 }
 
 impl fmt::Display for Suit {
@@ -150,6 +478,118 @@ impl fmt::Display for Suit {
     }
 }
 
+impl FromStr for Suit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Suit::from_char(c),
+            _ => Err(format!("Invalid suit: {}", s)),
+        }
+    }
+}
+
+// Serialized as the suit letter, "s"
+impl Serialize for Suit {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Suit {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+// --- Card Colors ---
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RgbColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl RgbColor {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        RgbColor { r, g, b }
+    }
+}
+
+// Parses "#rrggbb" (the '#' is optional).
+impl FromStr for RgbColor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.strip_prefix('#').unwrap_or(s);
+        let channel = |i: usize| {
+            hex.get(i..i + 2)
+                .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+        };
+        match (hex.len(), channel(0), channel(2), channel(4)) {
+            (6, Some(r), Some(g), Some(b)) => Ok(RgbColor { r, g, b }),
+            _ => Err(format!("Invalid color: {} (expected #rrggbb)", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CardColorScheme {
+    // Red hearts and diamonds, black spades and clubs
+    TwoColor,
+    // Black spades, red hearts, blue diamonds, green clubs
+    #[default]
+    FourColor,
+}
+
+impl CardColorScheme {
+    pub fn suit_color(&self, suit: Suit) -> RgbColor {
+        match (self, suit) {
+            (_, Suit::Spades) => RgbColor::new(0, 0, 0),
+            (_, Suit::Hearts) => RgbColor::new(255, 0, 0),
+            (CardColorScheme::TwoColor, Suit::Diamonds) => RgbColor::new(255, 0, 0),
+            (CardColorScheme::TwoColor, Suit::Clubs) => RgbColor::new(0, 0, 0),
+            (CardColorScheme::FourColor, Suit::Diamonds) => RgbColor::new(0, 0, 255),
+            (CardColorScheme::FourColor, Suit::Clubs) => RgbColor::new(0, 128, 0),
+        }
+    }
+}
+
+impl FromStr for CardColorScheme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "two_color" => Ok(CardColorScheme::TwoColor),
+            "four_color" => Ok(CardColorScheme::FourColor),
+            _ => Err(format!(
+                "Invalid card_colors: {} (expected two_color or four_color)",
+                s
+            )),
+        }
+    }
+}
+
+// Card colors from the "[theme]" table: a scheme plus optional per-suit overrides.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ThemeConfig {
+    pub card_colors: CardColorScheme,
+    pub suit_colors: HashMap<Suit, RgbColor>,
+}
+
+impl ThemeConfig {
+    pub fn suit_color(&self, suit: Suit) -> RgbColor {
+        self.suit_colors
+            .get(&suit)
+            .copied()
+            .unwrap_or_else(|| self.card_colors.suit_color(suit))
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Card {
     pub rank: Rank,
@@ -162,19 +602,72 @@ impl fmt::Display for Card {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+// Parses the `Display` form, e.g. "As" or "Td". The suit may be upper case ("AS").
+impl FromStr for Card {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(rank), Some(suit), None) => Ok(Card {
+                rank: Rank::from_char(rank).map_err(|e| format!("{} in card {}", e, s))?,
+                suit: Suit::from_char(suit.to_ascii_lowercase())
+                    .map_err(|e| format!("{} in card {} (expected s, h, d or c)", e, s))?,
+            }),
+            _ => Err(format!(
+                "Invalid card: {:?} (expected a rank and a suit, e.g. As or Td)",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Hand {
     pub card1: Card,
     pub card2: Card,
 }
 
+impl Hand {
+    pub fn shares_card_with(&self, other: &Hand) -> bool {
+        [self.card1, self.card2]
+            .iter()
+            .any(|card| *card == other.card1 || *card == other.card2)
+    }
+}
+
 impl fmt::Display for Hand {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{} {}", self.card1, self.card2)
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+// Parses two distinct cards, e.g. "AsKh", or the `Display` form "As Kh".
+impl FromStr for Hand {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cards: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        if cards.chars().count() != 4 || !cards.is_ascii() {
+            return Err(format!(
+                "Invalid hand: {:?} (expected two cards, e.g. AsKh)",
+                s
+            ));
+        }
+        let hand = Hand {
+            card1: cards[..2].parse()?,
+            card2: cards[2..].parse()?,
+        };
+        if hand.card1 == hand.card2 {
+            return Err(format!("Hand uses {} twice", hand.card1));
+        }
+        Ok(hand)
+    }
+}
+
+// Serialized as "pair", "suited" or "offsuit", like the lifetime stats keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum HandType {
     Pair,
     Suited,
@@ -189,6 +682,73 @@ pub struct HandNotation {
 }
 
 impl HandNotation {
+    // The hand in a cell of the 13x13 grid. Rows and columns run from ace down to two;
+    // pairs are on the diagonal, suited hands above it and offsuit hands below it, so
+    // (0, 1) is AKs and (1, 0) is AKo.
+    pub fn from_grid_position(row: usize, col: usize) -> HandNotation {
+        let row_rank = Rank::VALUES[Rank::VALUES.len() - 1 - row];
+        let col_rank = Rank::VALUES[Rank::VALUES.len() - 1 - col];
+        let hand_type = match row.cmp(&col) {
+            std::cmp::Ordering::Equal => HandType::Pair,
+            std::cmp::Ordering::Less => HandType::Suited,
+            std::cmp::Ordering::Greater => HandType::Offsuit,
+        };
+        HandNotation {
+            rank1: row_rank.max(col_rank),
+            rank2: row_rank.min(col_rank),
+            hand_type,
+        }
+    }
+
+    // The (row, col) of this hand in the grid of `from_grid_position`
+    pub fn grid_position(&self) -> (usize, usize) {
+        let index = |rank: Rank| Rank::VALUES.len() - 1 - rank as usize;
+        let (high, low) = (
+            index(self.rank1.max(self.rank2)),
+            index(self.rank1.min(self.rank2)),
+        );
+        match self.hand_type {
+            HandType::Pair | HandType::Suited => (high, low),
+            HandType::Offsuit => (low, high),
+        }
+    }
+
+    // Number of concrete two-card combinations: 6 for a pair, 4 suited, 12 offsuit.
+    pub fn combo_count(&self) -> u8 {
+        match self.hand_type {
+            HandType::Pair => 6,
+            HandType::Suited => 4,
+            HandType::Offsuit => 12,
+        }
+    }
+
+    // Every concrete hand this notation stands for.
+    pub fn combos(&self) -> Vec<Hand> {
+        let mut combos = Vec::with_capacity(self.combo_count() as usize);
+        for (i, &suit1) in Suit::VALUES.iter().enumerate() {
+            for (j, &suit2) in Suit::VALUES.iter().enumerate() {
+                let keep = match self.hand_type {
+                    HandType::Pair => i < j,
+                    HandType::Suited => i == j,
+                    HandType::Offsuit => i != j,
+                };
+                if keep {
+                    combos.push(Hand {
+                        card1: Card {
+                            rank: self.rank1,
+                            suit: suit1,
+                        },
+                        card2: Card {
+                            rank: self.rank2,
+                            suit: suit2,
+                        },
+                    });
+                }
+            }
+        }
+        combos
+    }
+
     pub fn from_hand(hand: Hand) -> Self {
         let rank1 = std::cmp::max(hand.card1.rank, hand.card2.rank);
         let rank2 = std::cmp::min(hand.card1.rank, hand.card2.rank);
@@ -207,6 +767,34 @@ impl HandNotation {
     }
 }
 
+// Canonical notation, high rank first: "AA", "AKs", "T9o".
+impl fmt::Display for HandNotation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let high = std::cmp::max(self.rank1, self.rank2);
+        let low = std::cmp::min(self.rank1, self.rank2);
+        match self.hand_type {
+            HandType::Pair => write!(f, "{}{}", high, low),
+            HandType::Suited => write!(f, "{}{}s", high, low),
+            HandType::Offsuit => write!(f, "{}{}o", high, low),
+        }
+    }
+}
+
+// Serialized as the notation string, "AKs", so it can key a JSON object
+impl Serialize for HandNotation {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for HandNotation {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 impl FromStr for HandNotation {
     type Err = String;
 
@@ -254,6 +842,15 @@ impl FromStr for HandNotation {
     }
 }
 
+// All 169 notations in the row-major order of the 13x13 grid: AA, AKs, AQs, ... A2s,
+// then AKo, KK, KQs, ... down to 22
+pub fn get_all_possible_hand_notations_grid_order() -> Vec<HandNotation> {
+    let size = Rank::VALUES.len();
+    (0..size * size)
+        .map(|cell| HandNotation::from_grid_position(cell / size, cell % size))
+        .collect()
+}
+
 // Helper function to generate all 169 unique HandNotations
 pub fn get_all_possible_hand_notations() -> Vec<HandNotation> {
     let mut hand_notations = Vec::new();
@@ -293,27 +890,82 @@ pub fn get_all_possible_hand_notations() -> Vec<HandNotation> {
 // --- Configuration Structures ---
 
 // New struct for BBDefense ranges
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct BBDefensePositionDetail {
     pub call_range: String,
     pub raise_range: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct GenericConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub allowed_spot_types: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coverage_first: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict_scoring: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub combo_weighting: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quiz_folds: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub boundary_favors: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table_size: Option<TableSizeToml>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stack_depth: Option<StackDepth>,
+}
+
+// `table_size` takes a seat count (2, 6 or 9) or a name ("heads_up", "six_max",
+// "nine_max")
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum TableSizeToml {
+    Seats(usize),
+    Name(String),
 }
 
-#[derive(Debug, Deserialize)]
+// Tables are BTreeMaps so `GameConfig::to_toml_string` writes them in a stable order
+#[derive(Debug, Deserialize, Serialize)]
 pub struct TomlConfig {
     #[serde(rename = "unopened_raise")]
-    pub unopened_raise: HashMap<String, PositionDetail>,
-    #[serde(rename = "bb_defense")]
-    pub bb_defense: Option<HashMap<String, BBDefensePositionDetail>>, // Use new struct here
+    pub unopened_raise: BTreeMap<String, PositionDetail>,
+    #[serde(rename = "bb_defense", skip_serializing_if = "Option::is_none")]
+    pub bb_defense: Option<BTreeMap<String, BBDefensePositionDetail>>, // Use new struct here
+    // Keyed by "<Opener>_<3-Bettor>", e.g. "CO_BTN"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vs_3bet: Option<BTreeMap<String, BBDefensePositionDetail>>,
+    // Keyed by "<Hero>_<Opener>", e.g. "BTN_CO"; "BB_<Opener>" is the same as [bb_defense]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub defense: Option<BTreeMap<String, BBDefensePositionDetail>>,
+    // Keyed by "<Opener>_<Caller>", e.g. "CO_BTN"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub squeeze: Option<BTreeMap<String, BBDefensePositionDetail>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub generic: Option<GenericConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<ThemeToml>,
+    // The small blind's limp range; adds the raise/limp/fold SB spot
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sb_limp: Option<PositionDetail>,
+    // Keyed by "<Position>_<Stack>", e.g. "BTN_10"; the range is the shove range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub push_fold: Option<BTreeMap<String, PositionDetail>>,
+    // Hand notation ("A5s") to a study note
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct ThemeToml {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub card_colors: Option<String>,
+    // Suit name ("spades", "hearts", "diamonds", "clubs") to "#rrggbb"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suit_colors: Option<BTreeMap<String, String>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct PositionDetail {
     pub range: String, // Keep this for unopened_raise
 }
@@ -321,7 +973,13 @@ pub struct PositionDetail {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Position {
     UTG,
+    // UTG+1, UTG+2, lojack and hijack only exist at a 9-max table
+    UTG1,
+    UTG2,
+    LJ,
+    // The 6-max seat between UTG and the cutoff
     MP,
+    HJ,
     CO,
     BTN,
     SB,
@@ -329,7 +987,21 @@ pub enum Position {
 }
 
 impl Position {
-    pub const VALUES: [Self; 6] = [
+    // Every seat of both table sizes, in the order they act preflop
+    pub const VALUES: [Self; 10] = [
+        Position::UTG,
+        Position::UTG1,
+        Position::UTG2,
+        Position::LJ,
+        Position::MP,
+        Position::HJ,
+        Position::CO,
+        Position::BTN,
+        Position::SB,
+        Position::BB,
+    ];
+
+    const SIX_MAX: [Self; 6] = [
         Position::UTG,
         Position::MP,
         Position::CO,
@@ -338,11 +1010,38 @@ impl Position {
         Position::BB,
     ];
 
+    const NINE_MAX: [Self; 9] = [
+        Position::UTG,
+        Position::UTG1,
+        Position::UTG2,
+        Position::LJ,
+        Position::HJ,
+        Position::CO,
+        Position::BTN,
+        Position::SB,
+        Position::BB,
+    ];
+
+    // Heads-up, the small blind is the button
+    const HEADS_UP: [Self; 2] = [Position::SB, Position::BB];
+
+    // The seats of a table in preflop order. 2 and 9 give the heads-up and 9-max seats;
+    // every other size gets the 6-max ones, so check sizes with `is_supported_table_size`
+    // first.
+    pub fn for_table_size(n: usize) -> &'static [Position] {
+        match n {
+            2 => &Self::HEADS_UP,
+            9 => &Self::NINE_MAX,
+            _ => &Self::SIX_MAX,
+        }
+    }
+
+    pub fn is_supported_table_size(n: usize) -> bool {
+        matches!(n, 2 | 6 | 9)
+    }
+
     pub fn is_opener(&self) -> bool {
-        matches!(
-            self,
-            Position::UTG | Position::MP | Position::CO | Position::BTN | Position::SB
-        )
+        !matches!(self, Position::BB)
     }
 }
 
@@ -351,7 +1050,11 @@ impl FromStr for Position {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_uppercase().as_str() {
             "UTG" => Ok(Position::UTG),
+            "UTG1" | "UTG+1" => Ok(Position::UTG1),
+            "UTG2" | "UTG+2" => Ok(Position::UTG2),
+            "LJ" => Ok(Position::LJ),
             "MP" => Ok(Position::MP),
+            "HJ" => Ok(Position::HJ),
             "CO" => Ok(Position::CO),
             "BTN" => Ok(Position::BTN),
             "SB" => Ok(Position::SB),
@@ -361,11 +1064,30 @@ impl FromStr for Position {
     }
 }
 
+// Serialized as the config string, "BTN", rather than the display name
+impl Serialize for Position {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(position_config_str(*self))
+    }
+}
+
+impl<'de> Deserialize<'de> for Position {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 impl fmt::Display for Position {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let s = match self {
             Position::UTG => "UTG",
+            Position::UTG1 => "UTG+1",
+            Position::UTG2 => "UTG+2",
+            Position::LJ => "Lojack",
             Position::MP => "MP",
+            Position::HJ => "Hijack",
             Position::CO => "CO",
             Position::BTN => "Button",
             Position::SB => "Small Blind",
@@ -375,643 +1097,4837 @@ impl fmt::Display for Position {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum SpotType {
-    Open { position: Position },
-    BBDefense { opener_position: Position },
-}
+// An opening raise size, stored in tenths of a big blind (25 = 2.5bb).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct OpenSize(pub u16);
 
-impl fmt::Display for SpotType {
+impl fmt::Display for OpenSize {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            SpotType::Open { position } => write!(f, "Open from {}", position),
-            SpotType::BBDefense { opener_position } => write!(f, "BB vs {} Open", opener_position),
+        if self.0.is_multiple_of(10) {
+            write!(f, "{}bb", self.0 / 10)
+        } else {
+            write!(f, "{}.{}bb", self.0 / 10, self.0 % 10)
         }
     }
 }
 
-impl FromStr for SpotType {
+impl FromStr for OpenSize {
     type Err = String;
-
+    // Accepts "2.5", "3" or "2.5bb".
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<&str> = s.split('_').collect();
-        if parts.len() != 2 {
-            return Err(format!("Invalid SpotType string format: {}", s));
+        let number = s.trim().trim_end_matches("bb");
+        let bb: f32 = number
+            .parse()
+            .map_err(|_| format!("Invalid open size: {}", s))?;
+        let tenths = (bb * 10.0).round();
+        if !(1.0..=u16::MAX as f32).contains(&tenths) || (bb * 10.0 - tenths).abs() > 1e-3 {
+            return Err(format!("Invalid open size: {}", s));
         }
+        Ok(OpenSize(tenths as u16))
+    }
+}
 
-        let type_str = parts[0];
-        let pos_str = parts[1];
+// An effective stack depth in whole big blinds. Serialized as the number, e.g.
+// `stack_depth = 40` in [generic].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct StackDepth(pub u16);
 
-        match type_str {
-            "Open" => Ok(SpotType::Open {
-                position: Position::from_str(pos_str)?,
-            }),
-            "BBDefense" => Ok(SpotType::BBDefense {
-                opener_position: Position::from_str(pos_str)?,
-            }),
-            _ => Err(format!("Unknown SpotType: {}", type_str)),
+impl fmt::Display for StackDepth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}bb", self.0)
+    }
+}
+
+impl FromStr for StackDepth {
+    type Err = String;
+    // Accepts "40" or "40bb".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().trim_end_matches("bb").parse::<u16>() {
+            Ok(bb) if bb > 0 => Ok(StackDepth(bb)),
+            _ => Err(format!("Invalid stack depth: {}", s)),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum UserAction {
-    Raise,
-    Call,
-    Fold,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpotType {
+    // Folded to hero: open-raise or fold. With a stack depth the spot uses the ranges at
+    // that depth, e.g. [unopened_raise.BTN_40], as do the spots facing a raise.
+    Open {
+        position: Position,
+        stack_depth: Option<StackDepth>,
+    },
+    // Folded to hero with a short stack: shove (raise) all in or fold
+    PushFold {
+        position: Position,
+        stack_bb: u8,
+    },
+    // Hero in the big blind facing a single open: 3-bet (raise), call or fold. An open
+    // size picks the ranges for that size, e.g. [bb_defense."BTN_2.5"], falling back to
+    // the opener's unsized ones; at a stack depth the depth's ranges are used instead.
+    BBDefense {
+        opener_position: Position,
+        open_size: Option<OpenSize>,
+        stack_depth: Option<StackDepth>,
+    },
+    // Hero faces a single open from a seat in front: 3-bet (raise), call or fold. With
+    // hero in the big blind this is BBDefense, which config strings parse to.
+    Defense {
+        hero_position: Position,
+        opener_position: Position,
+        stack_depth: Option<StackDepth>,
+    },
+    // Hero opened and a player behind 3-bet: 4-bet (raise), call or fold
+    Vs3Bet {
+        opener_position: Position,
+        threebettor_position: Position,
+        stack_depth: Option<StackDepth>,
+    },
+    // Hero in the big blind facing an open and a cold call: squeeze (raise), call or fold
+    Squeeze {
+        opener_position: Position,
+        caller_position: Position,
+        stack_depth: Option<StackDepth>,
+    },
+    // Folded to hero in the small blind, with a limp range: raise, limp or fold
+    SbLimp,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+// The config string forms `SpotType::from_str` reads, each with an example, in variant
+// order. A string of a known spot type in none of its forms is an error naming them,
+// and `parse_spot_type_with_hint` lists them all.
+pub const SPOT_TYPE_FORMATS: &[(&str, &str)] = &[
+    ("Open_<Position>", "Open_BTN"),
+    ("Open_<Position>_<Depth>", "Open_BTN_40"),
+    ("PushFold_<Position>_<Stack>", "PushFold_BTN_10"),
+    ("BBDefense_<Opener>", "BBDefense_CO"),
+    ("BBDefense_<Opener>_<Size>", "BBDefense_BTN_2.5"),
+    ("BBDefense_<Opener>_<Size>_<Depth>", "BBDefense_BTN_2.5_40"),
+    ("Defense_<Hero>_<Opener>", "Defense_BTN_CO"),
+    ("Defense_<Hero>_<Opener>_<Depth>", "Defense_BTN_CO_40"),
+    ("Vs3Bet_<Opener>_<3-Bettor>", "Vs3Bet_CO_BTN"),
+    ("Vs3Bet_<Opener>_<3-Bettor>_<Depth>", "Vs3Bet_CO_BTN_40"),
+    ("Squeeze_<Opener>_<Caller>", "Squeeze_CO_BTN"),
+    ("Squeeze_<Opener>_<Caller>_<Depth>", "Squeeze_CO_BTN_40"),
+    ("Open_SB_Limp", "Open_SB_Limp"),
+];
+
+impl SpotType {
+    // The spot types available at a 6-max table without size-specific config: every
+    // open and every BB defense against an opener.
+    pub fn standard_spot_types() -> Vec<SpotType> {
+        Self::standard_spot_types_for(TableSize::SixMax)
+    }
+
+    // Like `standard_spot_types`, for the seats of the given table.
+    pub fn standard_spot_types_for(table_size: TableSize) -> Vec<SpotType> {
+        let openers = table_size
+            .positions()
+            .iter()
+            .copied()
+            .filter(Position::is_opener);
+        openers
+            .clone()
+            .map(SpotType::open)
+            .chain(openers.map(SpotType::bb_defense))
+            .collect()
+    }
+
+    // Every seat that acts in the spot: hero and the villains.
+    pub fn positions(&self) -> Vec<Position> {
+        match *self {
+            SpotType::Open { position, .. } | SpotType::PushFold { position, .. } => {
+                vec![position]
+            }
+            SpotType::SbLimp => vec![Position::SB],
+            SpotType::BBDefense {
+                opener_position, ..
+            } => vec![opener_position, Position::BB],
+            SpotType::Defense {
+                hero_position,
+                opener_position,
+                ..
+            } => vec![opener_position, hero_position],
+            SpotType::Vs3Bet {
+                opener_position,
+                threebettor_position,
+                ..
+            } => vec![opener_position, threebettor_position],
+            SpotType::Squeeze {
+                opener_position,
+                caller_position,
+                ..
+            } => vec![opener_position, caller_position, Position::BB],
+        }
+    }
+
+    // The seat hero acts from: the big blind when defending it or squeezing, the opener
+    // when facing a 3-bet.
+    pub fn hero_position(&self) -> Position {
+        match *self {
+            SpotType::Open { position, .. } | SpotType::PushFold { position, .. } => position,
+            SpotType::BBDefense { .. } | SpotType::Squeeze { .. } => Position::BB,
+            SpotType::Defense { hero_position, .. } => hero_position,
+            SpotType::SbLimp => Position::SB,
+            SpotType::Vs3Bet {
+                opener_position, ..
+            } => opener_position,
+        }
+    }
+
+    pub fn kind(&self) -> SpotKind {
+        if self.is_facing_raise() {
+            SpotKind::Defense
+        } else {
+            SpotKind::Open
+        }
+    }
+
+    // Short strategic explanation of the spot for new players.
+    pub fn description(&self) -> &'static str {
+        match self {
+            SpotType::Open {
+                stack_depth: Some(_),
+                ..
+            } => {
+                "It's folded to you with a set effective stack; the shorter the stacks, the tighter and more all-in-or-fold the open range gets."
+            }
+            SpotType::Open { position, .. } => match position {
+                Position::UTG => {
+                    "Everyone has folded to you under the gun, first to act with the whole table behind you; open-raise a tight range or fold."
+                }
+                Position::UTG1 | Position::UTG2 => {
+                    "It's folded to you in early position with most of a full table still to act; open-raise a tight range or fold."
+                }
+                Position::LJ | Position::MP | Position::HJ => {
+                    "It's folded to you in middle position with several players still to act; open-raise a fairly tight range or fold."
+                }
+                Position::CO => {
+                    "It's folded to you in the cutoff with only the button and blinds left; open-raise a wide range or fold."
+                }
+                Position::BTN => {
+                    "It's folded to you on the button, the last seat to act after the flop; open-raise your widest range or fold."
+                }
+                Position::SB => {
+                    "It's folded to you in the small blind with only the big blind left, who will act after you postflop; raise or fold."
+                }
+                Position::BB => {
+                    "Everyone has folded to you in the big blind, so the pot is already yours."
+                }
+            },
+            SpotType::BBDefense {
+                opener_position, ..
+            } => match opener_position {
+                Position::UTG => {
+                    "You're in the big blind facing a single raise from under the gun, the tightest opener; choose to 3-bet, call, or fold."
+                }
+                Position::UTG1 | Position::UTG2 => {
+                    "You're in the big blind facing a single raise from early position at a full table, a tight opener; choose to 3-bet, call, or fold."
+                }
+                Position::LJ | Position::MP | Position::HJ => {
+                    "You're in the big blind facing a single raise from middle position; choose to 3-bet, call, or fold."
+                }
+                Position::CO => {
+                    "You're in the big blind facing a single raise from the cutoff; choose to 3-bet, call, or fold."
+                }
+                Position::BTN => {
+                    "You're in the big blind facing a single raise from the button, who opens wide; choose to 3-bet, call, or fold."
+                }
+                Position::SB => {
+                    "You're in the big blind facing a raise from the small blind, heads-up and in position; choose to 3-bet, call, or fold."
+                }
+                Position::BB => "You're in the big blind and nobody can open behind you.",
+            },
+            SpotType::Defense { .. } => {
+                "A player in front of you has opened and others are still to act behind you; choose to 3-bet, call, or fold. Calls invite squeezes, so 3-bet or fold more often than in the big blind."
+            }
+            SpotType::Vs3Bet { .. } => {
+                "You opened and a player behind you 3-bet; choose to 4-bet, call, or fold. Your range here is much tighter than your open."
+            }
+            SpotType::PushFold { .. } => {
+                "It's folded to you with a short stack; too short to open and fold to a 3-bet, so shove all in or fold."
+            }
+            SpotType::SbLimp => {
+                "It's folded to you in the small blind with only the big blind left; raise, limp, or fold. Limping keeps the pot small with hands that play well but don't want to bloat it out of position."
+            }
+            SpotType::Squeeze { .. } => {
+                "You're in the big blind facing a raise and a cold call; choose to squeeze, call, or fold. The dead money makes squeezing attractive, but you're out of position against two players."
+            }
+        }
+    }
+
+    // The string form used by `allowed_spot_types` and `FromStr`, e.g. "Open_UTG" or
+    // "BBDefense_BTN_2.5". Differs from `Display`, which is meant for players. A stack
+    // depth is a trailing "_40", with an unsized BB defense written as
+    // "Defense_BB_<Opener>_40".
+    pub fn to_config_string(&self) -> String {
+        let base = match self {
+            SpotType::Open { position, .. } => format!("Open_{}", position_config_str(*position)),
+            SpotType::PushFold { position, stack_bb } => {
+                format!("PushFold_{}_{}", position_config_str(*position), stack_bb)
+            }
+            SpotType::BBDefense {
+                opener_position,
+                open_size: None,
+                stack_depth: Some(_),
+            } => format!("Defense_BB_{}", position_config_str(*opener_position)),
+            SpotType::BBDefense {
+                opener_position,
+                open_size: None,
+                ..
+            } => format!("BBDefense_{}", position_config_str(*opener_position)),
+            SpotType::BBDefense {
+                opener_position,
+                open_size: Some(open_size),
+                ..
+            } => format!(
+                "BBDefense_{}_{}",
+                position_config_str(*opener_position),
+                open_size.to_string().trim_end_matches("bb")
+            ),
+            SpotType::Defense {
+                hero_position,
+                opener_position,
+                ..
+            } => format!(
+                "Defense_{}_{}",
+                position_config_str(*hero_position),
+                position_config_str(*opener_position)
+            ),
+            SpotType::Vs3Bet {
+                opener_position,
+                threebettor_position,
+                ..
+            } => format!(
+                "Vs3Bet_{}_{}",
+                position_config_str(*opener_position),
+                position_config_str(*threebettor_position)
+            ),
+            SpotType::Squeeze {
+                opener_position,
+                caller_position,
+                ..
+            } => format!(
+                "Squeeze_{}_{}",
+                position_config_str(*opener_position),
+                position_config_str(*caller_position)
+            ),
+            SpotType::SbLimp => "Open_SB_Limp".to_string(),
+        };
+        match self {
+            SpotType::PushFold { .. } => base,
+            _ => match self.stack_depth() {
+                Some(stack_depth) => format!("{}_{}", base, stack_depth.0),
+                None => base,
+            },
+        }
+    }
+
+    // An open at the default stack depth
+    pub fn open(position: Position) -> SpotType {
+        SpotType::Open {
+            position,
+            stack_depth: None,
+        }
+    }
+
+    // A BB defense against any open size, at the default stack depth
+    pub fn bb_defense(opener_position: Position) -> SpotType {
+        SpotType::BBDefense {
+            opener_position,
+            open_size: None,
+            stack_depth: None,
+        }
+    }
+
+    // A defense spot, as BBDefense when hero is in the big blind.
+    pub fn defense(hero_position: Position, opener_position: Position) -> SpotType {
+        if hero_position == Position::BB {
+            SpotType::bb_defense(opener_position)
+        } else {
+            SpotType::Defense {
+                hero_position,
+                opener_position,
+                stack_depth: None,
+            }
+        }
+    }
+
+    // Whether hero faces a raise and can raise, call or fold, rather than open or fold.
+    pub fn is_facing_raise(&self) -> bool {
+        !matches!(
+            self,
+            SpotType::Open { .. } | SpotType::PushFold { .. } | SpotType::SbLimp
+        )
+    }
+
+    // The effective stack of spots with depth-specific ranges, and the stack of a
+    // push/fold spot
+    pub fn stack_depth(&self) -> Option<StackDepth> {
+        match *self {
+            SpotType::Open { stack_depth, .. }
+            | SpotType::BBDefense { stack_depth, .. }
+            | SpotType::Defense { stack_depth, .. }
+            | SpotType::Vs3Bet { stack_depth, .. }
+            | SpotType::Squeeze { stack_depth, .. } => stack_depth,
+            SpotType::PushFold { stack_bb, .. } => Some(StackDepth(stack_bb as u16)),
+            SpotType::SbLimp => None,
+        }
+    }
+
+    // The spot at the given stack depth, or at the default depth for None. Spots without
+    // depth-specific ranges, push/fold included, are returned as they are.
+    pub fn with_stack_depth(self, depth: Option<StackDepth>) -> SpotType {
+        let mut spot_type = self;
+        match &mut spot_type {
+            SpotType::Open { stack_depth, .. }
+            | SpotType::BBDefense { stack_depth, .. }
+            | SpotType::Defense { stack_depth, .. }
+            | SpotType::Vs3Bet { stack_depth, .. }
+            | SpotType::Squeeze { stack_depth, .. } => *stack_depth = depth,
+            SpotType::PushFold { .. } | SpotType::SbLimp => {}
+        }
+        spot_type
+    }
+
+    // The action between raise and fold: limp in the SB limp spot, call otherwise. Its
+    // frequency is the "call" frequency of `get_action_frequencies`.
+    pub fn passive_action(&self) -> UserAction {
+        match self {
+            SpotType::SbLimp => UserAction::Limp,
+            _ => UserAction::Call,
+        }
+    }
+}
+
+// The position as ranges.toml writes it, e.g. "UTG1" for `Position::UTG1`
+pub fn position_config_str(position: Position) -> &'static str {
+    match position {
+        Position::UTG => "UTG",
+        Position::UTG1 => "UTG1",
+        Position::UTG2 => "UTG2",
+        Position::LJ => "LJ",
+        Position::MP => "MP",
+        Position::HJ => "HJ",
+        Position::CO => "CO",
+        Position::BTN => "BTN",
+        Position::SB => "SB",
+        Position::BB => "BB",
+    }
+}
+
+// Parses a spot type string like `SpotType::from_str`, but on failure the error also
+// lists the valid strings, for config files and command-line flags.
+pub fn parse_spot_type_with_hint(s: &str) -> Result<SpotType, String> {
+    SpotType::from_str(s).map_err(|e| {
+        let valid: Vec<String> = SpotType::standard_spot_types()
+            .iter()
+            .map(SpotType::to_config_string)
+            .collect();
+        let formats: Vec<String> = SPOT_TYPE_FORMATS
+            .iter()
+            .filter(|(format, _)| format.contains('<'))
+            .map(|(format, example)| format!("{}, e.g. {}", format, example))
+            .collect();
+        format!(
+            "{}. Valid spot types: {} (or {})",
+            e,
+            valid.join(", "),
+            formats.join("; ")
+        )
+    })
+}
+
+impl fmt::Display for SpotType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SpotType::Open { position, .. } => write!(f, "Open from {}", position)?,
+            SpotType::PushFold { position, stack_bb } => {
+                return write!(f, "Shove or Fold from {} at {}bb", position, stack_bb);
+            }
+            SpotType::BBDefense {
+                opener_position,
+                open_size: None,
+                ..
+            } => write!(f, "BB vs {} Open", opener_position)?,
+            SpotType::BBDefense {
+                opener_position,
+                open_size: Some(open_size),
+                ..
+            } => write!(f, "BB vs {} {} Open", opener_position, open_size)?,
+            SpotType::Defense {
+                hero_position,
+                opener_position,
+                ..
+            } => write!(f, "{} vs {} Open", hero_position, opener_position)?,
+            SpotType::Vs3Bet {
+                opener_position,
+                threebettor_position,
+                ..
+            } => write!(
+                f,
+                "{} Open vs {} 3-Bet",
+                opener_position, threebettor_position
+            )?,
+            SpotType::Squeeze {
+                opener_position,
+                caller_position,
+                ..
+            } => write!(
+                f,
+                "BB vs {} Open and {} Call",
+                opener_position, caller_position
+            )?,
+            SpotType::SbLimp => write!(f, "Open or Limp from {}", Position::SB)?,
+        }
+        match self.stack_depth() {
+            Some(stack_depth) => write!(f, " at {}", stack_depth),
+            None => Ok(()),
+        }
+    }
+}
+
+// Serialized as the config string, "BBDefense_CO", like allowed_spot_types
+impl Serialize for SpotType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_config_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for SpotType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl FromStr for SpotType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let spot_type = parse_spot_type_parts(s)?;
+        // 6-max and 9-max seats share one ordering, so "Defense_HJ_MP" would otherwise
+        // pass the seat-order checks
+        if ![TableSize::SixMax, TableSize::NineMax]
+            .iter()
+            .any(|table_size| table_size.has_seats_for(spot_type))
+        {
+            return Err(format!(
+                "Invalid spot: {} aren't seats at the same table",
+                spot_type
+                    .positions()
+                    .iter()
+                    .map(|&position| position_config_str(position))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        Ok(spot_type)
+    }
+}
+
+// `SpotType::from_str` before its table check
+fn parse_spot_type_parts(s: &str) -> Result<SpotType, String> {
+    let parts: Vec<&str> = s.split('_').collect();
+    match parts.as_slice() {
+        ["Open", pos_str] => Ok(SpotType::open(Position::from_str(pos_str)?)),
+        ["Open", "SB", "Limp"] => Ok(SpotType::SbLimp),
+        ["Open", pos_str, depth_str] => Ok(SpotType::Open {
+            position: Position::from_str(pos_str)?,
+            stack_depth: Some(StackDepth::from_str(depth_str)?),
+        }),
+        ["PushFold", pos_str, stack_str] => {
+            let (position, stack_bb) = parse_push_fold_key(pos_str, stack_str)?;
+            Ok(SpotType::PushFold { position, stack_bb })
+        }
+        ["BBDefense", pos_str] => Ok(SpotType::bb_defense(Position::from_str(pos_str)?)),
+        ["BBDefense", pos_str, size_str] => Ok(SpotType::BBDefense {
+            opener_position: Position::from_str(pos_str)?,
+            open_size: Some(OpenSize::from_str(size_str)?),
+            stack_depth: None,
+        }),
+        ["BBDefense", pos_str, size_str, depth_str] => Ok(SpotType::BBDefense {
+            opener_position: Position::from_str(pos_str)?,
+            open_size: Some(OpenSize::from_str(size_str)?),
+            stack_depth: Some(StackDepth::from_str(depth_str)?),
+        }),
+        ["Defense", hero_str, opener_str] => {
+            let (hero_position, opener_position) = parse_defense_positions(hero_str, opener_str)?;
+            Ok(SpotType::defense(hero_position, opener_position))
+        }
+        ["Vs3Bet", opener_str, threebettor_str] => {
+            let (opener_position, threebettor_position) =
+                parse_vs_3bet_positions(opener_str, threebettor_str)?;
+            Ok(SpotType::Vs3Bet {
+                opener_position,
+                threebettor_position,
+                stack_depth: None,
+            })
+        }
+        ["Squeeze", opener_str, caller_str] => {
+            let (opener_position, caller_position) =
+                parse_squeeze_positions(opener_str, caller_str)?;
+            Ok(SpotType::Squeeze {
+                opener_position,
+                caller_position,
+                stack_depth: None,
+            })
+        }
+        [
+            type_str @ ("Defense" | "Vs3Bet" | "Squeeze"),
+            first,
+            second,
+            depth_str,
+        ] => {
+            let spot_type = parse_spot_type_parts(&format!("{}_{}_{}", type_str, first, second))?;
+            Ok(spot_type.with_stack_depth(Some(StackDepth::from_str(depth_str)?)))
+        }
+        [type_str, ..] => {
+            let formats: Vec<&str> = SPOT_TYPE_FORMATS
+                .iter()
+                .map(|&(format, _)| format)
+                .filter(|format| format.split('_').next() == Some(*type_str))
+                .collect();
+            if formats.is_empty() {
+                Err(format!("Unknown SpotType: {}", type_str))
+            } else {
+                Err(format!(
+                    "Invalid SpotType string format: {} (expected {})",
+                    s,
+                    formats.join(" or ")
+                ))
+            }
+        }
+        [] => Err(format!("Invalid SpotType string format: {}", s)),
+    }
+}
+
+// The (position, stack in bb) of a push/fold spot; hero has to be first in.
+fn parse_push_fold_key(pos_str: &str, stack_str: &str) -> Result<(Position, u8), String> {
+    let position = Position::from_str(pos_str)?;
+    if !position.is_opener() {
+        return Err(format!(
+            "Invalid push/fold spot: {} can't be first in",
+            position
+        ));
+    }
+    match stack_str.trim_end_matches("bb").parse::<u8>() {
+        Ok(stack_bb) if stack_bb > 0 => Ok((position, stack_bb)),
+        _ => Err(format!("Invalid push/fold stack: {}", stack_str)),
+    }
+}
+
+// The (hero, opener) of a defense spot; hero has to act after the opener.
+fn parse_defense_positions(
+    hero_str: &str,
+    opener_str: &str,
+) -> Result<(Position, Position), String> {
+    let hero_position = Position::from_str(hero_str)?;
+    let opener_position = Position::from_str(opener_str)?;
+    if !opener_position.is_opener() || !acts_after(hero_position, opener_position) {
+        return Err(format!(
+            "Invalid defense spot: {} doesn't act after an open from {}",
+            hero_position, opener_position
+        ));
+    }
+    Ok((hero_position, opener_position))
+}
+
+fn acts_after(position: Position, other: Position) -> bool {
+    let seat = |position| Position::VALUES.iter().position(|&p| p == position);
+    seat(position) > seat(other)
+}
+
+// The (opener, 3-bettor) of a 3-bet spot; the 3-bettor has to act after the opener.
+fn parse_vs_3bet_positions(
+    opener_str: &str,
+    threebettor_str: &str,
+) -> Result<(Position, Position), String> {
+    let opener_position = Position::from_str(opener_str)?;
+    let threebettor_position = Position::from_str(threebettor_str)?;
+    if !opener_position.is_opener() || !acts_after(threebettor_position, opener_position) {
+        return Err(format!(
+            "Invalid 3-bet spot: {} can't 3-bet an open from {}",
+            threebettor_position, opener_position
+        ));
+    }
+    Ok((opener_position, threebettor_position))
+}
+
+// The (opener, caller) of a squeeze spot; the caller acts after the opener and before
+// hero in the big blind.
+fn parse_squeeze_positions(
+    opener_str: &str,
+    caller_str: &str,
+) -> Result<(Position, Position), String> {
+    let opener_position = Position::from_str(opener_str)?;
+    let caller_position = Position::from_str(caller_str)?;
+    if !opener_position.is_opener()
+        || !acts_after(caller_position, opener_position)
+        || caller_position == Position::BB
+    {
+        return Err(format!(
+            "Invalid squeeze spot: {} can't call an open from {} before the big blind",
+            caller_position, opener_position
+        ));
+    }
+    Ok((opener_position, caller_position))
+}
+
+// Spots grouped by what hero faces, for drilling one group: an unopened pot (opens,
+// shoves and SB limps) or a raise (BB and other defense, 3-bets faced and squeezes).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpotKind {
+    Open,
+    Defense,
+}
+
+impl FromStr for SpotKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "open" => Ok(SpotKind::Open),
+            "defense" => Ok(SpotKind::Defense),
+            _ => Err(format!(
+                "Invalid spot kind: {} (expected open or defense)",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum UserAction {
+    Raise,
+    Call,
+    // Completing the small blind in an unopened pot
+    Limp,
+    Fold,
+}
+
+// The table being trained; decides which seats spots may use.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TableSize {
+    HeadsUp,
+    #[default]
+    SixMax,
+    NineMax,
+}
+
+impl TableSize {
+    pub fn from_seats(seats: usize) -> Option<Self> {
+        match seats {
+            2 => Some(TableSize::HeadsUp),
+            6 => Some(TableSize::SixMax),
+            9 => Some(TableSize::NineMax),
+            _ => None,
+        }
+    }
+
+    pub fn seats(self) -> usize {
+        match self {
+            TableSize::HeadsUp => 2,
+            TableSize::SixMax => 6,
+            TableSize::NineMax => 9,
+        }
+    }
+
+    pub fn positions(self) -> &'static [Position] {
+        Position::for_table_size(self.seats())
+    }
+
+    // Whether every seat the spot involves is at this table
+    pub fn has_seats_for(self, spot_type: SpotType) -> bool {
+        spot_type
+            .positions()
+            .iter()
+            .all(|position| self.positions().contains(position))
+    }
+}
+
+impl FromStr for TableSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "heads_up" => Ok(TableSize::HeadsUp),
+            "six_max" => Ok(TableSize::SixMax),
+            "nine_max" => Ok(TableSize::NineMax),
+            _ => Err(format!(
+                "Invalid table_size: {} (expected heads_up, six_max, nine_max, 2, 6 or 9)",
+                s
+            )),
+        }
+    }
+}
+
+// Which action an RNG value exactly on the edge between two bands counts as. With
+// raise 50% / fold 50%, values 0-49 always raise and 51-99 always fold; 50 folds under
+// `Passive` (the later, more passive action) and raises under `Aggressive`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BoundaryFavors {
+    #[default]
+    Passive,
+    Aggressive,
+}
+
+impl FromStr for BoundaryFavors {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "passive" => Ok(BoundaryFavors::Passive),
+            "aggressive" => Ok(BoundaryFavors::Aggressive),
+            _ => Err(format!(
+                "Invalid boundary_favors: {} (expected passive or aggressive)",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AnswerResult {
     Correct,
     Wrong,
     FrequencyMistake,
 }
 
-#[derive(Debug, Clone, Default)]
-pub struct GameConfig {
-    pub unopened_raise_ranges: HashMap<Position, HashMap<HandNotation, f32>>,
-    pub bb_defense_call_ranges: HashMap<Position, HashMap<HandNotation, f32>>, // New
-    pub bb_defense_raise_ranges: HashMap<Position, HashMap<HandNotation, f32>>, // New
-    pub allowed_spot_types: Vec<SpotType>,
+// Score for an answer: a frequency mistake earns half credit, or none in strict mode.
+pub fn answer_credit(result: AnswerResult, strict: bool) -> f32 {
+    match result {
+        AnswerResult::Correct => 1.0,
+        AnswerResult::FrequencyMistake if !strict => 0.5,
+        AnswerResult::FrequencyMistake | AnswerResult::Wrong => 0.0,
+    }
+}
+
+// One range: each hand notation in it mapped to the frequency it's played at. Hands
+// that aren't listed are played at 0. The free range functions such as
+// `to_range_string` take and return it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RangeTable(HashMap<HandNotation, f32>);
+
+impl RangeTable {
+    pub fn frequency(&self, hand_notation: HandNotation) -> f32 {
+        self.get(hand_notation).unwrap_or(0.0)
+    }
+
+    // The hand's frequency if it's listed, even at 0
+    pub fn get(&self, hand_notation: HandNotation) -> Option<f32> {
+        self.0.get(&hand_notation).copied()
+    }
+
+    // Sets the hand's frequency, returning the one it was listed at before
+    pub fn insert(&mut self, hand_notation: HandNotation, frequency: f32) -> Option<f32> {
+        self.0.insert(hand_notation, frequency)
+    }
+
+    // Takes the hand out of the range, returning the frequency it was listed at
+    pub fn remove(&mut self, hand_notation: HandNotation) -> Option<f32> {
+        self.0.remove(&hand_notation)
+    }
+
+    // The listed hands and their frequencies, in no particular order
+    pub fn iter(&self) -> std::collections::hash_map::Iter<'_, HandNotation, f32> {
+        self.0.iter()
+    }
+
+    // The listed hands, in no particular order
+    pub fn hands(&self) -> impl Iterator<Item = HandNotation> + '_ {
+        self.0.keys().copied()
+    }
+
+    // Number of listed hands, including any listed at 0
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // Whether the hand is played at all, i.e. listed with a nonzero frequency
+    pub fn contains(&self, hand_notation: HandNotation) -> bool {
+        self.frequency(hand_notation) > 0.0
+    }
+
+    // Combos in the range, each counted at its frequency
+    pub fn combo_count(&self) -> f32 {
+        range_combo_count(self)
+    }
+
+    // The range as the 13x13 grid of `HandNotation::from_grid_position`, one row per
+    // line. Each cell is the hand and its frequency as a whole percent, or '.' when it
+    // isn't listed: "AKs 100", "KQs  50", "72o   .".
+    pub fn to_grid_string(&self) -> String {
+        self.to_grid_string_with(|_, _, cell| cell)
+    }
+
+    // `to_grid_string` with every cell passed through `style` along with its hand and
+    // frequency (0 when not listed), e.g. to color it for a terminal.
+    pub fn to_grid_string_with(
+        &self,
+        style: impl Fn(HandNotation, f32, String) -> String,
+    ) -> String {
+        let mut grid = String::new();
+        for row in get_all_possible_hand_notations_grid_order().chunks(Rank::VALUES.len()) {
+            let cells: Vec<String> = row
+                .iter()
+                .map(|&hand_notation| {
+                    let frequency = match self.0.get(&hand_notation) {
+                        Some(&frequency) => format!("{:.0}", frequency * 100.0),
+                        None => ".".to_string(),
+                    };
+                    let cell = format!("{:<3} {:>3}", hand_notation.to_string(), frequency);
+                    style(hand_notation, self.frequency(hand_notation), cell)
+                })
+                .collect();
+            grid.push_str(&cells.join(" "));
+            grid.push('\n');
+        }
+        grid
+    }
+
+    // Parses a 13x13 grid in `HandNotation::from_grid_position` layout, e.g. pasted from
+    // an equity tool or `to_grid_string`. Each cell is a frequency from 0 to 100 (a
+    // trailing '%' is fine), or '.', '-' or nothing for a hand that isn't listed. Cells
+    // are separated by tabs or commas, or else by whitespace; with whitespace a row is
+    // either 13 frequencies or 13 "<hand> <frequency>" pairs like `to_grid_string`
+    // prints. Blank lines are skipped.
+    pub fn from_grid_str(grid: &str) -> Result<RangeTable, RangeParseError> {
+        // A line of only tabs is a row of blank cells, not a blank line
+        let rows: Vec<&str> = grid
+            .lines()
+            .filter(|line| !line.trim_matches([' ', '\r']).is_empty())
+            .collect();
+        if rows.len() != Rank::VALUES.len() {
+            return Err(RangeParseError::GridRowCount { rows: rows.len() });
+        }
+        let mut range = RangeTable::default();
+        for (row, line) in rows.iter().enumerate() {
+            let invalid_row = |reason: String| RangeParseError::InvalidGridRow {
+                row: row + 1,
+                reason,
+            };
+            // (label, frequency) of each cell
+            let cells: Vec<(Option<&str>, &str)> = match [',', '\t']
+                .into_iter()
+                .find(|&separator| line.contains(separator))
+            {
+                Some(separator) => line
+                    .split(separator)
+                    .map(
+                        |cell| match cell.split_whitespace().collect::<Vec<_>>().as_slice() {
+                            [] => Ok((None, "")),
+                            [frequency] => Ok((None, *frequency)),
+                            [label, frequency] => Ok((Some(*label), *frequency)),
+                            _ => Err(invalid_row(format!("can't read cell \"{}\"", cell.trim()))),
+                        },
+                    )
+                    .collect::<Result<_, _>>()?,
+                None => {
+                    let tokens: Vec<&str> = line.split_whitespace().collect();
+                    // Hand labels never parse as numbers; frequencies like "22" can look
+                    // like hands
+                    let labelled = tokens.first().is_some_and(|first| {
+                        HandNotation::from_str(first).is_ok() && first.parse::<f32>().is_err()
+                    });
+                    if labelled && !tokens.len().is_multiple_of(2) {
+                        return Err(invalid_row(
+                            "expected a frequency or '.' after each hand".to_string(),
+                        ));
+                    } else if labelled {
+                        tokens
+                            .chunks(2)
+                            .map(|pair| (Some(pair[0]), pair[1]))
+                            .collect()
+                    } else {
+                        tokens.into_iter().map(|token| (None, token)).collect()
+                    }
+                }
+            };
+            if cells.len() != Rank::VALUES.len() {
+                return Err(invalid_row(format!(
+                    "expected 13 cells, found {}",
+                    cells.len()
+                )));
+            }
+
+            for (col, (label, frequency_str)) in cells.into_iter().enumerate() {
+                let hand_notation = HandNotation::from_grid_position(row, col);
+                if let Some(label) = label
+                    && HandNotation::from_str(label) != Ok(hand_notation)
+                {
+                    return Err(invalid_row(format!(
+                        "cell {} is labelled {}, but that position is {}",
+                        col + 1,
+                        label,
+                        hand_notation
+                    )));
+                }
+                if matches!(frequency_str, "" | "." | "-") {
+                    continue;
+                }
+                range.insert(hand_notation, parse_grid_percent(frequency_str)?);
+            }
+        }
+        Ok(range)
+    }
+
+    // Every starting hand at 1.0
+    pub fn all() -> Self {
+        get_all_possible_hand_notations()
+            .into_iter()
+            .map(|hn| (hn, 1.0))
+            .collect()
+    }
+
+    // Hands in either range. A hand in both plays at the sum of its frequencies, capped
+    // at 1.0, so a call range and a raise range union to everything that isn't folded.
+    pub fn union(&self, other: &RangeTable) -> RangeTable {
+        let mut union = self.clone();
+        for (&hn, &frequency) in other {
+            let combined = union.0.entry(hn).or_insert(0.0);
+            *combined = (*combined + frequency).min(1.0);
+        }
+        union
+    }
+
+    // Hands in both ranges, at the lower of the two frequencies
+    pub fn intersection(&self, other: &RangeTable) -> RangeTable {
+        self.0
+            .iter()
+            .map(|(&hn, &frequency)| (hn, frequency.min(other.frequency(hn))))
+            .filter(|&(_, frequency)| frequency > 0.0)
+            .collect()
+    }
+
+    // This range with `other`'s frequencies taken off; hands taken down to 0 drop out,
+    // e.g. "22+" minus "55-77" is 22-44 and 88+. Hands `other` doesn't list are kept
+    // as they are.
+    pub fn difference(&self, other: &RangeTable) -> RangeTable {
+        self.0
+            .iter()
+            .filter_map(|(&hn, &frequency)| match other.get(hn) {
+                Some(taken) => (frequency - taken > 0.0).then_some((hn, frequency - taken)),
+                None => Some((hn, frequency)),
+            })
+            .collect()
+    }
+}
+
+impl FromIterator<(HandNotation, f32)> for RangeTable {
+    fn from_iter<I: IntoIterator<Item = (HandNotation, f32)>>(iter: I) -> Self {
+        RangeTable(iter.into_iter().collect())
+    }
+}
+
+impl<'a> IntoIterator for &'a RangeTable {
+    type Item = (&'a HandNotation, &'a f32);
+    type IntoIter = std::collections::hash_map::Iter<'a, HandNotation, f32>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+// The canonical `to_range_string` form
+impl fmt::Display for RangeTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", to_range_string(self))
+    }
+}
+
+// Parses a range string with `parse_range_str`
+impl FromStr for RangeTable {
+    type Err = RangeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_range_str(s)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GameConfig {
+    pub unopened_raise_ranges: HashMap<Position, RangeTable>,
+    // Open ranges keyed by position and effective stack, from "[unopened_raise.BTN_40]"
+    // tables
+    pub unopened_raise_depth_ranges: HashMap<(Position, StackDepth), RangeTable>,
+    // Shove ranges keyed by position and stack in bb, from the "[push_fold]" table
+    pub push_fold_ranges: HashMap<(Position, u8), RangeTable>,
+    // BB defense keyed by opener and open size, from "[bb_defense.BTN]" tables and, for a
+    // size, "[bb_defense.\"BTN_2.5\"]" ones
+    pub bb_defense_call_ranges: HashMap<(Position, Option<OpenSize>), RangeTable>,
+    pub bb_defense_raise_ranges: HashMap<(Position, Option<OpenSize>), RangeTable>,
+    // Defense outside the big blind, keyed by (hero, opener), from the "[defense]" table.
+    // "[defense.BB_<Opener>]" tables fill the bb_defense ranges instead
+    pub defense_call_ranges: HashMap<(Position, Position), RangeTable>,
+    pub defense_raise_ranges: HashMap<(Position, Position), RangeTable>,
+    // Facing a 3-bet, keyed by (opener, 3-bettor), from the "[vs_3bet]" table. The raise
+    // range is the 4-bet range
+    pub vs_3bet_call_ranges: HashMap<(Position, Position), RangeTable>,
+    pub vs_3bet_raise_ranges: HashMap<(Position, Position), RangeTable>,
+    // Squeezing in the big blind, keyed by (opener, caller), from the "[squeeze]" table.
+    // The raise range is the squeeze range
+    pub squeeze_call_ranges: HashMap<(Position, Position), RangeTable>,
+    pub squeeze_raise_ranges: HashMap<(Position, Position), RangeTable>,
+    // The defense, vs_3bet and squeeze ranges at an effective stack, keyed by their
+    // positions and depth, from "[defense.BTN_CO_40]"-style tables. Unlike defense_*,
+    // defense_depth_* keeps "[defense.BB_<Opener>_<Depth>]" tables
+    pub defense_depth_call_ranges: HashMap<(Position, Position, StackDepth), RangeTable>,
+    pub defense_depth_raise_ranges: HashMap<(Position, Position, StackDepth), RangeTable>,
+    pub vs_3bet_depth_call_ranges: HashMap<(Position, Position, StackDepth), RangeTable>,
+    pub vs_3bet_depth_raise_ranges: HashMap<(Position, Position, StackDepth), RangeTable>,
+    pub squeeze_depth_call_ranges: HashMap<(Position, Position, StackDepth), RangeTable>,
+    pub squeeze_depth_raise_ranges: HashMap<(Position, Position, StackDepth), RangeTable>,
+    // The small blind's limp range, from "[sb_limp]". Limps stack after the SB open
+    // range's raises
+    pub sb_limp_ranges: RangeTable,
+    pub allowed_spot_types: Vec<SpotType>,
+    // Within a spot type, serve every hand once (still in weighted order) before any repeats
+    pub coverage_first: bool,
+    // Frequency mistakes count as misses in the displayed score
+    pub strict_scoring: bool,
+    // Scale each notation's selection weight by its number of combos, so offsuit hands
+    // come up about 3x as often as suited ones, like at a real table. On by default
+    pub combo_weighting: bool,
+    // Serve pure-fold hands as questions too. When false, only hands with a nonzero
+    // raise or call frequency come up
+    pub quiz_folds: bool,
+    // Which action an RNG value exactly on a band edge counts as
+    pub boundary_favors: BoundaryFavors,
+    // Only spots whose seats are all at this table are served
+    pub table_size: TableSize,
+    // A fixed effective stack from [generic] stack_depth: only spots at this depth are
+    // served. None plays every configured depth
+    pub stack_depth: Option<StackDepth>,
+    pub theme: ThemeConfig,
+    // Study notes from the "[notes]" table, shown in the feedback for that hand
+    pub notes: HashMap<HandNotation, String>,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig {
+            unopened_raise_ranges: HashMap::new(),
+            unopened_raise_depth_ranges: HashMap::new(),
+            push_fold_ranges: HashMap::new(),
+            bb_defense_call_ranges: HashMap::new(),
+            bb_defense_raise_ranges: HashMap::new(),
+            defense_call_ranges: HashMap::new(),
+            defense_raise_ranges: HashMap::new(),
+            vs_3bet_call_ranges: HashMap::new(),
+            vs_3bet_raise_ranges: HashMap::new(),
+            squeeze_call_ranges: HashMap::new(),
+            squeeze_raise_ranges: HashMap::new(),
+            defense_depth_call_ranges: HashMap::new(),
+            defense_depth_raise_ranges: HashMap::new(),
+            vs_3bet_depth_call_ranges: HashMap::new(),
+            vs_3bet_depth_raise_ranges: HashMap::new(),
+            squeeze_depth_call_ranges: HashMap::new(),
+            squeeze_depth_raise_ranges: HashMap::new(),
+            sb_limp_ranges: RangeTable::default(),
+            allowed_spot_types: Vec::new(),
+            coverage_first: false,
+            strict_scoring: false,
+            combo_weighting: true,
+            quiz_folds: true,
+            boundary_favors: BoundaryFavors::default(),
+            table_size: TableSize::default(),
+            stack_depth: None,
+            theme: ThemeConfig::default(),
+            notes: HashMap::new(),
+        }
+    }
+}
+
+impl GameConfig {
+    // The `allowed_spot_types` with hero in `position` and of `kind`, either filter
+    // skipped when None. Empty when the filters exclude every configured spot.
+    pub fn filtered_spot_types(
+        &self,
+        position: Option<Position>,
+        kind: Option<SpotKind>,
+    ) -> Vec<SpotType> {
+        self.allowed_spot_types
+            .iter()
+            .copied()
+            .filter(|spot_type| position.is_none_or(|p| spot_type.hero_position() == p))
+            .filter(|spot_type| kind.is_none_or(|k| spot_type.kind() == k))
+            .collect()
+    }
+
+    // The open range of an Open or PushFold spot (the shove range). A depth without its
+    // own range is empty rather than falling back to the default-depth range.
+    fn open_range(&self, spot_type: SpotType) -> &RangeTable {
+        match spot_type {
+            SpotType::Open {
+                position,
+                stack_depth: None,
+            } => self.unopened_raise_ranges.get(&position),
+            SpotType::Open {
+                position,
+                stack_depth: Some(stack_depth),
+            } => self
+                .unopened_raise_depth_ranges
+                .get(&(position, stack_depth)),
+            SpotType::PushFold { position, stack_bb } => {
+                self.push_fold_ranges.get(&(position, stack_bb))
+            }
+            _ => None,
+        }
+        .unwrap_or(&EMPTY_HAND_RANGE)
+    }
+
+    // Every range table in the config, depth- and size-keyed ones included
+    pub fn ranges_mut(&mut self) -> impl Iterator<Item = &mut RangeTable> {
+        self.unopened_raise_ranges
+            .values_mut()
+            .chain(self.unopened_raise_depth_ranges.values_mut())
+            .chain(self.push_fold_ranges.values_mut())
+            .chain(self.bb_defense_call_ranges.values_mut())
+            .chain(self.bb_defense_raise_ranges.values_mut())
+            .chain(self.defense_call_ranges.values_mut())
+            .chain(self.defense_raise_ranges.values_mut())
+            .chain(self.vs_3bet_call_ranges.values_mut())
+            .chain(self.vs_3bet_raise_ranges.values_mut())
+            .chain(self.squeeze_call_ranges.values_mut())
+            .chain(self.squeeze_raise_ranges.values_mut())
+            .chain(self.defense_depth_call_ranges.values_mut())
+            .chain(self.defense_depth_raise_ranges.values_mut())
+            .chain(self.vs_3bet_depth_call_ranges.values_mut())
+            .chain(self.vs_3bet_depth_raise_ranges.values_mut())
+            .chain(self.squeeze_depth_call_ranges.values_mut())
+            .chain(self.squeeze_depth_raise_ranges.values_mut())
+            .chain(std::iter::once(&mut self.sb_limp_ranges))
+    }
+
+    pub fn note_for(&self, hand_notation: HandNotation) -> Option<&str> {
+        self.notes.get(&hand_notation).map(String::as_str)
+    }
+
+    // The (call, raise) ranges for a BB defense spot. A size without its own ranges
+    // falls back to the opener's unsized ranges.
+    fn bb_defense_ranges(
+        &self,
+        opener_position: Position,
+        open_size: Option<OpenSize>,
+    ) -> (&RangeTable, &RangeTable) {
+        let sized_key = (opener_position, open_size);
+        let key = if self.bb_defense_call_ranges.contains_key(&sized_key)
+            || self.bb_defense_raise_ranges.contains_key(&sized_key)
+        {
+            sized_key
+        } else {
+            (opener_position, None)
+        };
+        (
+            self.bb_defense_call_ranges
+                .get(&key)
+                .unwrap_or(&EMPTY_HAND_RANGE),
+            self.bb_defense_raise_ranges
+                .get(&key)
+                .unwrap_or(&EMPTY_HAND_RANGE),
+        )
+    }
+
+    // Writes the config back out in the ranges.toml format, with ranges in the canonical
+    // `to_range_string` form. `parse_config_str` on the result gives back this config.
+    pub fn to_toml_string(&self) -> Result<String, ConfigError> {
+        fn range_detail(range: &RangeTable) -> PositionDetail {
+            PositionDetail {
+                range: to_range_string(range),
+            }
+        }
+        // A (call, raise) table; a key missing from one side is an empty range there
+        fn action_tables<K: Copy + Eq + std::hash::Hash>(
+            call_ranges: &HashMap<K, RangeTable>,
+            raise_ranges: &HashMap<K, RangeTable>,
+            key_str: impl Fn(K) -> String,
+        ) -> BTreeMap<String, BBDefensePositionDetail> {
+            call_ranges
+                .keys()
+                .chain(raise_ranges.keys())
+                .map(|&key| {
+                    let detail = BBDefensePositionDetail {
+                        call_range: to_range_string(
+                            call_ranges.get(&key).unwrap_or(&EMPTY_HAND_RANGE),
+                        ),
+                        raise_range: to_range_string(
+                            raise_ranges.get(&key).unwrap_or(&EMPTY_HAND_RANGE),
+                        ),
+                    };
+                    (key_str(key), detail)
+                })
+                .collect()
+        }
+        // A table keyed by position pairs, with its "<A>_<B>_<Depth>" keys next to the
+        // "<A>_<B>" ones
+        fn pair_tables(
+            call_ranges: &HashMap<(Position, Position), RangeTable>,
+            raise_ranges: &HashMap<(Position, Position), RangeTable>,
+            depth_call_ranges: &HashMap<(Position, Position, StackDepth), RangeTable>,
+            depth_raise_ranges: &HashMap<(Position, Position, StackDepth), RangeTable>,
+        ) -> Option<BTreeMap<String, BBDefensePositionDetail>> {
+            let position_pair = |first, second| {
+                format!(
+                    "{}_{}",
+                    position_config_str(first),
+                    position_config_str(second)
+                )
+            };
+            let mut tables = action_tables(call_ranges, raise_ranges, |(first, second)| {
+                position_pair(first, second)
+            });
+            tables.extend(action_tables(
+                depth_call_ranges,
+                depth_raise_ranges,
+                |(first, second, stack_depth)| {
+                    format!("{}_{}", position_pair(first, second), stack_depth.0)
+                },
+            ));
+            Some(tables).filter(|tables| !tables.is_empty())
+        }
+        let non_empty = |tables: BTreeMap<String, BBDefensePositionDetail>| {
+            Some(tables).filter(|tables| !tables.is_empty())
+        };
+
+        let mut unopened_raise: BTreeMap<String, PositionDetail> = self
+            .unopened_raise_ranges
+            .iter()
+            .map(|(&position, range)| {
+                (
+                    position_config_str(position).to_string(),
+                    range_detail(range),
+                )
+            })
+            .collect();
+        unopened_raise.extend(self.unopened_raise_depth_ranges.iter().map(
+            |(&(position, stack_depth), range)| {
+                (
+                    format!("{}_{}", position_config_str(position), stack_depth.0),
+                    range_detail(range),
+                )
+            },
+        ));
+
+        let bb_defense = action_tables(
+            &self.bb_defense_call_ranges,
+            &self.bb_defense_raise_ranges,
+            |(position, open_size)| match open_size {
+                Some(open_size) => format!(
+                    "{}_{}.{}",
+                    position_config_str(position),
+                    open_size.0 / 10,
+                    open_size.0 % 10
+                ),
+                None => position_config_str(position).to_string(),
+            },
+        );
+
+        let push_fold: BTreeMap<String, PositionDetail> = self
+            .push_fold_ranges
+            .iter()
+            .map(|(&(position, stack_bb), range)| {
+                (
+                    format!("{}_{}", position_config_str(position), stack_bb),
+                    range_detail(range),
+                )
+            })
+            .collect();
+
+        let generic = GenericConfig {
+            allowed_spot_types: Some(
+                self.allowed_spot_types
+                    .iter()
+                    .map(SpotType::to_config_string)
+                    .collect(),
+            ),
+            coverage_first: Some(self.coverage_first),
+            strict_scoring: Some(self.strict_scoring),
+            combo_weighting: Some(self.combo_weighting),
+            quiz_folds: Some(self.quiz_folds),
+            boundary_favors: Some(
+                match self.boundary_favors {
+                    BoundaryFavors::Passive => "passive",
+                    BoundaryFavors::Aggressive => "aggressive",
+                }
+                .to_string(),
+            ),
+            table_size: Some(TableSizeToml::Seats(self.table_size.seats())),
+            stack_depth: self.stack_depth,
+        };
+
+        let theme = ThemeToml {
+            card_colors: Some(
+                match self.theme.card_colors {
+                    CardColorScheme::TwoColor => "two_color",
+                    CardColorScheme::FourColor => "four_color",
+                }
+                .to_string(),
+            ),
+            suit_colors: Some(
+                self.theme
+                    .suit_colors
+                    .iter()
+                    .map(|(suit, color)| {
+                        let suit_str = match suit {
+                            Suit::Spades => "spades",
+                            Suit::Hearts => "hearts",
+                            Suit::Diamonds => "diamonds",
+                            Suit::Clubs => "clubs",
+                        };
+                        let color_str = format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b);
+                        (suit_str.to_string(), color_str)
+                    })
+                    .collect(),
+            )
+            .filter(|suit_colors: &BTreeMap<String, String>| !suit_colors.is_empty()),
+        };
+
+        let toml_config = TomlConfig {
+            unopened_raise,
+            bb_defense: non_empty(bb_defense),
+            vs_3bet: pair_tables(
+                &self.vs_3bet_call_ranges,
+                &self.vs_3bet_raise_ranges,
+                &self.vs_3bet_depth_call_ranges,
+                &self.vs_3bet_depth_raise_ranges,
+            ),
+            defense: pair_tables(
+                &self.defense_call_ranges,
+                &self.defense_raise_ranges,
+                &self.defense_depth_call_ranges,
+                &self.defense_depth_raise_ranges,
+            ),
+            squeeze: pair_tables(
+                &self.squeeze_call_ranges,
+                &self.squeeze_raise_ranges,
+                &self.squeeze_depth_call_ranges,
+                &self.squeeze_depth_raise_ranges,
+            ),
+            generic: Some(generic),
+            theme: Some(theme),
+            sb_limp: Some(&self.sb_limp_ranges)
+                .filter(|range| !range.is_empty())
+                .map(range_detail),
+            push_fold: Some(push_fold).filter(|push_fold| !push_fold.is_empty()),
+            notes: Some(
+                self.notes
+                    .iter()
+                    .map(|(hand_notation, note)| (hand_notation.to_string(), note.clone()))
+                    .collect(),
+            )
+            .filter(|notes: &BTreeMap<String, String>| !notes.is_empty()),
+        };
+        Ok(toml::to_string(&toml_config)?)
+    }
+}
+
+use std::path::{Path, PathBuf};
+
+pub fn find_or_create_config() -> Result<PathBuf, std::io::Error> {
+    // 1. Check current working directory
+    let cwd_candidate = PathBuf::from("ranges.toml");
+    if cwd_candidate.exists() {
+        return Ok(cwd_candidate);
+    }
+
+    // 2. Check executable directory
+    if let Ok(exe_path) = std::env::current_exe()
+        && let Some(exe_dir) = exe_path.parent()
+    {
+        let exe_candidate = exe_dir.join("ranges.toml");
+        if exe_candidate.exists() {
+            return Ok(exe_candidate); // Return immediately if found in exe dir
+        }
+    }
+
+    // 3. Check platform-specific config directory
+    if let Some(config_dir) = dirs::config_dir() {
+        let app_config_dir = config_dir.join("preflop-trainer");
+        if !app_config_dir.exists() {
+            fs::create_dir_all(&app_config_dir)?;
+        }
+        let config_path = app_config_dir.join("ranges.toml");
+        if config_path.exists() {
+            return Ok(config_path);
+        } else {
+            // 4. Create config from embedded example
+            let example_content = include_str!("../../ranges.toml.example");
+            fs::write(&config_path, example_content)?;
+            return Ok(config_path);
+        }
+    }
+
+    // 5. Fallback to a temporary file if all else fails
+    let tmp = std::env::temp_dir().join(format!(
+        "preflop_trainer_ranges_{}.toml",
+        std::process::id()
+    ));
+    let example_content = include_str!("../../ranges.toml.example");
+    fs::write(&tmp, example_content)?;
+    Ok(tmp)
+}
+
+// Loads every "*.toml" file in `dir` as a config profile, keyed by file name without
+// the extension, e.g. "cash_100bb.toml" becomes "cash_100bb". Other files are skipped;
+// the first file that doesn't load fails the whole call.
+pub fn load_profiles(dir: &Path) -> Result<HashMap<String, GameConfig>, ConfigError> {
+    let mut profiles = HashMap::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !path.is_file() || path.extension().is_none_or(|ext| ext != "toml") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let config = load_config_from_str(&fs::read_to_string(&path)?)?;
+        profiles.insert(name.to_string(), config);
+    }
+    Ok(profiles)
+}
+
+pub fn load_config() -> Result<GameConfig, ConfigError> {
+    let config_path = find_or_create_config()?;
+    let contents = fs::read_to_string(config_path)?;
+    load_config_from_str(&contents)
+}
+
+// Like `load_config`, but for ranges.toml contents the caller already has, so tests and
+// builds without a filesystem can supply the config directly.
+pub fn load_config_from_str(contents: &str) -> Result<GameConfig, ConfigError> {
+    parse_config_str(contents)
+}
+
+// Reads ranges.toml contents from any reader, e.g. an open file or a byte slice. Read
+// failures and invalid UTF-8 are `ConfigError::Io`.
+pub fn load_config_from_reader(mut reader: impl std::io::Read) -> Result<GameConfig, ConfigError> {
+    let mut contents = String::new();
+    reader.read_to_string(&mut contents)?;
+    load_config_from_str(&contents)
+}
+
+// A position in a table key; anything else is an unknown position
+fn config_position(s: &str) -> Result<Position, ConfigError> {
+    Position::from_str(s).map_err(|_| ConfigError::UnknownPosition(s.to_string()))
+}
+
+// Builds a GameConfig from the contents of a ranges.toml file.
+pub fn parse_config_str(contents: &str) -> Result<GameConfig, ConfigError> {
+    let toml_config: TomlConfig = toml::from_str(contents)?;
+    let generic_config = toml_config.generic.unwrap_or_default();
+    let table_size = match &generic_config.table_size {
+        Some(TableSizeToml::Seats(seats)) => TableSize::from_seats(*seats).ok_or_else(|| {
+            ConfigError::Invalid(format!(
+                "Invalid table_size: {} (expected heads_up, six_max, nine_max, 2, 6 or 9)",
+                seats
+            ))
+        })?,
+        Some(TableSizeToml::Name(name)) => {
+            TableSize::from_str(name).map_err(ConfigError::Invalid)?
+        }
+        None => TableSize::default(),
+    };
+
+    // Every seat named in a table key has to be at the table, e.g. no [unopened_raise.HJ]
+    // at a 6-max table. Parts that aren't positions, like the "40" of "BTN_40", are
+    // checked where the key is parsed.
+    let call_raise_keys = [
+        ("bb_defense", &toml_config.bb_defense),
+        ("defense", &toml_config.defense),
+        ("vs_3bet", &toml_config.vs_3bet),
+        ("squeeze", &toml_config.squeeze),
+    ]
+    .into_iter()
+    .flat_map(|(table, entries)| entries.iter().flatten().map(move |(key, _)| (table, key)));
+    let table_keys = toml_config
+        .unopened_raise
+        .keys()
+        .map(|key| ("unopened_raise", key))
+        .chain(
+            toml_config
+                .push_fold
+                .iter()
+                .flatten()
+                .map(|(key, _)| ("push_fold", key)),
+        )
+        .chain(call_raise_keys);
+    for (table, key_str) in table_keys {
+        if let Some(position) = key_str
+            .split('_')
+            .filter_map(|part| Position::from_str(part).ok())
+            .find(|position| !table_size.positions().contains(position))
+        {
+            return Err(ConfigError::Invalid(format!(
+                "[{}.{}] names {}, which isn't a seat at a {}-handed table",
+                table,
+                key_str,
+                position_config_str(position),
+                table_size.seats()
+            )));
+        }
+    }
+
+    let mut unopened_raise_ranges = HashMap::new();
+    let mut unopened_raise_depth_ranges = HashMap::new();
+    for (key_str, detail) in toml_config.unopened_raise {
+        let range_map = RangeTable::from_str(&detail.range)?;
+        // Keys are either a position ("BTN") or a position and stack depth ("BTN_40")
+        match key_str.split_once('_') {
+            Some((pos_str, depth_str)) => {
+                let key = (
+                    config_position(pos_str)?,
+                    StackDepth::from_str(depth_str).map_err(ConfigError::Invalid)?,
+                );
+                unopened_raise_depth_ranges.insert(key, range_map);
+            }
+            None => {
+                let position = config_position(&key_str)?;
+                unopened_raise_ranges.insert(position, range_map);
+            }
+        }
+    }
+
+    let mut bb_defense_call_ranges = HashMap::new();
+    let mut bb_defense_raise_ranges = HashMap::new();
+    if let Some(bb_defense_toml) = toml_config.bb_defense {
+        for (key_str, detail) in bb_defense_toml {
+            let call_range_map = RangeTable::from_str(&detail.call_range)?;
+            let raise_range_map = RangeTable::from_str(&detail.raise_range)?;
+            check_defense_totals(
+                &format!("[bb_defense.{}]", key_str),
+                &call_range_map,
+                &raise_range_map,
+            )?;
+            // Keys are either a position ("BTN") or a position and open size ("BTN_2.5")
+            let key = match key_str.split_once('_') {
+                Some((pos_str, size_str)) => (
+                    config_position(pos_str)?,
+                    Some(OpenSize::from_str(size_str).map_err(ConfigError::Invalid)?),
+                ),
+                None => (config_position(&key_str)?, None),
+            };
+            bb_defense_call_ranges.insert(key, call_range_map);
+            bb_defense_raise_ranges.insert(key, raise_range_map);
+        }
+    }
+
+    let PairTableRanges {
+        ranges: (mut defense_call_ranges, mut defense_raise_ranges),
+        depth_ranges: (defense_depth_call_ranges, defense_depth_raise_ranges),
+    } = parse_pair_table(
+        "defense",
+        toml_config.defense,
+        "<Hero>_<Opener>, e.g. BTN_CO",
+        parse_defense_positions,
+    )?;
+    // [defense.BB_<Opener>] tables are BB defense ranges
+    let bb_keys: Vec<(Position, Position)> = defense_call_ranges
+        .keys()
+        .copied()
+        .filter(|&(hero_position, _)| hero_position == Position::BB)
+        .collect();
+    for key in bb_keys {
+        let opener_position = key.1;
+        if bb_defense_call_ranges.contains_key(&(opener_position, None)) {
+            return Err(ConfigError::Invalid(format!(
+                "[defense.BB_{0}] repeats [bb_defense.{0}]",
+                position_config_str(opener_position)
+            )));
+        }
+        bb_defense_call_ranges.insert(
+            (opener_position, None),
+            defense_call_ranges.remove(&key).unwrap(),
+        );
+        bb_defense_raise_ranges.insert(
+            (opener_position, None),
+            defense_raise_ranges.remove(&key).unwrap(),
+        );
+    }
+
+    let PairTableRanges {
+        ranges: (vs_3bet_call_ranges, vs_3bet_raise_ranges),
+        depth_ranges: (vs_3bet_depth_call_ranges, vs_3bet_depth_raise_ranges),
+    } = parse_pair_table(
+        "vs_3bet",
+        toml_config.vs_3bet,
+        "<Opener>_<3-Bettor>, e.g. CO_BTN",
+        parse_vs_3bet_positions,
+    )?;
+
+    let PairTableRanges {
+        ranges: (squeeze_call_ranges, squeeze_raise_ranges),
+        depth_ranges: (squeeze_depth_call_ranges, squeeze_depth_raise_ranges),
+    } = parse_pair_table(
+        "squeeze",
+        toml_config.squeeze,
+        "<Opener>_<Caller>, e.g. CO_BTN",
+        parse_squeeze_positions,
+    )?;
+
+    let mut push_fold_ranges = HashMap::new();
+    for (_, key, detail) in parse_table_keys(
+        "push_fold",
+        toml_config.push_fold.unwrap_or_default(),
+        "<Position>_<Stack>, e.g. BTN_10",
+        parse_push_fold_key,
+    )? {
+        push_fold_ranges.insert(key, RangeTable::from_str(&detail.range)?);
+    }
+
+    let sb_limp_ranges = match toml_config.sb_limp {
+        Some(detail) => RangeTable::from_str(&detail.range)?,
+        None => RangeTable::default(),
+    };
+
+    let allowed_spot_types = generic_config
+        .allowed_spot_types
+        .map(|toml_spot_types| {
+            toml_spot_types
+                .into_iter()
+                .map(|s| parse_spot_type_with_hint(&s))
+                .collect::<Result<Vec<SpotType>, String>>()
+        })
+        .transpose()
+        .map_err(ConfigError::UnknownSpotType)?;
+
+    let boundary_favors = match &generic_config.boundary_favors {
+        Some(favors) => BoundaryFavors::from_str(favors).map_err(ConfigError::Invalid)?,
+        None => BoundaryFavors::default(),
+    };
+    let theme = parse_theme(toml_config.theme.unwrap_or_default()).map_err(ConfigError::Invalid)?;
+
+    let mut notes = HashMap::new();
+    for (notation_str, note) in toml_config.notes.unwrap_or_default() {
+        let hand_notation = HandNotation::from_str(&notation_str)
+            .map_err(|e| ConfigError::Invalid(format!("Invalid hand in [notes]: {}", e)))?;
+        notes.insert(hand_notation, note);
+    }
+
+    let mut config = GameConfig {
+        unopened_raise_ranges,
+        unopened_raise_depth_ranges,
+        push_fold_ranges,
+        bb_defense_call_ranges,
+        bb_defense_raise_ranges,
+        defense_call_ranges,
+        defense_raise_ranges,
+        vs_3bet_call_ranges,
+        vs_3bet_raise_ranges,
+        squeeze_call_ranges,
+        squeeze_raise_ranges,
+        defense_depth_call_ranges,
+        defense_depth_raise_ranges,
+        vs_3bet_depth_call_ranges,
+        vs_3bet_depth_raise_ranges,
+        squeeze_depth_call_ranges,
+        squeeze_depth_raise_ranges,
+        sb_limp_ranges,
+        allowed_spot_types: Vec::new(),
+        coverage_first: generic_config.coverage_first.unwrap_or(false),
+        strict_scoring: generic_config.strict_scoring.unwrap_or(false),
+        combo_weighting: generic_config.combo_weighting.unwrap_or(true),
+        quiz_folds: generic_config.quiz_folds.unwrap_or(true),
+        boundary_favors,
+        table_size,
+        stack_depth: generic_config.stack_depth,
+        theme,
+        notes,
+    };
+    config.allowed_spot_types = match allowed_spot_types {
+        Some(spot_types) => {
+            if let Some(spot_type) = spot_types
+                .iter()
+                .find(|&&spot_type| !table_size.has_seats_for(spot_type))
+            {
+                return Err(ConfigError::Invalid(format!(
+                    "allowed_spot_types lists {}, which needs a seat missing at a {}-handed table",
+                    spot_type.to_config_string(),
+                    table_size.seats()
+                )));
+            }
+            // A listed spot without ranges would only ever be served as a fold
+            if let Some(spot_type) = spot_types
+                .iter()
+                .find(|&&spot_type| !spot_has_range(&config, spot_type))
+            {
+                return Err(ConfigError::Invalid(format!(
+                    "allowed_spot_types lists {}, but its [{}] range is missing or empty",
+                    spot_type.to_config_string(),
+                    spot_range_table(*spot_type)
+                )));
+            }
+            if let Some(stack_depth) = config.stack_depth
+                && let Some(spot_type) = spot_types
+                    .iter()
+                    .find(|spot_type| spot_type.stack_depth() != Some(stack_depth))
+            {
+                return Err(ConfigError::Invalid(format!(
+                    "allowed_spot_types lists {}, which isn't at the {} stack_depth",
+                    spot_type.to_config_string(),
+                    stack_depth
+                )));
+            }
+            spot_types
+        }
+        None => {
+            let mut spot_types = default_allowed_spot_types(&config);
+            if let Some(stack_depth) = config.stack_depth {
+                spot_types.retain(|spot_type| spot_type.stack_depth() == Some(stack_depth));
+                if spot_types.is_empty() {
+                    return Err(ConfigError::Invalid(format!(
+                        "stack_depth is {}, but no range table has that depth",
+                        stack_depth
+                    )));
+                }
+            }
+            spot_types
+        }
+    };
+    Ok(config)
+}
+
+// The entries of a table keyed "<A>_<B>", such as [vs_3bet] keyed "CO_BTN", each with
+// its key as written and as parsed by `parse_key`. `key_hint` is the expected key form
+// for the error on a key without '_'.
+fn parse_table_keys<K, D>(
+    table: &str,
+    entries: BTreeMap<String, D>,
+    key_hint: &str,
+    parse_key: impl Fn(&str, &str) -> Result<K, String>,
+) -> Result<Vec<(String, K, D)>, ConfigError> {
+    entries
+        .into_iter()
+        .map(|(key_str, detail)| {
+            let key = match key_str.split_once('_') {
+                Some((first, second)) => parse_key(first, second).map_err(ConfigError::Invalid)?,
+                None => {
+                    return Err(ConfigError::Invalid(format!(
+                        "Invalid [{}] key: {} (expected {})",
+                        table, key_str, key_hint
+                    )));
+                }
+            };
+            Ok((key_str, key, detail))
+        })
+        .collect()
+}
+
+// The call and the raise ranges of a call/raise table, by key
+type CallRaiseRanges<K> = (HashMap<K, RangeTable>, HashMap<K, RangeTable>);
+
+// The (call, raise) ranges of a call/raise table like [vs_3bet], by parsed key. Every
+// entry goes through `check_defense_totals`.
+fn parse_call_raise_table<K: Copy + Eq + std::hash::Hash>(
+    table: &str,
+    entries: BTreeMap<String, BBDefensePositionDetail>,
+    key_hint: &str,
+    parse_key: impl Fn(&str, &str) -> Result<K, String>,
+) -> Result<CallRaiseRanges<K>, ConfigError> {
+    let mut call_ranges = HashMap::new();
+    let mut raise_ranges = HashMap::new();
+    for (key_str, key, detail) in parse_table_keys(table, entries, key_hint, parse_key)? {
+        let call_range = RangeTable::from_str(&detail.call_range)?;
+        let raise_range = RangeTable::from_str(&detail.raise_range)?;
+        check_defense_totals(
+            &format!("[{}.{}]", table, key_str),
+            &call_range,
+            &raise_range,
+        )?;
+        call_ranges.insert(key, call_range);
+        raise_ranges.insert(key, raise_range);
+    }
+    Ok((call_ranges, raise_ranges))
+}
+
+// A call/raise table keyed by position pairs, like [vs_3bet]: the ranges of its
+// "<A>_<B>" keys, and of its "<A>_<B>_<Depth>" keys at a stack depth
+struct PairTableRanges {
+    ranges: CallRaiseRanges<(Position, Position)>,
+    depth_ranges: CallRaiseRanges<(Position, Position, StackDepth)>,
+}
+
+fn parse_pair_table(
+    table: &str,
+    entries: Option<BTreeMap<String, BBDefensePositionDetail>>,
+    key_hint: &str,
+    parse_positions: impl Fn(&str, &str) -> Result<(Position, Position), String> + Copy,
+) -> Result<PairTableRanges, ConfigError> {
+    let (depth_entries, entries): (BTreeMap<_, _>, BTreeMap<_, _>) = entries
+        .unwrap_or_default()
+        .into_iter()
+        .partition(|(key_str, _)| key_str.matches('_').count() > 1);
+    let ranges = parse_call_raise_table(table, entries, key_hint, parse_positions)?;
+    let depth_ranges = parse_call_raise_table(table, depth_entries, key_hint, |first, rest| {
+        // `rest` is "<B>_<Depth>", as the key has a second '_'
+        let (second, depth_str) = rest.rsplit_once('_').unwrap_or((rest, ""));
+        let (first, second) = parse_positions(first, second)?;
+        Ok((first, second, StackDepth::from_str(depth_str)?))
+    })?;
+    Ok(PairTableRanges {
+        ranges,
+        depth_ranges,
+    })
+}
+
+fn spot_has_range(config: &GameConfig, spot_type: SpotType) -> bool {
+    spot_target_range(config, spot_type)
+        .iter()
+        .any(|(_, &freq)| freq > 0.0)
+}
+
+// The TOML table a spot type's ranges come from
+fn spot_range_table(spot_type: SpotType) -> &'static str {
+    match spot_type {
+        SpotType::Open { .. } => "unopened_raise",
+        SpotType::PushFold { .. } => "push_fold",
+        // BB defense ranges at a depth are [defense.BB_<Opener>_<Depth>]
+        SpotType::BBDefense {
+            stack_depth: Some(_),
+            ..
+        } => "defense",
+        SpotType::BBDefense { .. } => "bb_defense",
+        SpotType::Defense { .. } => "defense",
+        SpotType::Vs3Bet { .. } => "vs_3bet",
+        SpotType::Squeeze { .. } => "squeeze",
+        SpotType::SbLimp => "sb_limp",
+    }
+}
+
+// The spots practiced when `allowed_spot_types` isn't set: every opener and BB defense
+// at the configured table size, plus a spot for each configured open stack depth,
+// push/fold stack, sized BB defense, defense, 3-bet and squeeze table, with or without
+// a stack depth.
+// With a limp range, the SB open becomes the raise/limp/fold spot.
+fn default_allowed_spot_types(config: &GameConfig) -> Vec<SpotType> {
+    fn sorted_keys<K: Copy + Ord, V>(map: &HashMap<K, V>) -> Vec<K> {
+        let mut keys: Vec<K> = map.keys().copied().collect();
+        keys.sort();
+        keys
+    }
+
+    let mut spot_types = SpotType::standard_spot_types_for(config.table_size);
+    if !config.sb_limp_ranges.is_empty() {
+        for spot_type in spot_types.iter_mut() {
+            if *spot_type == SpotType::open(Position::SB) {
+                *spot_type = SpotType::SbLimp;
+            }
+        }
+    }
+    spot_types.extend(
+        sorted_keys(&config.unopened_raise_depth_ranges)
+            .into_iter()
+            .map(|(position, stack_depth)| SpotType::Open {
+                position,
+                stack_depth: Some(stack_depth),
+            }),
+    );
+    spot_types.extend(
+        sorted_keys(&config.push_fold_ranges)
+            .into_iter()
+            .map(|(position, stack_bb)| SpotType::PushFold { position, stack_bb }),
+    );
+    spot_types.extend(
+        sorted_keys(&config.bb_defense_call_ranges)
+            .into_iter()
+            // The unsized ones are among the standard spot types
+            .filter(|(_, open_size)| open_size.is_some())
+            .map(|(opener_position, open_size)| SpotType::BBDefense {
+                opener_position,
+                open_size,
+                stack_depth: None,
+            }),
+    );
+    spot_types.extend(
+        sorted_keys(&config.defense_call_ranges).into_iter().map(
+            |(hero_position, opener_position)| SpotType::defense(hero_position, opener_position),
+        ),
+    );
+    spot_types.extend(sorted_keys(&config.vs_3bet_call_ranges).into_iter().map(
+        |(opener_position, threebettor_position)| SpotType::Vs3Bet {
+            opener_position,
+            threebettor_position,
+            stack_depth: None,
+        },
+    ));
+    spot_types.extend(sorted_keys(&config.squeeze_call_ranges).into_iter().map(
+        |(opener_position, caller_position)| SpotType::Squeeze {
+            opener_position,
+            caller_position,
+            stack_depth: None,
+        },
+    ));
+    spot_types.extend(
+        sorted_keys(&config.defense_depth_call_ranges)
+            .into_iter()
+            .map(|(hero_position, opener_position, stack_depth)| {
+                SpotType::defense(hero_position, opener_position)
+                    .with_stack_depth(Some(stack_depth))
+            }),
+    );
+    spot_types.extend(
+        sorted_keys(&config.vs_3bet_depth_call_ranges)
+            .into_iter()
+            .map(
+                |(opener_position, threebettor_position, stack_depth)| SpotType::Vs3Bet {
+                    opener_position,
+                    threebettor_position,
+                    stack_depth: Some(stack_depth),
+                },
+            ),
+    );
+    spot_types.extend(
+        sorted_keys(&config.squeeze_depth_call_ranges)
+            .into_iter()
+            .map(
+                |(opener_position, caller_position, stack_depth)| SpotType::Squeeze {
+                    opener_position,
+                    caller_position,
+                    stack_depth: Some(stack_depth),
+                },
+            ),
+    );
+    spot_types
+}
+
+fn parse_theme(theme_toml: ThemeToml) -> Result<ThemeConfig, String> {
+    let card_colors = match theme_toml.card_colors {
+        Some(scheme) => CardColorScheme::from_str(&scheme)?,
+        None => CardColorScheme::default(),
+    };
+    let mut suit_colors = HashMap::new();
+    for (suit_str, color_str) in theme_toml.suit_colors.unwrap_or_default() {
+        let suit = match suit_str.as_str() {
+            "spades" => Suit::Spades,
+            "hearts" => Suit::Hearts,
+            "diamonds" => Suit::Diamonds,
+            "clubs" => Suit::Clubs,
+            _ => return Err(format!("Invalid suit in theme.suit_colors: {}", suit_str)),
+        };
+        suit_colors.insert(suit, RgbColor::from_str(&color_str)?);
+    }
+    Ok(ThemeConfig {
+        card_colors,
+        suit_colors,
+    })
+}
+
+// A JSON Schema (draft 2020-12) for the ranges.toml format, for editors that validate
+// TOML against a schema. Positions and spot types come from the config types.
+pub fn config_json_schema() -> String {
+    let openers: Vec<&str> = Position::VALUES
+        .iter()
+        .filter(|p| p.is_opener())
+        .map(|&p| position_config_str(p))
+        .collect();
+    // Every seat but UTG can face an open or 3-bet one
+    let threebettors: Vec<&str> = Position::VALUES[1..]
+        .iter()
+        .map(|&p| position_config_str(p))
+        .collect();
+    // Both table sizes; the 9-max list adds its early and middle seats
+    let mut spot_types: Vec<String> = SpotType::standard_spot_types_for(TableSize::NineMax)
+        .into_iter()
+        .chain(SpotType::standard_spot_types_for(TableSize::SixMax))
+        .map(|spot_type| spot_type.to_config_string())
+        .collect();
+    spot_types.sort();
+    spot_types.dedup();
+    spot_types.push(SpotType::SbLimp.to_config_string());
+    let callers: Vec<&str> = Position::VALUES[1..Position::VALUES.len() - 1]
+        .iter()
+        .map(|&p| position_config_str(p))
+        .collect();
+    let opener_pattern = openers.join("|");
+    let threebettor_pattern = threebettors.join("|");
+    let caller_pattern = callers.join("|");
+    let size_pattern = r"[0-9]+(\\.[0-9])?(bb)?";
+    let depth_pattern = "[0-9]+(bb)?";
+    let range_ref = r##"{ "$ref": "#/$defs/range" }"##;
+
+    format!(
+        r##"{{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "title": "Preflop Trainer ranges.toml",
+  "type": "object",
+  "required": ["unopened_raise"],
+  "properties": {{
+    "unopened_raise": {{
+      "description": "Opening range for each position, optionally by effective stack in bb (\"BTN_40\")",
+      "type": "object",
+      "propertyNames": {{ "pattern": "^({opener_pattern})(_{depth_pattern})?$" }},
+      "additionalProperties": {{
+        "type": "object",
+        "required": ["range"],
+        "properties": {{ "range": {range_ref} }}
+      }}
+    }},
+    "push_fold": {{
+      "description": "Shove ranges for short stacks, keyed by position and stack in bb (\"BTN_10\")",
+      "type": "object",
+      "propertyNames": {{ "pattern": "^({opener_pattern})_[0-9]+(bb)?$" }},
+      "additionalProperties": {{
+        "type": "object",
+        "required": ["range"],
+        "properties": {{ "range": {range_ref} }}
+      }}
+    }},
+    "sb_limp": {{
+      "description": "The small blind's limp range when folded to; stacks after the SB open range",
+      "type": "object",
+      "required": ["range"],
+      "properties": {{ "range": {range_ref} }}
+    }},
+    "bb_defense": {{
+      "description": "Big blind defense ranges by opener, optionally by open size (\"BTN_2.5\")",
+      "type": "object",
+      "propertyNames": {{ "pattern": "^({opener_pattern})(_{size_pattern})?$" }},
+      "additionalProperties": {{
+        "type": "object",
+        "required": ["call_range", "raise_range"],
+        "properties": {{
+          "call_range": {range_ref},
+          "raise_range": {range_ref}
+        }}
+      }}
+    }},
+    "defense": {{
+      "description": "Ranges facing a single open, keyed by hero and opener (\"BTN_CO\"), optionally by effective stack in bb (\"BTN_CO_40\"); \"BB_<Opener>\" is the same as a [bb_defense] table",
+      "type": "object",
+      "propertyNames": {{ "pattern": "^({threebettor_pattern})_({opener_pattern})(_{depth_pattern})?$" }},
+      "additionalProperties": {{
+        "type": "object",
+        "required": ["call_range", "raise_range"],
+        "properties": {{
+          "call_range": {range_ref},
+          "raise_range": {range_ref}
+        }}
+      }}
+    }},
+    "vs_3bet": {{
+      "description": "Ranges after opening and facing a 3-bet, keyed by opener and 3-bettor (\"CO_BTN\"), optionally by effective stack in bb (\"CO_BTN_40\"); the raise range is the 4-bet range",
+      "type": "object",
+      "propertyNames": {{ "pattern": "^({opener_pattern})_({threebettor_pattern})(_{depth_pattern})?$" }},
+      "additionalProperties": {{
+        "type": "object",
+        "required": ["call_range", "raise_range"],
+        "properties": {{
+          "call_range": {range_ref},
+          "raise_range": {range_ref}
+        }}
+      }}
+    }},
+    "squeeze": {{
+      "description": "Big blind ranges facing an open and a cold call, keyed by opener and caller (\"CO_BTN\"), optionally by effective stack in bb (\"CO_BTN_40\"); the raise range is the squeeze range",
+      "type": "object",
+      "propertyNames": {{ "pattern": "^({opener_pattern})_({caller_pattern})(_{depth_pattern})?$" }},
+      "additionalProperties": {{
+        "type": "object",
+        "required": ["call_range", "raise_range"],
+        "properties": {{
+          "call_range": {range_ref},
+          "raise_range": {range_ref}
+        }}
+      }}
+    }},
+    "generic": {{
+      "type": "object",
+      "properties": {{
+        "allowed_spot_types": {{
+          "type": "array",
+          "items": {{
+            "anyOf": [
+              {{ "enum": {spot_types} }},
+              {{ "pattern": "^Open_({opener_pattern})_{depth_pattern}$" }},
+              {{ "pattern": "^PushFold_({opener_pattern})_{depth_pattern}$" }},
+              {{ "pattern": "^BBDefense_({opener_pattern})_{size_pattern}$" }},
+              {{ "pattern": "^Defense_({threebettor_pattern})_({opener_pattern})(_{depth_pattern})?$" }},
+              {{ "pattern": "^Vs3Bet_({opener_pattern})_({threebettor_pattern})(_{depth_pattern})?$" }},
+              {{ "pattern": "^Squeeze_({opener_pattern})_({caller_pattern})(_{depth_pattern})?$" }}
+            ]
+          }}
+        }},
+        "coverage_first": {{ "type": "boolean" }},
+        "strict_scoring": {{ "type": "boolean" }},
+        "combo_weighting": {{ "type": "boolean" }},
+        "quiz_folds": {{ "type": "boolean" }},
+        "boundary_favors": {{ "enum": ["passive", "aggressive"] }},
+        "table_size": {{ "enum": [2, 6, 9, "heads_up", "six_max", "nine_max"] }},
+        "stack_depth": {{ "type": "integer", "minimum": 1 }}
+      }}
+    }},
+    "theme": {{
+      "type": "object",
+      "properties": {{
+        "card_colors": {{ "enum": ["two_color", "four_color"] }},
+        "suit_colors": {{
+          "type": "object",
+          "propertyNames": {{ "enum": ["spades", "hearts", "diamonds", "clubs"] }},
+          "additionalProperties": {{ "type": "string", "pattern": "^#?[0-9a-fA-F]{{6}}$" }}
+        }}
+      }}
+    }},
+    "notes": {{
+      "description": "Study notes keyed by hand notation, e.g. A5s",
+      "type": "object",
+      "propertyNames": {{ "pattern": "^[AKQJT2-9]{{2}}[so]?$" }},
+      "additionalProperties": {{ "type": "string" }}
+    }}
+  }},
+  "$defs": {{
+    "range": {{
+      "description": "Comma-separated hands with optional frequencies, e.g. \"22+,A2s+,KQo:0.5\"; '&' joins tokens sharing a frequency",
+      "type": "string"
+    }}
+  }}
+}}
+"##,
+        spot_types = json_string_array(&spot_types),
+    )
+}
+
+fn json_string_array<S: AsRef<str>>(items: &[S]) -> String {
+    let quoted: Vec<String> = items
+        .iter()
+        .map(|item| format!("\"{}\"", item.as_ref()))
+        .collect();
+    format!("[{}]", quoted.join(", "))
+}
+
+// Parses a comma-separated range string such as "22+,A2s+,KQo:0.5".
+//
+// A single comma field may join several tokens with '&', e.g. "QQ+&AQs+:0.5". The
+// field expands to the union of its tokens (here QQ, KK, AA, AQs, AKs) and the
+// frequency, if any, applies to every hand in it.
+//
+// A field starting with '-' removes hands instead, e.g. "pairs,-55-22" is every pair
+// from 66 up. Fields apply left to right, so a removal only takes out hands added
+// before it: "22+,-55,55" still has 55 and "-55,22+" has every pair. Removals take no
+// frequency.
+//
+// "top:15%" is the strongest 15% of all combos, picked like `percent_to_range`, at
+// full frequency.
+//
+// Listing a hand twice is fine as long as both mentions agree on its frequency. Empty
+// fields are not: "AA,,KK", "AA," and ",AA" are EmptyToken errors, since a stray comma
+// usually means a hand went missing. A string that is empty or all whitespace is the
+// empty range.
+pub fn parse_range_str(range_str: &str) -> Result<RangeTable, RangeParseError> {
+    let mut range_map = HashMap::new();
+    if range_str.trim().is_empty() {
+        return Ok(RangeTable::default());
+    }
+    for hand_part in range_str.split(',') {
+        let field = hand_part.trim();
+        if let Some(removal) = field.strip_prefix('-') {
+            if removal.contains(':') {
+                return Err(RangeParseError::RemovalWithFrequency {
+                    field: field.to_string(),
+                });
+            }
+            let mut removals = RangeTable::default();
+            for token in removal.split('&') {
+                for notation in expand_range_token(token.trim(), field)? {
+                    removals.insert(notation, 1.0);
+                }
+            }
+            range_map = RangeTable(range_map).difference(&removals).0;
+            continue;
+        }
+
+        if let Some(percent_str) = field.strip_prefix("top:") {
+            let pct = percent_str
+                .trim()
+                .trim_end_matches('%')
+                .parse::<f32>()
+                .ok()
+                .filter(|pct| (0.0..=100.0).contains(pct))
+                .ok_or_else(|| RangeParseError::InvalidPercent {
+                    field: field.to_string(),
+                })?;
+            for notation in percent_to_range(pct).0.into_keys() {
+                insert_range_hand(&mut range_map, notation, 1.0)?;
+            }
+            continue;
+        }
+
+        // Anything after the first ':' is the frequency, so "AA:0.5:0.3" is an error
+        let (hand_notation_str_raw, frequency) = match field.split_once(':') {
+            Some((hands, frequency_str)) => (hands, parse_range_frequency(frequency_str, field)?),
+            None => (field, 1.0),
+        };
+
+        for token in hand_notation_str_raw.split('&') {
+            for notation in expand_range_token(token.trim(), field)? {
+                insert_range_hand(&mut range_map, notation, frequency)?;
+            }
+        }
+    }
+    Ok(RangeTable(range_map))
+}
+
+// Reads a weighted hand list as PioSOLVER and GTO+ export it: "AA:1.0;KK:1.0;AKs:0.75"
+// or one entry per line. Entries are separated by ';', ',' or newlines, and each is a
+// hand notation or an explicit combo like "AhKh", optionally followed by ":weight".
+// Combos fold into their notation at the average weight over all of its combos, so
+// "AhKh:1" on its own is AKs at 0.25. Blank entries are skipped, since exports often
+// end with a separator.
+pub fn import_range_piosolver(text: &str) -> Result<RangeTable, RangeParseError> {
+    let mut range_map = HashMap::new();
+    // Weight of each listed combo, by its index in `HandNotation::combos`
+    let mut combo_weights: HashMap<HandNotation, HashMap<usize, f32>> = HashMap::new();
+    for entry in text.split([';', ',', '\n']) {
+        let entry = entry.trim();
+        if entry.is_empty() {
+            continue;
+        }
+        let (hand_str, weight) = match entry.split_once(':') {
+            Some((hand_str, weight_str)) => (
+                hand_str.trim(),
+                parse_range_frequency(weight_str.trim(), entry)?,
+            ),
+            None => (entry, 1.0),
+        };
+        let invalid = |reason: String| RangeParseError::InvalidHand {
+            token: entry.to_string(),
+            reason,
+        };
+        if let Ok(notation) = HandNotation::from_str(hand_str) {
+            if combo_weights.contains_key(&notation) {
+                return Err(invalid(format!(
+                    "{} is listed both as a hand and as combos",
+                    notation
+                )));
+            }
+            insert_range_hand(&mut range_map, notation, weight)?;
+            continue;
+        }
+        let combo = Hand::from_str(hand_str).map_err(|_| {
+            invalid(format!(
+                "Invalid hand: {} (expected a hand like AKs or a combo like AhKh)",
+                hand_str
+            ))
+        })?;
+        let notation = HandNotation::from_hand(combo);
+        if range_map.contains_key(&notation) {
+            return Err(invalid(format!(
+                "{} is listed both as a hand and as combos",
+                notation
+            )));
+        }
+        let index = notation
+            .combos()
+            .iter()
+            .position(|hand| {
+                (hand.card1, hand.card2) == (combo.card1, combo.card2)
+                    || (hand.card1, hand.card2) == (combo.card2, combo.card1)
+            })
+            .expect("a hand is one of its notation's combos");
+        match combo_weights
+            .entry(notation)
+            .or_default()
+            .insert(index, weight)
+        {
+            Some(first) if first != weight => {
+                return Err(invalid(format!(
+                    "{} is listed with two weights: {} and {}",
+                    hand_str, first, weight
+                )));
+            }
+            _ => {}
+        }
+    }
+    for (notation, weights) in combo_weights {
+        let total: f32 = weights.values().sum();
+        range_map.insert(notation, total / notation.combo_count() as f32);
+    }
+    Ok(RangeTable(range_map))
+}
+
+// Reads the 13x13 grid CSV that Flopzilla and Equilab export: 13 rows of 13
+// comma-separated percentages from 0 to 100, laid out like
+// `HandNotation::from_grid_position`. A blank cell is a hand that isn't listed. Cells may
+// be quoted, and blank lines are skipped. Past the byte order mark and the quotes that
+// Excel adds, this is the comma-separated case of `RangeTable::from_grid_str`.
+pub fn import_range_grid_csv(text: &str) -> Result<RangeTable, RangeParseError> {
+    let unquoted = text.trim_start_matches('\u{feff}').replace('"', "");
+    RangeTable::from_grid_str(&unquoted)
+}
+
+// A grid cell's percentage from 0 to 100, with or without a trailing '%', as a
+// frequency from 0 to 1.
+fn parse_grid_percent(cell: &str) -> Result<f32, RangeParseError> {
+    let percent = cell
+        .trim_end_matches('%')
+        .parse::<f32>()
+        .map_err(|source| RangeParseError::InvalidFrequency {
+            token: cell.to_string(),
+            source,
+        })?;
+    if !(0.0..=100.0).contains(&percent) {
+        return Err(RangeParseError::FrequencyOutOfRange {
+            token: cell.to_string(),
+            frequency: percent / 100.0,
+        });
+    }
+    Ok(percent / 100.0)
+}
+
+// A frequency after ':', either a fraction ("0.5") or a percentage ("50%"), within
+// [0, 1] so check_answer's thresholds stay meaningful.
+fn parse_range_frequency(frequency_str: &str, field: &str) -> Result<f32, RangeParseError> {
+    let invalid = |source| RangeParseError::InvalidFrequency {
+        token: field.to_string(),
+        source,
+    };
+    let frequency = match frequency_str.trim().strip_suffix('%') {
+        Some(percent_str) => percent_str.trim().parse::<f32>().map_err(invalid)? / 100.0,
+        None => frequency_str.parse::<f32>().map_err(invalid)?,
+    };
+    if !(0.0..=1.0).contains(&frequency) {
+        return Err(RangeParseError::FrequencyOutOfRange {
+            token: field.to_string(),
+            frequency,
+        });
+    }
+    Ok(frequency)
+}
+
+// A hand can't be called and raised more than 100% of the time between them
+fn check_defense_totals(
+    table: &str,
+    call_range: &RangeTable,
+    raise_range: &RangeTable,
+) -> Result<(), ConfigError> {
+    let mut overflows: Vec<String> = call_range
+        .iter()
+        .filter_map(|(hand, &call_freq)| {
+            let raise_freq = raise_range.frequency(*hand);
+            (call_freq + raise_freq > 1.0 + FREQUENCY_EPSILON)
+                .then(|| format!("{} (call {} + raise {})", hand, call_freq, raise_freq))
+        })
+        .collect();
+    if overflows.is_empty() {
+        return Ok(());
+    }
+    overflows.sort();
+    Err(ConfigError::Invalid(format!(
+        "{} calls and raises more than 100% of: {}",
+        table,
+        overflows.join(", ")
+    )))
+}
+
+fn insert_range_hand(
+    range_map: &mut HashMap<HandNotation, f32>,
+    notation: HandNotation,
+    frequency: f32,
+) -> Result<(), RangeParseError> {
+    match range_map.insert(notation, frequency) {
+        Some(first) if first != frequency => Err(RangeParseError::DuplicateHand {
+            hand: notation,
+            first,
+            second: frequency,
+        }),
+        _ => Ok(()),
+    }
+}
+
+// `expand_hand_token` with its errors tied to the token, for `parse_range_str`.
+fn expand_range_token(token: &str, field: &str) -> Result<Vec<HandNotation>, RangeParseError> {
+    if token.is_empty() {
+        return Err(RangeParseError::EmptyToken {
+            field: field.to_string(),
+        });
+    }
+    expand_hand_token(token).map_err(|reason| RangeParseError::InvalidHand {
+        token: token.to_string(),
+        reason,
+    })
+}
+
+// Expands a single token ("AKs", "22+", "A2s+", "55-22", "A5s-A2s" or the group
+// "pairs") into the hand notations it covers.
+fn expand_hand_token(token: &str) -> Result<Vec<HandNotation>, String> {
+    let mut notations = Vec::new();
+    if token == "pairs" {
+        for &rank in Rank::VALUES.iter().rev() {
+            notations.push(HandNotation {
+                rank1: rank,
+                rank2: rank,
+                hand_type: HandType::Pair,
+            });
+        }
+    } else if let Some((from_str, to_str)) = token.split_once('-') {
+        if from_str.is_empty() || to_str.is_empty() {
+            return Err(format!("Missing a hand on one side of '-': {}", token));
+        }
+        let from = HandNotation::from_str(from_str)?;
+        let to = HandNotation::from_str(to_str)?;
+        let same_family = from.hand_type == to.hand_type
+            && (from.hand_type == HandType::Pair || from.rank1 == to.rank1);
+        if !same_family {
+            return Err(format!(
+                "Invalid hand range: {} (both ends need the same high card and suitedness)",
+                token
+            ));
+        }
+        let (high, low) = if from.rank2 >= to.rank2 {
+            (from, to)
+        } else {
+            (to, from)
+        };
+        for &rank in Rank::VALUES.iter().rev() {
+            if rank > high.rank2 || rank < low.rank2 {
+                continue;
+            }
+            notations.push(if high.hand_type == HandType::Pair {
+                HandNotation {
+                    rank1: rank,
+                    rank2: rank,
+                    hand_type: HandType::Pair,
+                }
+            } else {
+                HandNotation {
+                    rank1: high.rank1,
+                    rank2: rank,
+                    hand_type: high.hand_type,
+                }
+            });
+        }
+    } else if let Some(base_hand_str) = token.strip_suffix('+') {
+        let base_hand_notation = HandNotation::from_str(base_hand_str)?;
+
+        if base_hand_notation.hand_type == HandType::Pair {
+            let base_rank = base_hand_notation.rank1;
+            for &rank in Rank::VALUES.iter().rev() {
+                // Iterate from Ace down to Two
+                if rank >= base_rank {
+                    notations.push(HandNotation {
+                        rank1: rank,
+                        rank2: rank,
+                        hand_type: HandType::Pair,
+                    });
+                } else {
+                    break;
+                }
+            }
+        } else {
+            // Handle suited and offsuit '+' notation
+            let base_rank1 = base_hand_notation.rank1;
+            let base_rank2 = base_hand_notation.rank2;
+            let hand_type = base_hand_notation.hand_type;
+
+            // For XYs+ or XYo+, fix the higher rank (rank1) and iterate the lower rank (rank2) upwards
+            // Example: A2s+ means A2s, A3s, ..., AKs (all suited Aces with lower card >= 2)
+            for &rank2_iter in Rank::VALUES.iter() {
+                if rank2_iter >= base_rank2 && rank2_iter < base_rank1 {
+                    // Lower rank must be less than higher rank
+                    notations.push(HandNotation {
+                        rank1: base_rank1,
+                        rank2: rank2_iter,
+                        hand_type,
+                    });
+                } else if rank2_iter >= base_rank1 {
+                    break; // Stop if lower rank becomes higher than or equal to base_rank1
+                }
+            }
+        }
+    } else {
+        notations.push(HandNotation::from_str(token)?);
+    }
+    Ok(notations)
+}
+
+// Turns a range map back into a range string that `parse_range_str` accepts.
+// Hands are listed pairs first, then by high card, suited before offsuit, and
+// frequencies of 1.0 are left implicit. Runs that reach the top with one frequency
+// collapse to plus notation, e.g. "TT+" or "A9s+:0.5".
+pub fn to_range_string(range: &RangeTable) -> String {
+    let mut tokens = Vec::new();
+    let pairs: Vec<HandNotation> = Rank::VALUES
+        .iter()
+        .rev()
+        .map(|&rank| HandNotation {
+            rank1: rank,
+            rank2: rank,
+            hand_type: HandType::Pair,
+        })
+        .collect();
+    tokens.extend(compact_run_tokens(range, &pairs));
+
+    for &rank1 in Rank::VALUES.iter().rev() {
+        for hand_type in [HandType::Suited, HandType::Offsuit] {
+            let kickers: Vec<HandNotation> = Rank::VALUES
+                .iter()
+                .rev()
+                .filter(|&&rank2| rank2 < rank1)
+                .map(|&rank2| HandNotation {
+                    rank1,
+                    rank2,
+                    hand_type,
+                })
+                .collect();
+            tokens.extend(compact_run_tokens(range, &kickers));
+        }
+    }
+    tokens.join(",")
+}
+
+// Tokens for the in-range hands among `notations` (strongest first). A leading run of
+// at least two hands with the same frequency becomes a single "X+" token.
+fn compact_run_tokens(range: &RangeTable, notations: &[HandNotation]) -> Vec<String> {
+    let format_token = |token: String, frequency: f32| {
+        if frequency == 1.0 {
+            token
+        } else {
+            format!("{}:{}", token, frequency)
+        }
+    };
+
+    let mut tokens = Vec::new();
+    let mut rest = notations;
+    if let Some(&top_frequency) = notations.first().and_then(|hn| range.0.get(hn)) {
+        let run_len = notations
+            .iter()
+            .take_while(|hn| range.0.get(hn) == Some(&top_frequency))
+            .count();
+        if run_len >= 2 {
+            tokens.push(format_token(
+                format!("{}+", notations[run_len - 1]),
+                top_frequency,
+            ));
+            rest = &notations[run_len..];
+        }
+    }
+    for hn in rest {
+        if let Some(&frequency) = range.0.get(hn) {
+            tokens.push(format_token(hn.to_string(), frequency));
+        }
+    }
+    tokens
+}
+
+// Parses a range string and re-emits it in the canonical compact form of
+// `to_range_string`: duplicates collapsed, hands sorted, runs in plus notation.
+pub fn normalize_range_str(range_str: &str) -> Result<String, RangeParseError> {
+    Ok(to_range_string(&parse_range_str(range_str)?))
+}
+
+// Position of a hand in the canonical strength ordering; 0 is AA, 168 is 32o.
+pub fn hand_strength_rank(hand_notation: HandNotation) -> usize {
+    HAND_STRENGTH_RANKS[&hand_notation]
+}
+
+// All 169 hand notations from strongest to weakest.
+pub fn hands_by_strength() -> &'static [HandNotation] {
+    &HANDS_BY_STRENGTH
+}
+
+// Number of combos in a range, each hand counted at its frequency.
+pub fn range_combo_count(range: &RangeTable) -> f32 {
+    range
+        .iter()
+        .map(|(hn, &frequency)| hn.combo_count() as f32 * frequency)
+        .sum()
+}
+
+// Removes the weakest hands of a range until about `fraction` of its combos are gone.
+pub fn tighten_range(range: &RangeTable, fraction: f32) -> RangeTable {
+    let combos_to_remove = range_combo_count(range) * fraction.clamp(0.0, 1.0);
+    let mut tightened = range.clone();
+    let mut removed_combos = 0.0;
+    for hn in hands_by_strength().iter().rev() {
+        if removed_combos >= combos_to_remove {
+            break;
+        }
+        if let Some(frequency) = tightened.remove(*hn) {
+            removed_combos += hn.combo_count() as f32 * frequency;
+        }
+    }
+    tightened
+}
+
+// Adds the strongest hands missing from a range, at full frequency, until it has about
+// `fraction` more combos.
+pub fn widen_range(range: &RangeTable, fraction: f32) -> RangeTable {
+    let combos_to_add = range_combo_count(range) * fraction.max(0.0);
+    let mut widened = range.clone();
+    let mut added_combos = 0.0;
+    for &hn in hands_by_strength() {
+        if added_combos >= combos_to_add {
+            break;
+        }
+        if widened.frequency(hn) <= 0.0 {
+            widened.insert(hn, 1.0);
+            added_combos += hn.combo_count() as f32;
+        }
+    }
+    widened
+}
+
+// The strongest `pct` percent of all 1326 combos as a full-frequency range, e.g. 15 for
+// the top 15%. Stops at whichever hand boundary lands closest to the target.
+pub fn percent_to_range(pct: f32) -> RangeTable {
+    let total_combos: f32 = hands_by_strength()
+        .iter()
+        .map(|hn| hn.combo_count() as f32)
+        .sum();
+    let target_combos = total_combos * pct.clamp(0.0, 100.0) / 100.0;
+    let mut range = RangeTable::default();
+    let mut combos = 0.0;
+    for &hn in hands_by_strength() {
+        let next_combos = combos + hn.combo_count() as f32;
+        if next_combos - target_combos > target_combos - combos {
+            break;
+        }
+        range.insert(hn, 1.0);
+        combos = next_combos;
+    }
+    range
+}
+
+// Number of villain combos still possible once hero's cards are removed from the deck.
+// Every hand in the range counts fully, whatever its frequency.
+pub fn remaining_combos(hero: Hand, villain: &RangeTable) -> u32 {
+    villain
+        .iter()
+        .filter(|&(_, &freq)| freq > 0.0)
+        .map(|(hn, _)| {
+            hn.combos()
+                .iter()
+                .filter(|combo| !combo.shares_card_with(&hero))
+                .count() as u32
+        })
+        .sum()
+}
+
+// The range hero is up against in a spot: the opener's range when defending the BB or
+// squeezing. At a stack depth that's the opener's open range at that depth, if configured.
+pub fn villain_range(config: &GameConfig, spot_type: SpotType) -> Option<&RangeTable> {
+    match spot_type {
+        SpotType::Open { .. } | SpotType::PushFold { .. } => None,
+        SpotType::BBDefense {
+            opener_position,
+            stack_depth,
+            ..
+        }
+        | SpotType::Defense {
+            opener_position,
+            stack_depth,
+            ..
+        }
+        | SpotType::Squeeze {
+            opener_position,
+            stack_depth,
+            ..
+        } => stack_depth
+            .and_then(|stack_depth| {
+                config
+                    .unopened_raise_depth_ranges
+                    .get(&(opener_position, stack_depth))
+            })
+            .or_else(|| config.unopened_raise_ranges.get(&opener_position)),
+        // The 3-bettor's 3-bet range isn't configured
+        SpotType::Vs3Bet { .. } | SpotType::SbLimp => None,
+    }
+}
+
+// Feedback line such as "You block 3 of villain's 12 AKo combos, 1 of 4 AKs combos
+// (154 of 162 combos remain)". Lists the three strongest blocked hands; None when hero
+// blocks nothing in the range.
+pub fn blocker_feedback(hero: Hand, villain: &RangeTable) -> Option<String> {
+    let mut blocked: Vec<(HandNotation, usize, u8)> = villain
+        .iter()
+        .filter(|&(_, &freq)| freq > 0.0)
+        .filter_map(|(&hn, _)| {
+            let count = hn
+                .combos()
+                .iter()
+                .filter(|combo| combo.shares_card_with(&hero))
+                .count();
+            (count > 0).then_some((hn, count, hn.combo_count()))
+        })
+        .collect();
+    if blocked.is_empty() {
+        return None;
+    }
+    blocked.sort_by_key(|&(hn, _, _)| hand_strength_rank(hn));
+
+    let total: u32 = villain
+        .iter()
+        .filter(|&(_, &freq)| freq > 0.0)
+        .map(|(hn, _)| hn.combo_count() as u32)
+        .sum();
+    let details: Vec<String> = blocked
+        .iter()
+        .take(3)
+        .enumerate()
+        .map(|(i, (hn, count, combo_count))| {
+            if i == 0 {
+                format!("{} of villain's {} {} combos", count, combo_count, hn)
+            } else {
+                format!("{} of {} {} combos", count, combo_count, hn)
+            }
+        })
+        .collect();
+    Some(format!(
+        "You block {} ({} of {} combos remain)",
+        details.join(", "),
+        remaining_combos(hero, villain),
+        total
+    ))
+}
+
+// Selection weights per notation from a spot's (raise, call) ranges: mixed-strategy
+// hands, with more than one action, are boosted over pure ones, and with
+// `combo_weighting` each weight is scaled by the notation's combo count. Without
+// `quiz_folds`, pure folds get no weight and are never served.
+fn calculate_weighted_hand_notations(
+    (raise_range, call_range): (&RangeTable, &RangeTable),
+    all_notations: &[HandNotation],
+    quiz_folds: bool,
+    combo_weighting: bool,
+) -> Vec<(HandNotation, u32)> {
+    let mut weighted_notations = Vec::new();
+
+    for &hand_notation in all_notations {
+        let freqs = action_frequencies_in(hand_notation, raise_range, call_range);
+        let actions = [freqs.raise, freqs.call, freqs.fold]
+            .iter()
+            .filter(|&&freq| freq > 0.0)
+            .count();
+        let mut weight = if freqs.fold >= 1.0 {
+            // Default weight for hands not in any range
+            if quiz_folds { 20 } else { 0 }
+        } else if actions > 1 {
+            5000 // High weight for mixed strategy hands, e.g. call 50% and raise 50%
+        } else {
+            50 // Reduced weight for solid in-range hands
+        };
+        if combo_weighting {
+            weight *= hand_notation.combo_count() as u32;
+        }
+        weighted_notations.push((hand_notation, weight));
+    }
+    weighted_notations
+}
+
+// The range that drives hand selection for a spot. For BB defense this combines the
+// call and raise ranges of the given opener.
+fn spot_target_range(config: &GameConfig, spot_type: SpotType) -> RangeTable {
+    match spot_type {
+        SpotType::Open { .. } | SpotType::PushFold { .. } => config.open_range(spot_type).clone(),
+        SpotType::BBDefense { .. }
+        | SpotType::Defense { .. }
+        | SpotType::Vs3Bet { .. }
+        | SpotType::Squeeze { .. }
+        | SpotType::SbLimp => {
+            let (call_map, raise_map) = call_raise_spot_ranges(config, spot_type);
+            // A hand in both plays at its total action frequency
+            call_map.union(raise_map)
+        }
+    }
+}
+
+// The (call, raise) ranges for a spot facing a raise; empty for open spots. A depth
+// without its own ranges is empty, like an open depth.
+fn call_raise_spot_ranges(config: &GameConfig, spot_type: SpotType) -> (&RangeTable, &RangeTable) {
+    fn ranges_at<'a, K: Eq + std::hash::Hash>(
+        call_ranges: &'a HashMap<K, RangeTable>,
+        raise_ranges: &'a HashMap<K, RangeTable>,
+        key: K,
+    ) -> (&'a RangeTable, &'a RangeTable) {
+        (
+            call_ranges.get(&key).unwrap_or(&EMPTY_HAND_RANGE),
+            raise_ranges.get(&key).unwrap_or(&EMPTY_HAND_RANGE),
+        )
+    }
+    match spot_type {
+        SpotType::Open { .. } | SpotType::PushFold { .. } => (&EMPTY_HAND_RANGE, &EMPTY_HAND_RANGE),
+        SpotType::BBDefense {
+            opener_position,
+            open_size,
+            stack_depth: None,
+        } => config.bb_defense_ranges(opener_position, open_size),
+        // Depth ranges aren't keyed by open size
+        SpotType::BBDefense {
+            opener_position,
+            stack_depth: Some(stack_depth),
+            ..
+        } => ranges_at(
+            &config.defense_depth_call_ranges,
+            &config.defense_depth_raise_ranges,
+            (Position::BB, opener_position, stack_depth),
+        ),
+        SpotType::Defense {
+            hero_position,
+            opener_position,
+            stack_depth: None,
+        } => ranges_at(
+            &config.defense_call_ranges,
+            &config.defense_raise_ranges,
+            (hero_position, opener_position),
+        ),
+        SpotType::Defense {
+            hero_position,
+            opener_position,
+            stack_depth: Some(stack_depth),
+        } => ranges_at(
+            &config.defense_depth_call_ranges,
+            &config.defense_depth_raise_ranges,
+            (hero_position, opener_position, stack_depth),
+        ),
+        SpotType::Vs3Bet {
+            opener_position,
+            threebettor_position,
+            stack_depth: None,
+        } => ranges_at(
+            &config.vs_3bet_call_ranges,
+            &config.vs_3bet_raise_ranges,
+            (opener_position, threebettor_position),
+        ),
+        SpotType::Vs3Bet {
+            opener_position,
+            threebettor_position,
+            stack_depth: Some(stack_depth),
+        } => ranges_at(
+            &config.vs_3bet_depth_call_ranges,
+            &config.vs_3bet_depth_raise_ranges,
+            (opener_position, threebettor_position, stack_depth),
+        ),
+        SpotType::Squeeze {
+            opener_position,
+            caller_position,
+            stack_depth: None,
+        } => ranges_at(
+            &config.squeeze_call_ranges,
+            &config.squeeze_raise_ranges,
+            (opener_position, caller_position),
+        ),
+        SpotType::Squeeze {
+            opener_position,
+            caller_position,
+            stack_depth: Some(stack_depth),
+        } => ranges_at(
+            &config.squeeze_depth_call_ranges,
+            &config.squeeze_depth_raise_ranges,
+            (opener_position, caller_position, stack_depth),
+        ),
+        // Limping takes the place of calling
+        SpotType::SbLimp => (
+            &config.sb_limp_ranges,
+            config
+                .unopened_raise_ranges
+                .get(&Position::SB)
+                .unwrap_or(&EMPTY_HAND_RANGE),
+        ),
+    }
+}
+
+// Number of distinct (spot type, hand notation) combinations the trainer can present
+// with the given config, i.e. those with a nonzero selection weight.
+pub fn unique_spot_count(config: &GameConfig) -> usize {
+    let all_notations = get_all_possible_hand_notations();
+    let mut seen_spot_types: Vec<SpotType> = Vec::new();
+    let mut count = 0;
+    for &spot_type in &config.allowed_spot_types {
+        if seen_spot_types.contains(&spot_type) {
+            continue;
+        }
+        seen_spot_types.push(spot_type);
+        count += calculate_weighted_hand_notations(
+            spot_action_ranges(config, spot_type),
+            &all_notations,
+            config.quiz_folds,
+            config.combo_weighting,
+        )
+        .iter()
+        .filter(|&&(_, weight)| weight > 0)
+        .count();
+    }
+    count
+}
+
+// --- Deck Structure ---
+#[derive(Debug, Clone)]
+pub struct Deck {
+    cards: Vec<Card>,
+    // The combos of each notation still in `cards`, in deck order. Rebuilt on shuffle and
+    // kept up to date by `deal_hand` and `remove_cards`
+    available_combos: HashMap<HandNotation, Vec<Hand>>,
+}
+
+impl Deck {
+    pub fn new() -> Self {
+        let mut cards = Vec::with_capacity(52);
+        for &suit in &Suit::VALUES {
+            for &rank in &Rank::VALUES {
+                cards.push(Card { rank, suit });
+            }
+        }
+        let mut deck = Deck {
+            cards,
+            available_combos: HashMap::new(),
+        };
+        deck.index_combos();
+        deck
+    }
+
+    fn index_combos(&mut self) {
+        self.available_combos.clear();
+        for (i, &card1) in self.cards.iter().enumerate() {
+            for &card2 in &self.cards[i + 1..] {
+                let hand = Hand { card1, card2 };
+                self.available_combos
+                    .entry(HandNotation::from_hand(hand))
+                    .or_default()
+                    .push(hand);
+            }
+        }
+    }
+
+    // Drops every indexed combo that uses `card`; only notations with its rank can.
+    fn unindex_card(&mut self, card: Card) {
+        for other in Rank::VALUES {
+            let (rank1, rank2) = (
+                std::cmp::max(card.rank, other),
+                std::cmp::min(card.rank, other),
+            );
+            let hand_types: &[HandType] = if other == card.rank {
+                &[HandType::Pair]
+            } else {
+                &[HandType::Suited, HandType::Offsuit]
+            };
+            for &hand_type in hand_types {
+                let notation = HandNotation {
+                    rank1,
+                    rank2,
+                    hand_type,
+                };
+                if let Some(combos) = self.available_combos.get_mut(&notation) {
+                    combos.retain(|combo| combo.card1 != card && combo.card2 != card);
+                }
+            }
+        }
+    }
+
+    // The cards still in the deck; `deal_hand` takes them from the end.
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    // The hands of a notation that can still be dealt, in the order a scan of `cards`
+    // finds them; each hand's `card1` comes before its `card2` in `cards`.
+    pub fn available_combos(&self, notation: HandNotation) -> &[Hand] {
+        self.available_combos
+            .get(&notation)
+            .map_or(&[], Vec::as_slice)
+    }
+
+    // Takes the given cards out of the deck.
+    pub fn remove_cards(&mut self, removed: &[Card]) {
+        self.cards.retain(|card| !removed.contains(card));
+        for &card in removed {
+            self.unindex_card(card);
+        }
+    }
+
+    pub fn shuffle(&mut self) {
+        let mut rng = ThreadRng::default();
+        self.shuffle_with(&mut rng);
+    }
+
+    pub fn shuffle_with<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.cards.shuffle(rng);
+        self.index_combos();
+    }
+
+    pub fn deal_hand(&mut self) -> Option<Hand> {
+        if self.cards.len() < 2 {
+            return None;
+        }
+        let card1 = self.cards.pop()?;
+        let card2 = self.cards.pop()?;
+        self.unindex_card(card1);
+        self.unindex_card(card2);
+        Some(Hand { card1, card2 })
+    }
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// --- Game State ---
+#[derive(Debug, Clone)]
+pub struct Game {
+    deck: Deck,
+    config: GameConfig,
+    all_possible_hand_notations: Vec<HandNotation>,
+    rng: StdRng,
+    last_opener_hand: Option<Hand>,
+    // Notations served so far in the current coverage-first cycle of each spot type
+    covered_notations: HashMap<SpotType, std::collections::HashSet<HandNotation>>,
+    // Selection weights of each spot type served so far, worked out once per spot type and
+    // dropped when `set_config` swaps the config
+    spot_weights: HashMap<SpotType, Vec<(HandNotation, u32)>>,
+    // Spots still to serve, in order, for a scripted game
+    script: Option<VecDeque<(SpotType, Hand, u8)>>,
+    // When any allowed spot is in here, only those are served; see `set_focus`
+    focus: Vec<SpotType>,
+}
+
+// The part of a Game that dealing changes, for `TrainerSession::undo_last`. The config
+// and the cached weights don't change between deals, so they aren't copied.
+#[derive(Debug, Clone)]
+struct DealState {
+    deck: Deck,
+    rng: StdRng,
+    last_opener_hand: Option<Hand>,
+    covered_notations: HashMap<SpotType, std::collections::HashSet<HandNotation>>,
+}
+
+impl Game {
+    pub fn new(config: GameConfig) -> Self {
+        Self::with_rng(config, StdRng::from_os_rng())
+    }
+
+    // Same config and seed always produce the same sequence of spots.
+    pub fn new_with_seed(config: GameConfig, seed: u64) -> Self {
+        Self::with_rng(config, StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(config: GameConfig, rng: StdRng) -> Self {
+        let all_possible_hand_notations = get_all_possible_hand_notations();
+        let mut game = Game {
+            deck: Deck::new(),
+            config,
+            all_possible_hand_notations,
+            rng,
+            last_opener_hand: None,
+            covered_notations: HashMap::new(),
+            spot_weights: HashMap::new(),
+            script: None,
+            focus: Vec::new(),
+        };
+        game.deck.shuffle_with(&mut game.rng);
+        game
+    }
+
+    // A game that serves exactly these spots, in order, instead of random ones, e.g. to
+    // replay a spot list from `load_spot_list`. Once they have all been served,
+    // `generate_random_spot` returns an error.
+    pub fn new_scripted(config: GameConfig, spots: Vec<(SpotType, Hand, u8)>) -> Self {
+        let mut game = Self::new(config);
+        game.script = Some(spots.into());
+        game
+    }
+
+    // Swaps in another config, e.g. a different profile from `load_profiles`, without
+    // restarting. The deck and RNG carry on; coverage and the cached weights start over.
+    pub fn set_config(&mut self, config: GameConfig) {
+        self.config = config;
+        self.spot_weights.clear();
+        self.covered_notations.clear();
+        self.last_opener_hand = None;
+    }
+
+    // Serves only the allowed spots in `spot_types`, e.g. the ones not mastered yet. With
+    // none of them allowed, every allowed spot is served again. Scripted games ignore it.
+    pub fn set_focus(&mut self, spot_types: Vec<SpotType>) {
+        self.focus = spot_types;
+    }
+
+    // Starts over with a full, freshly shuffled deck and a new coverage cycle, e.g. for
+    // "Play Again". The config and its cached weights are kept.
+    pub fn reset(&mut self) {
+        self.reshuffle();
+        self.covered_notations.clear();
+        self.last_opener_hand = None;
+    }
+
+    fn reshuffle(&mut self) {
+        self.deck = Deck::new();
+        self.deck.shuffle_with(&mut self.rng);
+    }
+
+    fn deal_state(&self) -> DealState {
+        DealState {
+            deck: self.deck.clone(),
+            rng: self.rng.clone(),
+            last_opener_hand: self.last_opener_hand,
+            covered_notations: self.covered_notations.clone(),
+        }
+    }
+
+    // Rolls dealing back to `state`. `served` are the spots dealt since, oldest first; a
+    // scripted game puts them back to serve again.
+    fn restore_deal_state(&mut self, state: DealState, served: &[(SpotType, Hand, u8)]) {
+        self.deck = state.deck;
+        self.rng = state.rng;
+        self.last_opener_hand = state.last_opener_hand;
+        self.covered_notations = state.covered_notations;
+        if let Some(script) = &mut self.script {
+            for &spot in served.iter().rev() {
+                script.push_front(spot);
+            }
+        }
+    }
+
+    // The spot the next `generate_random_spot` call will return, without consuming it.
+    // Works on a copy of the game, so the deck, RNG and coverage state are untouched.
+    pub fn peek_next_spot(&self) -> Result<(SpotType, Hand, u8), CoreError> {
+        self.clone().generate_random_spot()
+    }
+
+    // Picks one of the allowed spot types, then a notation for it by selection weight.
+    // None when the spot has no hand left to serve.
+    fn pick_spot_and_notation(&mut self) -> Result<Option<(SpotType, HandNotation)>, CoreError> {
+        // If no allowed spot types are configured, no spots can be generated
+        if self.config.allowed_spot_types.is_empty() {
+            return Err(CoreError::SpotGeneration(
+                "No valid spot types configured or able to be generated. Please configure 'allowed_spot_types' in GameConfig.".to_string(),
+            ));
+        }
+
+        // Randomly select one of the allowed spot types, within the focus if it has any
+        let focused: Vec<SpotType> = self
+            .config
+            .allowed_spot_types
+            .iter()
+            .filter(|spot_type| self.focus.contains(spot_type))
+            .copied()
+            .collect();
+        let candidates = if focused.is_empty() {
+            &self.config.allowed_spot_types
+        } else {
+            &focused
+        };
+        let spot_type = *candidates
+            .choose(&mut self.rng)
+            .expect("Should always be able to choose from a non-empty list of allowed spot types");
+        if !self.config.table_size.has_seats_for(spot_type) {
+            return Err(CoreError::SpotGeneration(format!(
+                "Invalid spot {}: not every seat is at a {}-handed table",
+                spot_type.to_config_string(),
+                self.config.table_size.seats()
+            )));
+        }
+        if let SpotType::Defense {
+            hero_position,
+            opener_position,
+            ..
+        } = spot_type
+            && !acts_after(hero_position, opener_position)
+        {
+            return Err(CoreError::SpotGeneration(format!(
+                "Invalid spot {}: {} doesn't act after {}",
+                spot_type.to_config_string(),
+                hero_position,
+                opener_position
+            )));
+        }
+        let weighted_hand_notations = self.spot_weights.entry(spot_type).or_insert_with(|| {
+            calculate_weighted_hand_notations(
+                spot_action_ranges(&self.config, spot_type),
+                &self.all_possible_hand_notations,
+                self.config.quiz_folds,
+                self.config.combo_weighting,
+            )
+        });
+
+        let covered = if self.config.coverage_first {
+            let covered = self.covered_notations.entry(spot_type).or_default();
+            // Start a new cycle once every servable notation has come up
+            if weighted_hand_notations
+                .iter()
+                .all(|(hn, weight)| *weight == 0 || covered.contains(hn))
+            {
+                covered.clear();
+            }
+            Some(&*covered)
+        } else {
+            None
+        };
+        // Notations already covered this cycle are skipped
+        let weight_of = |&(hn, weight): &(HandNotation, u32)| match covered {
+            Some(covered) if covered.contains(&hn) => 0,
+            _ => weight,
+        };
+
+        // Manual weighted selection of a HandNotation
+        let total_weight: u32 = weighted_hand_notations.iter().map(weight_of).sum();
+        if total_weight == 0 {
+            // Without quiz_folds, spots whose ranges are all folds have nothing to
+            // serve; give up only if that's true of every allowed spot.
+            if !self.config.quiz_folds && unique_spot_count(&self.config) == 0 {
+                return Err(CoreError::SpotGeneration(
+                    "No allowed spot type has a hand to play and quiz_folds is off.".to_string(),
+                ));
+            }
+            // The selected range is empty or has no weighted hands
+            return Ok(None);
+        }
+
+        let mut rand_weight = self.rng.random_range(0..total_weight);
+        let chosen_hand_notation = weighted_hand_notations
+            .iter()
+            .find_map(|entry| {
+                let (hn, weight) = (entry.0, weight_of(entry));
+                if rand_weight < weight {
+                    Some(hn)
+                } else {
+                    rand_weight -= weight;
+                    None
+                }
+            })
+            .expect("Weighted selection failed to find a hand");
+        Ok(Some((spot_type, chosen_hand_notation)))
+    }
+
+    // Like `generate_random_spot`, but only picks the hand notation: no cards are dealt,
+    // for hero or the opener. Coverage-first cycles count the notation as served.
+    pub fn generate_random_notation(&mut self) -> Result<(SpotType, HandNotation, u8), CoreError> {
+        if let Some(script) = &mut self.script {
+            self.last_opener_hand = None;
+            return script
+                .pop_front()
+                .map(|(spot_type, hand, rng_value)| {
+                    (spot_type, HandNotation::from_hand(hand), rng_value)
+                })
+                .ok_or_else(|| {
+                    CoreError::SpotGeneration("Every scripted spot has been served.".to_string())
+                });
+        }
+        self.last_opener_hand = None;
+        loop {
+            let Some((spot_type, hand_notation)) = self.pick_spot_and_notation()? else {
+                continue;
+            };
+            if self.config.coverage_first {
+                self.covered_notations
+                    .entry(spot_type)
+                    .or_default()
+                    .insert(hand_notation);
+            }
+            let mixed_strategy_rng_value: u8 = self.rng.random_range(0..100);
+            return Ok((spot_type, hand_notation, mixed_strategy_rng_value));
+        }
+    }
+
+    pub fn generate_random_spot(&mut self) -> Result<(SpotType, Hand, u8), CoreError> {
+        if let Some(script) = &mut self.script {
+            self.last_opener_hand = None;
+            return script.pop_front().ok_or_else(|| {
+                CoreError::SpotGeneration("Every scripted spot has been served.".to_string())
+            });
+        }
+        loop {
+            // Reshuffle if deck is empty or too few cards
+            if self.deck.len() < 2 {
+                self.reshuffle();
+            }
+
+            // 1. Pick the spot type and a HandNotation for it; if the spot has no hand to
+            // serve, reshuffle and try to get a new spot and hand.
+            let Some((spot_type, chosen_hand_notation)) = self.pick_spot_and_notation()? else {
+                self.reshuffle();
+                continue;
+            };
+
+            // 3. Attempt to deal the concrete hand
+            if let Some(hand) = self.try_deal_specific_hand(&chosen_hand_notation) {
+                if self.config.coverage_first {
+                    self.covered_notations
+                        .entry(spot_type)
+                        .or_default()
+                        .insert(chosen_hand_notation);
+                }
+
+                // For BB defense, also deal the opener a hand they would have opened
+                self.last_opener_hand = None;
+                if villain_range(&self.config, spot_type)
+                    .is_some_and(|open_range| open_range.iter().any(|(_, &freq)| freq > 0.0))
+                {
+                    match self.deal_opener_hand(spot_type) {
+                        Some(opener_hand) => self.last_opener_hand = Some(opener_hand),
+                        None => {
+                            // The remaining deck can't make an opening hand; start over
+                            self.reshuffle();
+                            continue;
+                        }
+                    }
+                }
+
+                // 4. Generate RNG value for mixed strategies
+                let mixed_strategy_rng_value: u8 = self.rng.random_range(0..100);
+                return Ok((spot_type, hand, mixed_strategy_rng_value));
+            }
+            // If try_deal_specific_hand returns None, we reshuffle and try again.
+            self.reshuffle();
+        }
+    }
+
+    // The opener's hand in the last spot from `generate_random_spot`.
+    //
+    // For BB defense spots whose opener has a configured open range, the opener is dealt
+    // a hand from the cards left after hero's, picked with probability proportional to
+    // its open frequency. So the opener always holds a hand from their open range that
+    // doesn't clash with hero's cards; scenarios where no such hand is left are thrown
+    // away and generated again. None for open spots and openers without a range.
+    pub fn last_opener_hand(&self) -> Option<Hand> {
+        self.last_opener_hand
+    }
+
+    fn deal_opener_hand(&mut self, spot_type: SpotType) -> Option<Hand> {
+        let open_range = villain_range(&self.config, spot_type)?;
+        let deck_cards: std::collections::HashSet<Card> =
+            self.deck.cards().iter().copied().collect();
+        let candidates: Vec<(Hand, f32)> = open_range
+            .iter()
+            .filter(|&(_, &freq)| freq > 0.0)
+            .flat_map(|(hn, &freq)| hn.combos().into_iter().map(move |combo| (combo, freq)))
+            .filter(|(combo, _)| {
+                deck_cards.contains(&combo.card1) && deck_cards.contains(&combo.card2)
+            })
+            .collect();
+        let &(hand, _) = candidates
+            .choose_weighted(&mut self.rng, |&(_, freq)| freq)
+            .ok()?;
+        self.deck.remove_cards(&[hand.card1, hand.card2]);
+        Some(hand)
+    }
+
+    // Same as `generate_random_spot` plus a second, independent RNG value, for scoring
+    // with `check_answer_dual_rng`.
+    pub fn generate_random_spot_dual_rng(&mut self) -> Result<(SpotType, Hand, u8, u8), CoreError> {
+        let (spot_type, hand, raise_rng_value) = self.generate_random_spot()?;
+        let rest_rng_value: u8 = self.rng.random_range(0..100);
+        Ok((spot_type, hand, raise_rng_value, rest_rng_value))
+    }
+
+    // Deals the first hand of the given notation found in the current deck, scanning in
+    // deck order rather than picking at random. Same deck state, same hand; meant for
+    // tests and scripted lessons. None if the deck holds no such hand.
+    pub fn deal_notation_exact(&mut self, target_notation: HandNotation) -> Option<Hand> {
+        let hand = *self.deck.available_combos(target_notation).first()?;
+        self.deck.remove_cards(&[hand.card1, hand.card2]);
+        Some(hand)
+    }
+
+    // Another helper function: tries to deal a specific hand from the current deck without reshuffling
+    fn try_deal_specific_hand(&mut self, target_notation: &HandNotation) -> Option<Hand> {
+        // Pick a random matching hand from the ones left in the deck; None if there are none
+        let hand_to_deal = *self
+            .deck
+            .available_combos(*target_notation)
+            .choose(&mut self.rng)?;
+        self.deck
+            .remove_cards(&[hand_to_deal.card1, hand_to_deal.card2]);
+        Some(hand_to_deal)
+    }
+}
+
+pub fn check_answer(
+    config: &GameConfig,
+    spot_type: SpotType,
+    hand: Hand,
+    user_action: UserAction,
+    mixed_strategy_rng_value: u8,
+) -> AnswerResult {
+    check_answer_detailed(
+        config,
+        spot_type,
+        hand,
+        user_action,
+        mixed_strategy_rng_value,
+    )
+    .result
+}
+
+// How an answer was scored and why: the hand's strategy in the spot, the RNG value and
+// the action it dictates. Enough to explain "you raised, but 72 is past the 50% raise
+// band, so this one folds".
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnswerFeedback {
+    pub result: AnswerResult,
+    pub correct_action: UserAction,
+    pub raise_freq: f32,
+    pub call_freq: f32,
+    pub fold_freq: f32,
+    pub rng_value: u8,
+}
+
+// Like `check_answer`, but returns the frequencies and the dictated action the result
+// was based on along with it.
+pub fn check_answer_detailed(
+    config: &GameConfig,
+    spot_type: SpotType,
+    hand: Hand,
+    user_action: UserAction,
+    mixed_strategy_rng_value: u8,
+) -> AnswerFeedback {
+    let hand_notation = HandNotation::from_hand(hand);
+    let (raise_freq, call_freq, fold_freq) = get_action_frequencies(config, spot_type, hand);
+    let correct_action = get_correct_action(config, spot_type, hand, mixed_strategy_rng_value);
+
+    let result = match spot_type {
+        SpotType::Open { .. } | SpotType::PushFold { .. } => {
+            if matches!(user_action, UserAction::Call | UserAction::Limp) {
+                // Cannot call an unopened pot
+                AnswerResult::Wrong
+            } else if user_action == correct_action {
+                AnswerResult::Correct
+            } else if raise_freq > 0.0 && raise_freq < 1.0 {
+                // Right play for the hand, wrong side of the mix
+                AnswerResult::FrequencyMistake
+            } else {
+                AnswerResult::Wrong
+            }
+        }
+        SpotType::BBDefense { .. }
+        | SpotType::Defense { .. }
+        | SpotType::Vs3Bet { .. }
+        | SpotType::Squeeze { .. }
+        | SpotType::SbLimp => {
+            let (call_range, raise_range) = call_raise_spot_ranges(config, spot_type);
+
+            grade_raise_call_fold(
+                user_action,
+                correct_action,
+                raise_range.frequency(hand_notation),
+                call_range.frequency(hand_notation),
+                spot_type.passive_action(),
+            )
+        }
+    };
+
+    AnswerFeedback {
+        result,
+        correct_action,
+        raise_freq,
+        call_freq,
+        fold_freq,
+        rng_value: mixed_strategy_rng_value,
+    }
+}
+
+// The action the RNG value dictates: the raise, call and fold frequencies are stacked on
+// 0-99 and the value picks the band it falls in. This is the one RNG-to-action mapping:
+// `check_answer` scores exactly this action as correct and the GUI highlights it. Open
+// spots have no call band, so it's only ever Raise or Fold there.
+pub fn get_correct_action(
+    config: &GameConfig,
+    spot_type: SpotType,
+    hand: Hand,
+    mixed_strategy_rng_value: u8,
+) -> UserAction {
+    let (raise_freq, call_freq, _) = get_action_frequencies(config, spot_type, hand);
+    match action_for_rng(
+        raise_freq,
+        call_freq,
+        mixed_strategy_rng_value,
+        config.boundary_favors,
+    ) {
+        UserAction::Call => spot_type.passive_action(),
+        action => action,
+    }
+}
+
+// Maps an RNG value to an action given the raise and call frequencies. The bands are
+// stacked on 0-99 in the order raise, call, fold, with edges at the rounded percentages:
+// raise 33.7% / call 40% gives raise 0-33, call 34-73 and fold 74-99.
+// `boundary_favors` decides which side a value exactly on a band edge lands on; with
+// `Aggressive` the values 34 and 74 above go to raise and call instead.
+pub fn action_for_rng(
+    raise_freq: f32,
+    call_freq: f32,
+    rng_value: u8,
+    boundary_favors: BoundaryFavors,
+) -> UserAction {
+    let raise_threshold = rng_threshold(raise_freq);
+    // Rounded from the running total, so the bands always add up to 100
+    let call_threshold = rng_threshold(raise_freq + call_freq).max(raise_threshold);
+
+    if below_threshold(rng_value, 0, raise_threshold, boundary_favors) {
+        UserAction::Raise
+    } else if below_threshold(rng_value, raise_threshold, call_threshold, boundary_favors) {
+        UserAction::Call
+    } else {
+        UserAction::Fold
+    }
+}
+
+// A frequency as the number of RNG values (out of 0-99) that play it, rounded to the
+// nearest percent.
+fn rng_threshold(freq: f32) -> u8 {
+    (freq * 100.0).round().clamp(0.0, 100.0) as u8
+}
+
+// Whether `rng_value` lands in the band that ends at `threshold`. An empty band
+// (starting at `threshold`) never takes a value, even on the boundary.
+fn below_threshold(
+    rng_value: u8,
+    band_start: u8,
+    threshold: u8,
+    boundary_favors: BoundaryFavors,
+) -> bool {
+    match boundary_favors {
+        BoundaryFavors::Passive => rng_value < threshold,
+        BoundaryFavors::Aggressive => {
+            rng_value < threshold || (rng_value == threshold && threshold > band_start)
+        }
+    }
+}
+
+// Grades an answer in a spot where raise, call and fold are all available.
+// `passive_action` is the action played at the call frequency (call or limp).
+fn grade_raise_call_fold(
+    user_action: UserAction,
+    correct_action: UserAction,
+    raise_freq: f32,
+    call_freq: f32,
+    passive_action: UserAction,
+) -> AnswerResult {
+    if user_action == correct_action {
+        AnswerResult::Correct
+    } else {
+        // The user's action did not match the action dictated by the RNG.
+        // We return `FrequencyMistake` if the user's action is *any* valid part of the
+        // hand's overall strategy (even if it's not correct for this specific RNG).
+        // Otherwise, it's just plain `Wrong`.
+        let is_raise_possible = raise_freq > FREQUENCY_EPSILON;
+        let is_call_possible = call_freq > FREQUENCY_EPSILON;
+        let is_fold_possible = (raise_freq + call_freq) < 1.0 - FREQUENCY_EPSILON;
+
+        let is_user_action_part_of_strategy = (user_action == UserAction::Raise
+            && is_raise_possible)
+            || (user_action == passive_action && is_call_possible)
+            || (user_action == UserAction::Fold && is_fold_possible);
+
+        if is_user_action_part_of_strategy {
+            AnswerResult::FrequencyMistake
+        } else {
+            AnswerResult::Wrong
+        }
+    }
+}
+
+// Like `check_answer`, but with two independent RNG values instead of one.
+//
+// `check_answer` stacks the raise, call and fold bands on a single 0-99 value, so e.g.
+// with raise 30% / call 40% the values 0-29 raise, 30-69 call and 70-99 fold. Here the
+// decision is made in two steps instead: `raise_rng_value` decides raise versus
+// everything else (raise when below the raise frequency), then `rest_rng_value`
+// decides call versus fold among the remaining frequency (call when below
+// call / (1 - raise)). Both models play each action at the configured frequency.
+// Open spots only have one decision and use `raise_rng_value` alone.
+pub fn check_answer_dual_rng(
+    config: &GameConfig,
+    spot_type: SpotType,
+    hand: Hand,
+    user_action: UserAction,
+    raise_rng_value: u8,
+    rest_rng_value: u8,
+) -> AnswerResult {
+    match spot_type {
+        SpotType::Open { .. } | SpotType::PushFold { .. } => {
+            check_answer(config, spot_type, hand, user_action, raise_rng_value)
+        }
+        SpotType::BBDefense { .. }
+        | SpotType::Defense { .. }
+        | SpotType::Vs3Bet { .. }
+        | SpotType::Squeeze { .. }
+        | SpotType::SbLimp => {
+            let (raise_freq, call_freq, _) = get_action_frequencies(config, spot_type, hand);
+
+            let raise_threshold = rng_threshold(raise_freq);
+            let remaining_freq = 1.0 - raise_freq;
+            let call_share = if remaining_freq > FREQUENCY_EPSILON {
+                (call_freq / remaining_freq).min(1.0)
+            } else {
+                0.0
+            };
+            let call_threshold = rng_threshold(call_share);
+
+            let favors = config.boundary_favors;
+            let correct_action = if below_threshold(raise_rng_value, 0, raise_threshold, favors) {
+                UserAction::Raise
+            } else if below_threshold(rest_rng_value, 0, call_threshold, favors) {
+                spot_type.passive_action()
+            } else {
+                UserAction::Fold
+            };
+
+            grade_raise_call_fold(
+                user_action,
+                correct_action,
+                raise_freq,
+                call_freq,
+                spot_type.passive_action(),
+            )
+        }
+    }
+}
+
+pub fn get_action_frequencies(
+    config: &GameConfig,
+    spot_type: SpotType,
+    hand: Hand,
+) -> (f32, f32, f32) {
+    // (raise, call, fold)
+    let (raise_range, call_range) = spot_action_ranges(config, spot_type);
+    let freqs = action_frequencies_in(HandNotation::from_hand(hand), raise_range, call_range);
+    (freqs.raise, freqs.call, freqs.fold)
+}
+
+// (raise, limp, fold) when it's folded to the small blind and limping is allowed.
+pub fn get_sb_action_frequencies(config: &GameConfig, hand: Hand) -> (f32, f32, f32) {
+    get_action_frequencies(config, SpotType::SbLimp, hand)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ActionFrequencies {
+    pub raise: f32,
+    pub call: f32,
+    pub fold: f32,
+}
+
+// Action frequencies of all 169 notations in a spot, in `get_all_possible_hand_notations`
+// order. Looks the spot's ranges up once instead of per hand.
+pub fn all_action_frequencies(
+    config: &GameConfig,
+    spot_type: SpotType,
+) -> Vec<(HandNotation, ActionFrequencies)> {
+    let (raise_range, call_range) = spot_action_ranges(config, spot_type);
+    get_all_possible_hand_notations()
+        .into_iter()
+        .map(|hn| (hn, action_frequencies_in(hn, raise_range, call_range)))
+        .collect()
+}
+
+// The (raise, call) ranges of a spot. Open spots have no call range.
+fn spot_action_ranges(config: &GameConfig, spot_type: SpotType) -> (&RangeTable, &RangeTable) {
+    match spot_type {
+        SpotType::Open { .. } | SpotType::PushFold { .. } => {
+            (config.open_range(spot_type), &EMPTY_HAND_RANGE)
+        }
+        SpotType::BBDefense { .. }
+        | SpotType::Defense { .. }
+        | SpotType::Vs3Bet { .. }
+        | SpotType::Squeeze { .. }
+        | SpotType::SbLimp => {
+            let (call_range, raise_range) = call_raise_spot_ranges(config, spot_type);
+            (raise_range, call_range)
+        }
+    }
+}
+
+fn action_frequencies_in(
+    hand_notation: HandNotation,
+    raise_range: &RangeTable,
+    call_range: &RangeTable,
+) -> ActionFrequencies {
+    let raise = snap_frequency(raise_range.frequency(hand_notation));
+    let call = snap_frequency(call_range.frequency(hand_notation));
+    ActionFrequencies {
+        raise,
+        call,
+        fold: snap_frequency(1.0 - (raise + call).min(1.0)),
+    }
+}
+
+// The strategy for a hand in words: "Raise" for a pure action, "Raise 50%, Fold 50%"
+// for a mixed one.
+pub fn recommended_play(config: &GameConfig, spot_type: SpotType, hand: Hand) -> String {
+    let (raise_freq, call_freq, fold_freq) = get_action_frequencies(config, spot_type, hand);
+    let passive_name = match spot_type.passive_action() {
+        UserAction::Limp => "Limp",
+        _ => "Call",
+    };
+    let actions: Vec<(&str, f32)> = [
+        ("Raise", raise_freq),
+        (passive_name, call_freq),
+        ("Fold", fold_freq),
+    ]
+    .into_iter()
+    .filter(|&(_, freq)| freq > FREQUENCY_EPSILON)
+    .collect();
+    match actions.as_slice() {
+        [(name, _)] => name.to_string(),
+        _ => actions
+            .iter()
+            .map(|(name, freq)| format!("{} {:.0}%", name, freq * 100.0))
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
+
+// A markdown worksheet for offline study: the spots as numbered questions with a blank
+// for the answer, followed by an answer key.
+pub fn export_worksheet(config: &GameConfig, spots: &[(SpotType, Hand)]) -> String {
+    let mut worksheet = String::from("# Preflop Worksheet\n\n## Questions\n\n");
+    for (i, (spot_type, hand)) in spots.iter().enumerate() {
+        worksheet.push_str(&format!(
+            "{}. {}: {} ({}) — Answer: ________\n",
+            i + 1,
+            spot_type,
+            hand,
+            HandNotation::from_hand(*hand)
+        ));
+    }
+    worksheet.push_str("\n## Answer Key\n\n");
+    for (i, (spot_type, hand)) in spots.iter().enumerate() {
+        worksheet.push_str(&format!(
+            "{}. {}\n",
+            i + 1,
+            recommended_play(config, *spot_type, *hand)
+        ));
+    }
+    worksheet
+}
+
+// A spot list as text, one spot per line: the spot's config string, the two cards and
+// the RNG value, e.g. "BBDefense_CO As Kd 37".
+pub fn spot_list_to_string(spots: &[(SpotType, Hand, u8)]) -> String {
+    spots
+        .iter()
+        .map(|(spot_type, hand, rng)| {
+            format!("{} {} {}\n", spot_type.to_config_string(), hand, rng)
+        })
+        .collect()
+}
+
+// Parses `spot_list_to_string` output. Blank lines and lines starting with '#' are
+// skipped.
+pub fn parse_spot_list(contents: &str) -> Result<Vec<(SpotType, Hand, u8)>, CoreError> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(i, line)| {
+            parse_spot_line(line).map_err(|e| {
+                CoreError::Config(ConfigError::Invalid(format!(
+                    "Spot list line {}: {}",
+                    i + 1,
+                    e
+                )))
+            })
+        })
+        .collect()
+}
+
+fn parse_spot_line(line: &str) -> Result<(SpotType, Hand, u8), String> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let [spot_str, card1, card2, rng_str] = fields.as_slice() else {
+        return Err(format!(
+            "expected \"<spot> <card> <card> <rng>\", got \"{}\"",
+            line
+        ));
+    };
+    let hand = Hand {
+        card1: card1.parse()?,
+        card2: card2.parse()?,
+    };
+    if hand.card1 == hand.card2 {
+        return Err(format!("Hand uses {} twice", hand.card1));
+    }
+    let rng = rng_str
+        .parse::<u8>()
+        .ok()
+        .filter(|rng| *rng < 100)
+        .ok_or_else(|| format!("Invalid RNG value: {}", rng_str))?;
+    Ok((SpotType::from_str(spot_str)?, hand, rng))
+}
+
+pub fn save_spot_list(
+    path: &std::path::Path,
+    spots: &[(SpotType, Hand, u8)],
+) -> Result<(), std::io::Error> {
+    fs::write(path, spot_list_to_string(spots))
+}
+
+pub fn load_spot_list(path: &std::path::Path) -> Result<Vec<(SpotType, Hand, u8)>, CoreError> {
+    parse_spot_list(&fs::read_to_string(path)?)
+}
+
+// --- Stress Testing ---
+
+#[derive(Debug, Clone, Default)]
+pub struct StressReport {
+    pub iterations: usize,
+    pub spots_generated: usize,
+    // Allowed spot types that never came up
+    pub unreachable_spot_types: Vec<SpotType>,
+    // Human-readable descriptions of scoring that contradicts the configured frequencies
+    pub inconsistencies: Vec<String>,
+}
+
+impl StressReport {
+    pub fn is_ok(&self) -> bool {
+        self.spots_generated == self.iterations
+            && self.unreachable_spot_types.is_empty()
+            && self.inconsistencies.is_empty()
+    }
+}
+
+// Plays `iterations` spots from a seeded game, answering each with its most frequent
+// action, and checks that every allowed spot type comes up and that scoring agrees
+// with `get_action_frequencies`: one action is correct for the RNG value, and the most
+// frequent action is never graded `Wrong`.
+pub fn stress_test(config: &GameConfig, seed: u64, iterations: usize) -> StressReport {
+    let mut report = StressReport {
+        iterations,
+        ..Default::default()
+    };
+    if config.allowed_spot_types.is_empty() {
+        report
+            .inconsistencies
+            .push("No allowed spot types configured".to_string());
+        return report;
+    }
+
+    let mut game = Game::new_with_seed(config.clone(), seed);
+    let mut seen_spot_types: Vec<SpotType> = Vec::new();
+    for _ in 0..iterations {
+        let Ok((spot_type, hand, rng_value)) = game.generate_random_spot() else {
+            continue;
+        };
+        report.spots_generated += 1;
+        if !seen_spot_types.contains(&spot_type) {
+            seen_spot_types.push(spot_type);
+        }
+
+        let (raise_freq, call_freq, fold_freq) = get_action_frequencies(config, spot_type, hand);
+        let best_action = if raise_freq >= call_freq && raise_freq >= fold_freq {
+            UserAction::Raise
+        } else if call_freq >= fold_freq {
+            spot_type.passive_action()
+        } else {
+            UserAction::Fold
+        };
+
+        if check_answer(config, spot_type, hand, best_action, rng_value) == AnswerResult::Wrong {
+            report.inconsistencies.push(format!(
+                "{} with {}: most frequent action {:?} graded Wrong (RNG {})",
+                spot_type, hand, best_action, rng_value
+            ));
+        }
+
+        let has_correct_action = [
+            UserAction::Raise,
+            spot_type.passive_action(),
+            UserAction::Fold,
+        ]
+        .into_iter()
+        .any(|action| {
+            check_answer(config, spot_type, hand, action, rng_value) == AnswerResult::Correct
+        });
+        if !has_correct_action {
+            report.inconsistencies.push(format!(
+                "{} with {}: no action graded Correct (RNG {})",
+                spot_type, hand, rng_value
+            ));
+        }
+    }
+
+    for &spot_type in &config.allowed_spot_types {
+        if !seen_spot_types.contains(&spot_type)
+            && !report.unreachable_spot_types.contains(&spot_type)
+        {
+            report.unreachable_spot_types.push(spot_type);
+        }
+    }
+    report
+}
+
+// --- Session and Lifetime Stats ---
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct StatTotals {
+    pub answered: u32,
+    // Correct answers count 1, frequency mistakes 0.5
+    pub score: f32,
+    #[serde(default)]
+    pub frequency_mistakes: u32,
+    // `answered` and `score` with every older answer weighted down by MASTERY_DECAY, so
+    // `mastery` follows recent play. Stats saved before these existed start at 0
+    #[serde(default)]
+    pub recent_answered: f32,
+    #[serde(default)]
+    pub recent_score: f32,
+}
+
+impl StatTotals {
+    pub fn record(&mut self, result: AnswerResult) {
+        self.answered += 1;
+        self.score += answer_credit(result, false);
+        if result == AnswerResult::FrequencyMistake {
+            self.frequency_mistakes += 1;
+        }
+        self.recent_answered = self.recent_answered * MASTERY_DECAY + 1.0;
+        self.recent_score = self.recent_score * MASTERY_DECAY + answer_credit(result, false);
+    }
+
+    // Adds `other`, taking its answers as the newer ones, as when a session is merged
+    // into the lifetime stats.
+    pub fn merge(&mut self, other: &StatTotals) {
+        self.answered += other.answered;
+        self.score += other.score;
+        self.frequency_mistakes += other.frequency_mistakes;
+        let decay = MASTERY_DECAY.powi(other.answered.min(i32::MAX as u32) as i32);
+        self.recent_answered = self.recent_answered * decay + other.recent_answered;
+        self.recent_score = self.recent_score * decay + other.recent_score;
+    }
+
+    // Accuracy in percent, 0 when nothing was answered.
+    pub fn accuracy(&self) -> f32 {
+        self.accuracy_with(false)
+    }
+
+    // Accuracy in percent with frequency mistakes counted as misses.
+    pub fn strict_accuracy(&self) -> f32 {
+        self.accuracy_with(true)
+    }
+
+    pub fn accuracy_with(&self, strict: bool) -> f32 {
+        if self.answered == 0 {
+            return 0.0;
+        }
+        let score = if strict {
+            self.score - 0.5 * self.frequency_mistakes as f32
+        } else {
+            self.score
+        };
+        score / self.answered as f32 * 100.0
+    }
+
+    // Bayesian estimate of recent accuracy in percent: the decayed score shrunk towards
+    // a 50% prior worth MASTERY_PRIOR_HANDS answers, so a few lucky hands don't read as
+    // mastered, and a spot played well long ago but badly lately doesn't either.
+    pub fn mastery(&self) -> f32 {
+        let prior_score = MASTERY_PRIOR_HANDS * 0.5;
+        (self.recent_score + prior_score) / (self.recent_answered + MASTERY_PRIOR_HANDS) * 100.0
+    }
+
+    // Mastered once the mastery estimate reaches MASTERY_THRESHOLD over at least
+    // MASTERY_MIN_HANDS answers.
+    pub fn is_mastered(&self) -> bool {
+        self.answered >= MASTERY_MIN_HANDS && self.mastery() >= MASTERY_THRESHOLD
+    }
+}
+
+pub const MASTERY_PRIOR_HANDS: f32 = 10.0;
+pub const MASTERY_THRESHOLD: f32 = 80.0;
+pub const MASTERY_MIN_HANDS: u32 = 20;
+// Weight of an answer relative to the one after it in `mastery`: the last ~35 answers
+// carry half the weight
+pub const MASTERY_DECAY: f32 = 0.98;
+
+// A slice of the stats: everything, one hero position, one hand type or one spot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatBucket {
+    Overall,
+    Position(Position),
+    HandType(HandType),
+    Spot(SpotType),
+}
+
+// Totals overall, by hero position ("UTG" .. "BB"), by hand type ("pair", "suited",
+// "offsuit") and by spot ("Open_UTG"). Keys are strings so the stats serialize to a
+// plain TOML file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CumulativeStats {
+    pub overall: StatTotals,
+    #[serde(default)]
+    pub by_position: BTreeMap<String, StatTotals>,
+    #[serde(default)]
+    pub by_hand_type: BTreeMap<String, StatTotals>,
+    #[serde(default)]
+    pub by_spot: BTreeMap<String, StatTotals>,
+}
+
+impl CumulativeStats {
+    pub fn record(&mut self, spot_type: SpotType, hand: Hand, result: AnswerResult) {
+        let hero_position = spot_type.hero_position();
+        let hand_type = HandNotation::from_hand(hand).hand_type;
+        self.overall.record(result);
+        self.by_position
+            .entry(position_config_str(hero_position).to_string())
+            .or_default()
+            .record(result);
+        self.by_hand_type
+            .entry(hand_type_key(hand_type).to_string())
+            .or_default()
+            .record(result);
+        self.by_spot
+            .entry(spot_type.to_config_string())
+            .or_default()
+            .record(result);
+    }
+
+    // Totals for a bucket; empty when nothing in it was answered yet.
+    pub fn totals(&self, bucket: StatBucket) -> StatTotals {
+        match bucket {
+            StatBucket::Overall => Some(&self.overall),
+            StatBucket::Position(position) => self.by_position.get(position_config_str(position)),
+            StatBucket::HandType(hand_type) => self.by_hand_type.get(hand_type_key(hand_type)),
+            StatBucket::Spot(spot_type) => self.by_spot.get(&spot_type.to_config_string()),
+        }
+        .copied()
+        .unwrap_or_default()
+    }
+
+    pub fn merge(&mut self, other: &CumulativeStats) {
+        self.overall.merge(&other.overall);
+        for (map, other_map) in [
+            (&mut self.by_position, &other.by_position),
+            (&mut self.by_hand_type, &other.by_hand_type),
+            (&mut self.by_spot, &other.by_spot),
+        ] {
+            for (key, totals) in other_map {
+                map.entry(key.clone()).or_default().merge(totals);
+            }
+        }
+    }
+
+    // Default location of the all-time stats file, next to the config directory's
+    // ranges.toml.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("preflop-trainer").join("lifetime_stats.toml"))
+    }
+
+    // A missing file loads as empty stats. An unreadable or corrupt one is an error,
+    // so it isn't overwritten with only this session's answers on the next save.
+    pub fn load(path: &std::path::Path) -> Result<Self, std::io::Error> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e),
+        };
+        toml::from_str(&contents)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn save(&self, path: &std::path::Path) -> Result<(), std::io::Error> {
+        let contents = toml::to_string(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents)
+    }
+
+    // e.g. "84% over 12,340 hands"
+    pub fn summary(&self) -> String {
+        self.summary_with(false)
+    }
+
+    pub fn summary_with(&self, strict: bool) -> String {
+        format!(
+            "{:.0}% over {} hands",
+            self.overall.accuracy_with(strict),
+            format_thousands(self.overall.answered)
+        )
+    }
+}
+
+// One answer of a session, as exported by `SessionStats::to_csv`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AnsweredSpot {
+    pub spot_type: SpotType,
+    pub hand_notation: HandNotation,
+    pub user_action: UserAction,
+    pub correct_action: UserAction,
+    pub result: AnswerResult,
+    pub rng_value: u8,
 }
 
-use std::path::PathBuf;
+// Stats for a single session: the totals that get merged into the lifetime stats, plus a
+// confusion matrix of the chosen action against the action the RNG dictated, totals
+// per spot and per hand for the end-of-session breakdown and every answer in order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionStats {
+    pub totals: CumulativeStats,
+    // Keyed by (chosen, correct)
+    pub confusion: HashMap<(UserAction, UserAction), u32>,
+    pub by_spot_type: HashMap<SpotType, StatTotals>,
+    pub by_hand: HashMap<HandNotation, StatTotals>,
+    pub answers: Vec<AnsweredSpot>,
+}
 
-pub fn find_or_create_config() -> Result<PathBuf, std::io::Error> {
-    // 1. Check current working directory
-    let cwd_candidate = PathBuf::from("ranges.toml");
-    if cwd_candidate.exists() {
-        return Ok(cwd_candidate);
+impl SessionStats {
+    pub fn record(
+        &mut self,
+        spot_type: SpotType,
+        hand: Hand,
+        chosen: UserAction,
+        correct: UserAction,
+        result: AnswerResult,
+        rng_value: u8,
+    ) {
+        let hand_notation = HandNotation::from_hand(hand);
+        self.totals.record(spot_type, hand, result);
+        *self.confusion.entry((chosen, correct)).or_insert(0) += 1;
+        self.by_spot_type
+            .entry(spot_type)
+            .or_default()
+            .record(result);
+        self.by_hand
+            .entry(hand_notation)
+            .or_default()
+            .record(result);
+        self.answers.push(AnsweredSpot {
+            spot_type,
+            hand_notation,
+            user_action: chosen,
+            correct_action: correct,
+            result,
+            rng_value,
+        });
     }
 
-    // 2. Check executable directory
-    if let Ok(exe_path) = std::env::current_exe()
-        && let Some(exe_dir) = exe_path.parent()
-    {
-        let exe_candidate = exe_dir.join("ranges.toml");
-        if exe_candidate.exists() {
-            return Ok(exe_candidate); // Return immediately if found in exe dir
+    // Every answer as CSV with a header row, for a spreadsheet:
+    //
+    //   spot,positions,hand,action,correct_action,result,rng
+    //   BB vs CO Open,CO BB,KTs,Fold,Call,Wrong,41
+    //
+    // Positions are the spot's seats as in the config; fields with commas or quotes are
+    // quoted.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("spot,positions,hand,action,correct_action,result,rng\n");
+        for answer in &self.answers {
+            let positions: Vec<&str> = answer
+                .spot_type
+                .positions()
+                .into_iter()
+                .map(position_config_str)
+                .collect();
+            let fields = [
+                answer.spot_type.to_string(),
+                positions.join(" "),
+                answer.hand_notation.to_string(),
+                format!("{:?}", answer.user_action),
+                format!("{:?}", answer.correct_action),
+                format!("{:?}", answer.result),
+                answer.rng_value.to_string(),
+            ];
+            let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
         }
+        csv
     }
 
-    // 3. Check platform-specific config directory
-    if let Some(config_dir) = dirs::config_dir() {
-        let app_config_dir = config_dir.join("preflop-trainer");
-        if !app_config_dir.exists() {
-            fs::create_dir_all(&app_config_dir)?;
-        }
-        let config_path = app_config_dir.join("ranges.toml");
-        if config_path.exists() {
-            return Ok(config_path);
-        } else {
-            // 4. Create config from embedded example
-            let example_content = include_str!("../../ranges.toml.example");
-            fs::write(&config_path, example_content)?;
-            return Ok(config_path);
+    // The `breakdown_report` as CSV: one row per spot answered, then the most missed
+    // hands, worst first:
+    //
+    //   kind,name,answered,score,accuracy
+    //   spot,BB vs CO Open,2,1.5,75
+    //   hand,KTs,1,0,0
+    pub fn breakdown_to_csv(&self) -> String {
+        let mut csv = String::from("kind,name,answered,score,accuracy\n");
+        let mut rows: Vec<(&str, String, StatTotals)> = self
+            .accuracy_by_spot_type()
+            .into_iter()
+            .map(|(spot_type, _)| ("spot", spot_type.to_string(), self.by_spot_type[&spot_type]))
+            .collect();
+        rows.extend(
+            self.most_missed_hands(MOST_MISSED_HANDS_SHOWN)
+                .into_iter()
+                .map(|(hand_notation, totals)| ("hand", hand_notation.to_string(), totals)),
+        );
+        for (kind, name, totals) in rows {
+            csv.push_str(&format!(
+                "{},{},{},{},{:.0}\n",
+                kind,
+                csv_field(&name),
+                totals.answered,
+                totals.score,
+                totals.accuracy()
+            ));
         }
+        csv
     }
 
-    // 5. Fallback to a temporary file if all else fails
-    let tmp = std::env::temp_dir().join(format!(
-        "preflop_trainer_ranges_{}.toml",
-        std::process::id()
-    ));
-    let example_content = include_str!("../../ranges.toml.example");
-    fs::write(&tmp, example_content)?;
-    Ok(tmp)
-}
+    // Accuracy in percent per hero position answered, in seat order.
+    pub fn accuracy_by_position(&self) -> Vec<(Position, f32)> {
+        let mut accuracies: Vec<(Position, f32)> = self
+            .totals
+            .by_position
+            .iter()
+            .filter_map(|(key, totals)| Some((key.parse().ok()?, totals.accuracy())))
+            .collect();
+        accuracies.sort_by_key(|&(position, _)| position);
+        accuracies
+    }
 
-pub fn load_config() -> Result<GameConfig, Box<dyn std::error::Error>> {
-    let config_path = find_or_create_config()?;
-    let contents = fs::read_to_string(config_path)?;
-    let toml_config: TomlConfig = toml::from_str(&contents)?;
+    // Accuracy in percent per spot answered, ordered by the spots' config strings so
+    // the breakdown reads the same every time: "BBDefense_BTN" before "Open_UTG".
+    pub fn accuracy_by_spot_type(&self) -> Vec<(SpotType, f32)> {
+        let mut accuracies: Vec<(SpotType, f32)> = self
+            .by_spot_type
+            .iter()
+            .map(|(&spot_type, totals)| (spot_type, totals.accuracy()))
+            .collect();
+        accuracies.sort_by_cached_key(|(spot_type, _)| spot_type.to_config_string());
+        accuracies
+    }
 
-    let mut unopened_raise_ranges = HashMap::new();
-    for (pos_str, detail) in toml_config.unopened_raise {
-        let position = Position::from_str(&pos_str)?;
-        let range_map = parse_range_str(&detail.range)?;
-        unopened_raise_ranges.insert(position, range_map);
+    // Up to `n` hands that lost the most credit, worst first. A frequency mistake loses
+    // half a point and a wrong answer a full one; ties go to the lower accuracy, then
+    // to grid order. Hands that were always answered correctly aren't listed.
+    pub fn most_missed_hands(&self, n: usize) -> Vec<(HandNotation, StatTotals)> {
+        let missed = |totals: &StatTotals| totals.answered as f32 - totals.score;
+        let mut hands: Vec<(HandNotation, StatTotals)> = self
+            .by_hand
+            .iter()
+            .filter(|(_, totals)| missed(totals) > 0.0)
+            .map(|(&hand_notation, &totals)| (hand_notation, totals))
+            .collect();
+        hands.sort_by(|(a, a_totals), (b, b_totals)| {
+            missed(b_totals)
+                .total_cmp(&missed(a_totals))
+                .then(a_totals.accuracy().total_cmp(&b_totals.accuracy()))
+                .then(a.grid_position().cmp(&b.grid_position()))
+        });
+        hands.truncate(n);
+        hands
     }
 
-    let mut bb_defense_call_ranges = HashMap::new();
-    let mut bb_defense_raise_ranges = HashMap::new();
-    if let Some(bb_defense_toml) = toml_config.bb_defense {
-        for (pos_str, detail) in bb_defense_toml {
-            let position = Position::from_str(&pos_str)?;
-            let call_range_map = parse_range_str(&detail.call_range)?;
-            let raise_range_map = parse_range_str(&detail.raise_range)?;
-            bb_defense_call_ranges.insert(position, call_range_map);
-            bb_defense_raise_ranges.insert(position, raise_range_map);
+    // The end-of-session breakdown as text, one line per spot answered and then the
+    // hands missed most. Spots the session has mastered say so:
+    //
+    //   Open from UTG: 92% (40 hands, mastered)
+    //   BB vs Button Open: 61% (9 hands)
+    //   Most missed: AJo, KTs, 76s
+    pub fn breakdown_report(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .accuracy_by_spot_type()
+            .into_iter()
+            .map(|(spot_type, accuracy)| {
+                let answered = self.by_spot_type[&spot_type].answered;
+                let hands = if answered == 1 { "hand" } else { "hands" };
+                let mastered = if self.is_mastered(StatBucket::Spot(spot_type)) {
+                    ", mastered"
+                } else {
+                    ""
+                };
+                format!(
+                    "{}: {:.0}% ({} {}{})",
+                    spot_type, accuracy, answered, hands, mastered
+                )
+            })
+            .collect();
+        let missed: Vec<String> = self
+            .most_missed_hands(MOST_MISSED_HANDS_SHOWN)
+            .iter()
+            .map(|(hand_notation, _)| hand_notation.to_string())
+            .collect();
+        if !missed.is_empty() {
+            lines.push(format!("Most missed: {}", missed.join(", ")));
         }
+        lines
     }
 
-    Ok(GameConfig {
-        unopened_raise_ranges,
-        bb_defense_call_ranges,
-        bb_defense_raise_ranges,
-        allowed_spot_types: if let Some(generic_config) = toml_config.generic {
-            if let Some(toml_spot_types) = generic_config.allowed_spot_types {
-                toml_spot_types
-                    .into_iter()
-                    .map(|s| SpotType::from_str(&s))
-                    .collect::<Result<Vec<SpotType>, String>>()?
-            } else {
-                vec![
-                    SpotType::Open {
-                        position: Position::UTG,
-                    },
-                    SpotType::Open {
-                        position: Position::MP,
-                    },
-                    SpotType::Open {
-                        position: Position::CO,
-                    },
-                    SpotType::Open {
-                        position: Position::BTN,
-                    },
-                    SpotType::Open {
-                        position: Position::SB,
-                    },
-                    SpotType::BBDefense {
-                        opener_position: Position::UTG,
-                    },
-                    SpotType::BBDefense {
-                        opener_position: Position::MP,
-                    },
-                    SpotType::BBDefense {
-                        opener_position: Position::CO,
-                    },
-                    SpotType::BBDefense {
-                        opener_position: Position::BTN,
-                    },
-                    SpotType::BBDefense {
-                        opener_position: Position::SB,
-                    },
-                ]
+    pub fn confusion_count(&self, chosen: UserAction, correct: UserAction) -> u32 {
+        self.confusion.get(&(chosen, correct)).copied().unwrap_or(0)
+    }
+
+    // The confusion matrix as a text table, one row per chosen action:
+    //
+    //   chosen \ correct  Raise   Call   Fold
+    //   Raise                 12      1      3
+    //
+    // A Limp row and column are added once a limp spot has been answered.
+    pub fn confusion_report(&self) -> Vec<String> {
+        let mut actions = vec![UserAction::Raise, UserAction::Call, UserAction::Fold];
+        if self
+            .confusion
+            .keys()
+            .any(|&(chosen, correct)| chosen == UserAction::Limp || correct == UserAction::Limp)
+        {
+            actions.insert(2, UserAction::Limp);
+        }
+        let mut header = format!("{:<17}", "chosen \\ correct");
+        for action in &actions {
+            header.push_str(&format!("{:>7}", format!("{:?}", action)));
+        }
+        let mut lines = vec![header];
+        for &chosen in &actions {
+            let mut line = format!("{:<17}", format!("{:?}", chosen));
+            for &correct in &actions {
+                line.push_str(&format!("{:>7}", self.confusion_count(chosen, correct)));
             }
-        } else {
-            vec![
-                SpotType::Open {
-                    position: Position::UTG,
-                },
-                SpotType::Open {
-                    position: Position::MP,
-                },
-                SpotType::Open {
-                    position: Position::CO,
-                },
-                SpotType::Open {
-                    position: Position::BTN,
-                },
-                SpotType::Open {
-                    position: Position::SB,
-                },
-                SpotType::BBDefense {
-                    opener_position: Position::UTG,
-                },
-                SpotType::BBDefense {
-                    opener_position: Position::MP,
-                },
-                SpotType::BBDefense {
-                    opener_position: Position::CO,
-                },
-                SpotType::BBDefense {
-                    opener_position: Position::BTN,
-                },
-                SpotType::BBDefense {
-                    opener_position: Position::SB,
-                },
-            ]
-        },
-    })
-}
+            lines.push(line);
+        }
+        lines
+    }
 
-pub fn parse_range_str(range_str: &str) -> Result<HashMap<HandNotation, f32>, String> {
-    let mut range_map = HashMap::new();
-    if range_str.is_empty() {
-        return Ok(range_map);
+    // See `StatTotals::mastery`.
+    pub fn mastery(&self, bucket: StatBucket) -> f32 {
+        self.totals.totals(bucket).mastery()
     }
-    for hand_part in range_str.split(',') {
-        let parts: Vec<&str> = hand_part.trim().split(':').collect();
-        let hand_notation_str_raw = parts[0];
 
-        let frequency = if parts.len() == 2 {
-            parts[1].parse::<f32>().map_err(|e| e.to_string())?
-        } else {
-            1.0
+    pub fn is_mastered(&self, bucket: StatBucket) -> bool {
+        self.totals.totals(bucket).is_mastered()
+    }
+
+    // Where the CLI keeps the last session for `--resume`, next to the lifetime stats.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("preflop-trainer").join("last_session.json"))
+    }
+
+    pub fn save_json(&self, path: &Path) -> Result<(), std::io::Error> {
+        let mut confusion: Vec<(UserAction, UserAction, u32)> = self
+            .confusion
+            .iter()
+            .map(|(&(chosen, correct), &count)| (chosen, correct, count))
+            .collect();
+        confusion.sort_by_key(|&(chosen, correct, _)| (chosen as u8, correct as u8));
+        let mut by_spot_type: Vec<(SpotType, StatTotals)> =
+            self.by_spot_type.iter().map(|(&k, &v)| (k, v)).collect();
+        by_spot_type.sort_by_cached_key(|(spot_type, _)| spot_type.to_config_string());
+        let mut by_hand: Vec<(HandNotation, StatTotals)> =
+            self.by_hand.iter().map(|(&k, &v)| (k, v)).collect();
+        by_hand.sort_by_key(|(hand_notation, _)| hand_notation.grid_position());
+        let file = SessionStatsFile {
+            totals: self.totals.clone(),
+            confusion,
+            by_spot_type,
+            by_hand,
+            answers: self.answers.clone(),
         };
+        let contents = serde_json::to_string_pretty(&file)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents)
+    }
 
-        if hand_notation_str_raw.ends_with('+') {
-            let base_hand_str = &hand_notation_str_raw[0..hand_notation_str_raw.len() - 1];
-            let base_hand_notation = HandNotation::from_str(base_hand_str)?;
-
-            if base_hand_notation.hand_type == HandType::Pair {
-                let base_rank = base_hand_notation.rank1;
-                for &rank in Rank::VALUES.iter().rev() {
-                    // Iterate from Ace down to Two
-                    if rank >= base_rank {
-                        let notation = HandNotation {
-                            rank1: rank,
-                            rank2: rank,
-                            hand_type: HandType::Pair,
-                        };
-                        range_map.insert(notation, frequency);
-                    } else {
-                        break;
-                    }
-                }
-            } else {
-                // Handle suited and offsuit '+' notation
-                let base_rank1 = base_hand_notation.rank1;
-                let base_rank2 = base_hand_notation.rank2;
-                let hand_type = base_hand_notation.hand_type;
-
-                // For XYs+ or XYo+, fix the higher rank (rank1) and iterate the lower rank (rank2) upwards
-                // Example: A2s+ means A2s, A3s, ..., AKs (all suited Aces with lower card >= 2)
-                for &rank2_iter in Rank::VALUES.iter() {
-                    if rank2_iter >= base_rank2 && rank2_iter < base_rank1 {
-                        // Lower rank must be less than higher rank
-                        let notation = HandNotation {
-                            rank1: base_rank1,
-                            rank2: rank2_iter,
-                            hand_type,
-                        };
-                        range_map.insert(notation, frequency);
-                    } else if rank2_iter >= base_rank1 {
-                        break; // Stop if lower rank becomes higher than or equal to base_rank1
-                    }
-                }
-            }
-        } else {
-            let hand_notation = HandNotation::from_str(hand_notation_str_raw)?;
-            range_map.insert(hand_notation, frequency);
+    // Unlike `CumulativeStats::load` a missing file is an error too, since the user
+    // asked to resume that session.
+    pub fn load_json(path: &Path) -> Result<Self, std::io::Error> {
+        let contents = fs::read_to_string(path)?;
+        let file: SessionStatsFile = serde_json::from_str(&contents)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        Ok(SessionStats {
+            totals: file.totals,
+            confusion: file
+                .confusion
+                .into_iter()
+                .map(|(chosen, correct, count)| ((chosen, correct), count))
+                .collect(),
+            by_spot_type: file.by_spot_type.into_iter().collect(),
+            by_hand: file.by_hand.into_iter().collect(),
+            answers: file.answers,
+        })
+    }
+
+    // Drops the per-spot stats and answers of spots `config` doesn't serve, e.g. from a
+    // session saved under another config, and returns them in config-string order. The
+    // per-position totals of positions no remaining spot has as hero go too. The overall,
+    // per-hand-type and per-hand totals keep those answers.
+    pub fn retain_config_spots(&mut self, config: &GameConfig) -> Vec<SpotType> {
+        let mut dropped: Vec<SpotType> = self
+            .by_spot_type
+            .keys()
+            .filter(|spot_type| !config.allowed_spot_types.contains(spot_type))
+            .copied()
+            .collect();
+        dropped.sort_by_cached_key(|spot_type| spot_type.to_config_string());
+        for spot_type in &dropped {
+            self.by_spot_type.remove(spot_type);
+            self.totals.by_spot.remove(&spot_type.to_config_string());
         }
+        self.answers
+            .retain(|answer| !dropped.contains(&answer.spot_type));
+        let hero_positions: std::collections::HashSet<&str> = config
+            .allowed_spot_types
+            .iter()
+            .map(|spot_type| position_config_str(spot_type.hero_position()))
+            .collect();
+        self.totals
+            .by_position
+            .retain(|position, _| hero_positions.contains(position.as_str()));
+        dropped
     }
-    Ok(range_map)
 }
 
-// Helper function to calculate weighted hand notations
-fn calculate_weighted_hand_notations(
-    target_range: &HashMap<HandNotation, f32>,
-    all_notations: &[HandNotation],
-) -> Vec<(HandNotation, u32)> {
-    let mut weighted_notations = Vec::new();
+// The JSON layout of `SessionStats`. JSON object keys have to be strings, so the maps
+// keyed by tuples or hands are written as lists of entries, in a stable order.
+#[derive(Serialize, Deserialize)]
+struct SessionStatsFile {
+    totals: CumulativeStats,
+    confusion: Vec<(UserAction, UserAction, u32)>,
+    by_spot_type: Vec<(SpotType, StatTotals)>,
+    by_hand: Vec<(HandNotation, StatTotals)>,
+    #[serde(default)]
+    answers: Vec<AnsweredSpot>,
+}
 
-    for &hand_notation in all_notations {
-        let mut weight = 20; // Default weight for hands not in any range
+// How many hands the breakdowns list under "Most missed"
+pub const MOST_MISSED_HANDS_SHOWN: usize = 5;
 
-        if let Some(&frequency) = target_range.get(&hand_notation) {
-            if frequency < 1.0 && frequency > 0.0 {
-                weight = 5000; // High weight for mixed strategy hands
-            } else if frequency == 1.0 {
-                weight = 50; // Reduced weight for solid in-range hands
-            }
-        }
-        weighted_notations.push((hand_notation, weight));
+// A CSV field, quoted with its quotes doubled when it holds a comma, quote or newline.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
     }
-    weighted_notations
 }
 
-// --- Deck Structure ---
-#[derive(Debug, Clone)]
-pub struct Deck {
-    pub cards: Vec<Card>,
+fn hand_type_key(hand_type: HandType) -> &'static str {
+    match hand_type {
+        HandType::Pair => "pair",
+        HandType::Suited => "suited",
+        HandType::Offsuit => "offsuit",
+    }
 }
 
-impl Deck {
-    pub fn new() -> Self {
-        let mut cards = Vec::with_capacity(52);
-        for &suit in &Suit::VALUES {
-            for &rank in &Rank::VALUES {
-                cards.push(Card { rank, suit });
-            }
+fn format_thousands(n: u32) -> String {
+    let digits = n.to_string();
+    let mut formatted = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            formatted.push(',');
         }
-        Deck { cards }
+        formatted.push(c);
     }
+    formatted
+}
 
-    pub fn shuffle(&mut self) {
-        let mut rng = ThreadRng::default();
-        self.cards.shuffle(&mut rng);
+// Result of a timed speed round: how many spots were answered, with what score, in how
+// much time.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SpeedRoundReport {
+    pub answered: u32,
+    pub score: f32,
+    pub elapsed: Duration,
+}
+
+impl SpeedRoundReport {
+    pub fn hands_per_minute(&self) -> f32 {
+        let secs = self.elapsed.as_secs_f32();
+        if secs <= 0.0 {
+            return 0.0;
+        }
+        self.answered as f32 * 60.0 / secs
     }
 
-    pub fn deal_hand(&mut self) -> Option<Hand> {
-        if self.cards.len() < 2 {
-            return None;
+    // Accuracy in percent, 0 when nothing was answered.
+    pub fn accuracy(&self) -> f32 {
+        if self.answered == 0 {
+            return 0.0;
         }
-        let card1 = self.cards.pop()?;
-        let card2 = self.cards.pop()?;
-        Some(Hand { card1, card2 })
+        self.score / self.answered as f32 * 100.0
     }
-}
 
-impl Default for Deck {
-    fn default() -> Self {
-        Self::new()
+    // Correct answers per minute, the round's headline number.
+    pub fn score_per_minute(&self) -> f32 {
+        self.hands_per_minute() * self.accuracy() / 100.0
+    }
+
+    // e.g. "42 hands in 60s: 42.0 hands/min, 88% accuracy"
+    pub fn summary(&self) -> String {
+        format!(
+            "{} hands in {}s: {:.1} hands/min, {:.0}% accuracy",
+            self.answered,
+            self.elapsed.as_secs(),
+            self.hands_per_minute(),
+            self.accuracy()
+        )
     }
 }
 
-// --- Game State ---
+#[derive(Debug, Clone, Copy)]
+struct SpeedRound {
+    duration: Duration,
+    started: Instant,
+    answered: u32,
+    score: f32,
+}
+
+// What `TrainerSession::undo_last` restores: the state the last answer and the deals
+// after it changed. The answer list isn't copied, since undoing just drops its last entry.
 #[derive(Debug, Clone)]
-pub struct Game {
-    deck: Deck,
+struct AnswerUndo {
+    deal_state: DealState,
+    served_len: usize,
+    totals: CumulativeStats,
+    confusion: HashMap<(UserAction, UserAction), u32>,
+    by_spot_type: HashMap<SpotType, StatTotals>,
+    by_hand: HashMap<HandNotation, StatTotals>,
+    new_totals: CumulativeStats,
+    speed_round: Option<SpeedRound>,
+}
+
+// A training session: serves spots from a Game, scores answers and keeps the session's
+// stats. With a lifetime stats file, the all-time totals are loaded at the start and
+// the session is merged into them by `finish`.
+#[derive(Debug, Clone)]
+pub struct TrainerSession {
+    game: Game,
     config: GameConfig,
-    all_possible_hand_notations: Vec<HandNotation>,
+    stats: SessionStats,
+    // Only the answers given since the session started or was resumed, which is what
+    // `finish` merges into the lifetime stats
+    new_totals: CumulativeStats,
+    lifetime_stats: CumulativeStats,
+    lifetime_stats_path: Option<PathBuf>,
+    speed_round: Option<SpeedRound>,
+    served: Vec<(SpotType, Hand, u8)>,
+    auto_focus: bool,
+    undo: Option<Box<AnswerUndo>>,
 }
 
-impl Game {
-    pub fn new(config: GameConfig) -> Self {
-        let mut deck = Deck::new();
-        deck.shuffle();
-        let all_possible_hand_notations = get_all_possible_hand_notations();
-        Game {
-            deck,
+impl TrainerSession {
+    pub fn new(game: Game, config: GameConfig) -> Self {
+        TrainerSession {
+            game,
             config,
-            all_possible_hand_notations,
+            stats: SessionStats::default(),
+            new_totals: CumulativeStats::default(),
+            lifetime_stats: CumulativeStats::default(),
+            lifetime_stats_path: None,
+            speed_round: None,
+            served: Vec::new(),
+            auto_focus: false,
+            undo: None,
         }
     }
 
-    pub fn generate_random_spot(&mut self) -> Option<(SpotType, Hand, u8)> {
-        let mut rng = ThreadRng::default();
-
-        loop {
-            // Reshuffle if deck is empty or too few cards
-            if self.deck.cards.len() < 2 {
-                self.deck = Deck::new();
-                self.deck.shuffle();
-            }
-
-            let spot_type: SpotType;
-            let target_hand_range: HashMap<HandNotation, f32>; // This will be owned
-
-            // If no allowed spot types are configured, panic as no spots can be generated
-            if self.config.allowed_spot_types.is_empty() {
-                panic!(
-                    "No valid spot types configured or able to be generated. Please configure 'allowed_spot_types' in GameConfig."
-                );
-            }
-
-            // Randomly select one of the allowed spot types
-            let chosen_allowed_spot_type = self.config.allowed_spot_types.choose(&mut rng).expect(
-                "Should always be able to choose from a non-empty list of allowed spot types",
-            );
-
-            match chosen_allowed_spot_type {
-                SpotType::Open {
-                    position: chosen_position,
-                } => {
-                    spot_type = SpotType::Open {
-                        position: *chosen_position,
-                    };
-                    target_hand_range = self
-                        .config
-                        .unopened_raise_ranges
-                        .get(chosen_position)
-                        .cloned() // Clone the HashMap to own it
-                        .unwrap_or_else(|| EMPTY_HAND_RANGE.clone()); // Or use EMPTY_HAND_RANGE
-                }
-                SpotType::BBDefense {
-                    opener_position: chosen_opener_position,
-                } => {
-                    spot_type = SpotType::BBDefense {
-                        opener_position: *chosen_opener_position,
-                    };
-
-                    let mut combined_bb_defense_range = HashMap::new();
-                    if let Some(call_map) = self
-                        .config
-                        .bb_defense_call_ranges
-                        .get(chosen_opener_position)
-                    {
-                        combined_bb_defense_range.extend(call_map.iter().map(|(&k, &v)| (k, v)));
-                    }
-                    if let Some(raise_map) = self
-                        .config
-                        .bb_defense_raise_ranges
-                        .get(chosen_opener_position)
-                    {
-                        // Raise frequencies take precedence if hand is in both
-                        combined_bb_defense_range.extend(raise_map.iter().map(|(&k, &v)| (k, v)));
-                    }
-                    target_hand_range = combined_bb_defense_range;
-                }
-            }
+    // Fails if the stats file exists but can't be read or parsed, see
+    // `CumulativeStats::load`.
+    pub fn with_lifetime_stats(
+        game: Game,
+        config: GameConfig,
+        path: PathBuf,
+    ) -> Result<Self, std::io::Error> {
+        let mut session = Self::new(game, config);
+        session.lifetime_stats = CumulativeStats::load(&path)?;
+        session.lifetime_stats_path = Some(path);
+        Ok(session)
+    }
 
-            let weighted_hand_notations = calculate_weighted_hand_notations(
-                &target_hand_range, // Now `target_hand_range` is owned
-                &self.all_possible_hand_notations,
-            );
+    // Continues from stats saved with `SessionStats::save_json`. Their answers were
+    // merged into the lifetime stats when that session finished, so `finish` only adds
+    // the ones given from here on.
+    pub fn resume(&mut self, stats: SessionStats) {
+        self.stats = stats;
+        self.undo = None;
+    }
 
-            // 1. Manual weighted selection of a HandNotation
-            let total_weight: u32 = weighted_hand_notations
-                .iter()
-                .map(|&(_, weight)| weight)
-                .sum();
-            if total_weight == 0 {
-                // If the selected range is empty or has no weighted hands,
-                // reshuffle and try to get a new spot and hand.
-                self.deck = Deck::new();
-                self.deck.shuffle();
-                continue;
-            }
+    // Swaps the config used for dealing and scoring, e.g. with fewer `allowed_spot_types`.
+    // The session's stats and served spots carry on; see `Game::set_config`.
+    pub fn set_config(&mut self, config: GameConfig) {
+        self.game.set_config(config.clone());
+        self.config = config;
+        self.undo = None;
+    }
 
-            let mut rand_weight = rng.random_range(0..total_weight);
-            let chosen_hand_notation = weighted_hand_notations
-                .iter()
-                .find_map(|&(hn, weight)| {
-                    if rand_weight < weight {
-                        Some(hn)
-                    } else {
-                        rand_weight -= weight;
-                        None
-                    }
-                })
-                .expect("Weighted selection failed to find a hand");
+    // Starts the session stats over, e.g. for "Play Again". Answers already given still
+    // go into the lifetime stats at `finish`.
+    pub fn clear_stats(&mut self) {
+        self.stats = SessionStats::default();
+        self.undo = None;
+    }
 
-            // 3. Attempt to deal the concrete hand
-            if let Some(hand) = self.try_deal_specific_hand(&chosen_hand_notation) {
-                // 4. Generate RNG value for mixed strategies
-                let mixed_strategy_rng_value: u8 = rng.random_range(0..100);
-                return Some((spot_type, hand, mixed_strategy_rng_value));
-            }
-            // If try_deal_specific_hand returns None, we reshuffle and try again.
-            self.deck = Deck::new();
-            self.deck.shuffle();
+    // With auto-focus on, `next_spot` only deals the allowed spots that aren't mastered
+    // yet, until every one of them is.
+    pub fn set_auto_focus(&mut self, auto_focus: bool) {
+        self.auto_focus = auto_focus;
+        if !auto_focus {
+            self.game.set_focus(Vec::new());
         }
     }
 
-    // Another helper function: tries to deal a specific hand from the current deck without reshuffling
-    fn try_deal_specific_hand(&mut self, target_notation: &HandNotation) -> Option<Hand> {
-        let mut matching_card_indices = Vec::new();
-
-        // Iterate through all cards in the deck to find pairs that match the target_notation
-        for i in 0..self.deck.cards.len() {
-            for j in (i + 1)..self.deck.cards.len() {
-                let card1 = self.deck.cards[i];
-                let card2 = self.deck.cards[j];
+    // Totals of a bucket over the lifetime stats and this session's answers, the newest
+    // last, so `mastery` weighs this session's play most.
+    pub fn totals(&self, bucket: StatBucket) -> StatTotals {
+        let mut totals = self.lifetime_stats.totals(bucket);
+        totals.merge(&self.new_totals.totals(bucket));
+        totals
+    }
 
-                // Create a temporary Hand and its HandNotation to compare
-                let current_hand_notation = HandNotation::from_hand(Hand { card1, card2 });
+    pub fn is_mastered(&self, bucket: StatBucket) -> bool {
+        self.totals(bucket).is_mastered()
+    }
 
-                if current_hand_notation == *target_notation {
-                    matching_card_indices.push((i, j));
-                }
-            }
-        }
+    // The allowed spots not mastered yet, in config order: what auto-focus deals.
+    pub fn unmastered_spot_types(&self) -> Vec<SpotType> {
+        self.config
+            .allowed_spot_types
+            .iter()
+            .filter(|&&spot_type| !self.is_mastered(StatBucket::Spot(spot_type)))
+            .copied()
+            .collect()
+    }
 
-        if matching_card_indices.is_empty() {
-            return None; // No matching hand found in current deck
+    pub fn next_spot(&mut self) -> Result<(SpotType, Hand, u8), CoreError> {
+        if self.auto_focus {
+            self.game.set_focus(self.unmastered_spot_types());
         }
+        let spot = self.game.generate_random_spot()?;
+        self.served.push(spot);
+        Ok(spot)
+    }
 
-        // Pick a random matching hand from the found ones
-        let mut rng = ThreadRng::default();
-        let (idx1, idx2) = matching_card_indices.choose(&mut rng)?.to_owned();
+    // Every spot served so far, in order, with its RNG value. Save it with
+    // `save_spot_list` and replay it through `Game::new_scripted` to drill the same set.
+    pub fn export_spot_list(&self) -> Vec<(SpotType, Hand, u8)> {
+        self.served.clone()
+    }
 
-        // Get the cards before removing them
-        let card1 = self.deck.cards[idx1];
-        let card2 = self.deck.cards[idx2];
-        let hand_to_deal = Hand { card1, card2 };
+    // Scores the answer and records it in the session stats.
+    pub fn answer(
+        &mut self,
+        spot_type: SpotType,
+        hand: Hand,
+        user_action: UserAction,
+        mixed_strategy_rng_value: u8,
+    ) -> AnswerResult {
+        self.answer_detailed(spot_type, hand, user_action, mixed_strategy_rng_value)
+            .result
+    }
 
-        // Remove the chosen cards from the deck
-        // Remove higher index first to avoid issues with shifting indices
-        self.deck.cards.remove(std::cmp::max(idx1, idx2));
-        self.deck.cards.remove(std::cmp::min(idx1, idx2));
+    // Like `answer`, but with the `check_answer_detailed` breakdown of the score.
+    pub fn answer_detailed(
+        &mut self,
+        spot_type: SpotType,
+        hand: Hand,
+        user_action: UserAction,
+        mixed_strategy_rng_value: u8,
+    ) -> AnswerFeedback {
+        let feedback = check_answer_detailed(
+            &self.config,
+            spot_type,
+            hand,
+            user_action,
+            mixed_strategy_rng_value,
+        );
+        self.undo = Some(Box::new(AnswerUndo {
+            deal_state: self.game.deal_state(),
+            served_len: self.served.len(),
+            totals: self.stats.totals.clone(),
+            confusion: self.stats.confusion.clone(),
+            by_spot_type: self.stats.by_spot_type.clone(),
+            by_hand: self.stats.by_hand.clone(),
+            new_totals: self.new_totals.clone(),
+            speed_round: self.speed_round,
+        }));
+        self.stats.record(
+            spot_type,
+            hand,
+            user_action,
+            feedback.correct_action,
+            feedback.result,
+            mixed_strategy_rng_value,
+        );
+        self.new_totals.record(spot_type, hand, feedback.result);
+        if let Some(round) = &mut self.speed_round
+            && round.started.elapsed() < round.duration
+        {
+            round.answered += 1;
+            round.score += answer_credit(feedback.result, self.config.strict_scoring);
+        }
+        feedback
+    }
 
-        Some(hand_to_deal)
+    pub fn can_undo(&self) -> bool {
+        self.undo.is_some()
     }
-}
 
-pub fn check_answer(
-    config: &GameConfig,
-    spot_type: SpotType,
-    hand: Hand,
-    user_action: UserAction,
-    mixed_strategy_rng_value: u8,
-) -> AnswerResult {
-    let hand_notation = HandNotation::from_hand(hand);
+    // Takes back the last answer and any spots dealt after it: the stats, deck and RNG
+    // are as they were before it, so answering again deals the same next spot. Only the
+    // last answer can be taken back, and not after the config, stats or speed round
+    // change. Returns whether there was an answer to undo.
+    pub fn undo_last(&mut self) -> bool {
+        let Some(undo) = self.undo.take() else {
+            return false;
+        };
+        let undo = *undo;
+        self.game
+            .restore_deal_state(undo.deal_state, &self.served[undo.served_len..]);
+        self.served.truncate(undo.served_len);
+        self.stats.totals = undo.totals;
+        self.stats.confusion = undo.confusion;
+        self.stats.by_spot_type = undo.by_spot_type;
+        self.stats.by_hand = undo.by_hand;
+        self.stats.answers.pop();
+        self.new_totals = undo.new_totals;
+        self.speed_round = undo.speed_round;
+        true
+    }
 
-    match spot_type {
-        SpotType::Open { position } => {
-            // For Open spots, only Raise and Fold are considered valid actions based on range
-            if user_action == UserAction::Call {
-                return AnswerResult::Wrong; // Cannot call an unopened pot
-            }
+    // Starts a timed round. Answers given before `duration` runs out count towards the
+    // round; starting a new round discards the previous one.
+    pub fn speed_round(&mut self, duration: Duration) {
+        self.undo = None;
+        self.speed_round = Some(SpeedRound {
+            duration,
+            started: Instant::now(),
+            answered: 0,
+            score: 0.0,
+        });
+    }
 
-            let position_range = config
-                .unopened_raise_ranges
-                .get(&position)
-                .unwrap_or(&EMPTY_HAND_RANGE);
-            let expected_to_raise_freq = position_range.get(&hand_notation).copied().unwrap_or(0.0);
-
-            if expected_to_raise_freq == 1.0 {
-                // 100% Raise
-                if user_action == UserAction::Raise {
-                    AnswerResult::Correct
-                } else {
-                    AnswerResult::Wrong
-                }
-            } else if expected_to_raise_freq == 0.0 {
-                // 100% Fold
-                if user_action == UserAction::Fold {
-                    AnswerResult::Correct
-                } else {
-                    AnswerResult::Wrong
-                }
-            } else {
-                // Mixed strategy for Raise/Fold
-                let correct_action =
-                    if (expected_to_raise_freq * 100.0) as u8 > mixed_strategy_rng_value {
-                        UserAction::Raise
-                    } else {
-                        UserAction::Fold
-                    };
-                if user_action == correct_action {
-                    AnswerResult::Correct
-                } else {
-                    AnswerResult::FrequencyMistake
-                }
-            }
-        }
-        SpotType::BBDefense { opener_position } => {
-            let call_range = config
-                .bb_defense_call_ranges
-                .get(&opener_position)
-                .unwrap_or(&EMPTY_HAND_RANGE);
-            let raise_range = config
-                .bb_defense_raise_ranges
-                .get(&opener_position)
-                .unwrap_or(&EMPTY_HAND_RANGE);
+    // Time left in the current speed round, None when no round is running.
+    pub fn speed_round_remaining(&self) -> Option<Duration> {
+        self.speed_round
+            .map(|round| round.duration.saturating_sub(round.started.elapsed()))
+    }
 
-            let call_freq = call_range.get(&hand_notation).copied().unwrap_or(0.0);
-            let raise_freq = raise_range.get(&hand_notation).copied().unwrap_or(0.0);
+    pub fn speed_round_over(&self) -> bool {
+        self.speed_round_remaining() == Some(Duration::ZERO)
+    }
 
-            // Determine the correct action based on stacked frequencies
-            let raise_threshold = (raise_freq * 100.0) as u8;
-            let call_threshold = raise_threshold.saturating_add((call_freq * 100.0) as u8);
+    // Ends the current speed round and reports it. The elapsed time is capped at the
+    // round's duration.
+    pub fn end_speed_round(&mut self) -> Option<SpeedRoundReport> {
+        self.undo = None;
+        self.speed_round.take().map(|round| SpeedRoundReport {
+            answered: round.answered,
+            score: round.score,
+            elapsed: round.started.elapsed().min(round.duration),
+        })
+    }
 
-            let correct_action = if mixed_strategy_rng_value < raise_threshold {
-                UserAction::Raise
-            } else if mixed_strategy_rng_value < call_threshold {
-                UserAction::Call
-            } else {
-                UserAction::Fold
-            };
+    pub fn stats(&self) -> &SessionStats {
+        &self.stats
+    }
 
-            if user_action == correct_action {
-                AnswerResult::Correct
-            } else {
-                // The user's action did not match the action dictated by the RNG.
-                // We return `FrequencyMistake` if the user's action is *any* valid part of the
-                // hand's overall strategy (even if it's not correct for this specific RNG).
-                // Otherwise, it's just plain `Wrong`.
-                let is_raise_possible = raise_freq > 0.0;
-                let is_call_possible = call_freq > 0.0;
-                let is_fold_possible = (raise_freq + call_freq) < 1.0;
-
-                let is_user_action_part_of_strategy = (user_action == UserAction::Raise
-                    && is_raise_possible)
-                    || (user_action == UserAction::Call && is_call_possible)
-                    || (user_action == UserAction::Fold && is_fold_possible);
-
-                if is_user_action_part_of_strategy {
-                    AnswerResult::FrequencyMistake
-                } else {
-                    AnswerResult::Wrong
-                }
-            }
-        }
+    // All-time stats as loaded at the start, not including this session.
+    pub fn lifetime_stats(&self) -> &CumulativeStats {
+        &self.lifetime_stats
     }
-}
 
-pub fn get_action_frequencies(
-    config: &GameConfig,
-    spot_type: SpotType,
-    hand: Hand,
-) -> (f32, f32, f32) {
-    // (raise, call, fold)
-    let hand_notation = HandNotation::from_hand(hand);
-    match spot_type {
-        SpotType::Open { position } => {
-            let range = config
-                .unopened_raise_ranges
-                .get(&position)
-                .unwrap_or(&EMPTY_HAND_RANGE);
-            let raise_freq = range.get(&hand_notation).copied().unwrap_or(0.0);
-            (raise_freq, 0.0, 1.0 - raise_freq)
-        }
-        SpotType::BBDefense { opener_position } => {
-            let call_range = config
-                .bb_defense_call_ranges
-                .get(&opener_position)
-                .unwrap_or(&EMPTY_HAND_RANGE);
-            let raise_range = config
-                .bb_defense_raise_ranges
-                .get(&opener_position)
-                .unwrap_or(&EMPTY_HAND_RANGE);
-            let call_freq = call_range.get(&hand_notation).copied().unwrap_or(0.0);
-            let raise_freq = raise_range.get(&hand_notation).copied().unwrap_or(0.0);
-            let total_play_freq = call_freq + raise_freq;
-            (raise_freq, call_freq, 1.0 - total_play_freq.min(1.0))
+    // Merges the session into the lifetime stats, writes them back if a stats file is
+    // in use and returns the new all-time totals.
+    pub fn finish(mut self) -> Result<CumulativeStats, std::io::Error> {
+        self.lifetime_stats.merge(&self.new_totals);
+        if let Some(path) = &self.lifetime_stats_path {
+            self.lifetime_stats.save(path)?;
         }
+        Ok(self.lifetime_stats)
     }
 }
@@ -1,8 +1,11 @@
 use preflop_trainer_core::{
-    AnswerResult, Card, GameConfig, Hand, Position, Rank, SpotType, Suit, UserAction, check_answer,
+    ActionFrequencies, AnswerResult, BoundaryFavors, Card, Game, GameConfig, Hand, HandNotation,
+    OpenSize, Position, Rank, SpotType, StackDepth, Suit, UserAction, action_for_rng,
+    all_action_frequencies, check_answer, check_answer_dual_rng, get_action_frequencies,
     parse_range_str,
 };
 use std::collections::HashMap;
+use std::str::FromStr;
 
 // Helper to create a Card for tests
 fn c(rank_char: char, suit_char: char) -> Card {
@@ -28,55 +31,35 @@ fn create_full_test_game_config(
     let mut game_config_unopened_raise = HashMap::new();
     if let Some(ur_map) = unopened_raise_ranges {
         for (pos, range_str) in ur_map {
-            game_config_unopened_raise.insert(pos, parse_range_str(&range_str).unwrap());
+            game_config_unopened_raise.insert(pos, range_str.parse().unwrap());
         }
     }
 
     let mut game_config_bb_call = HashMap::new();
     if let Some(bb_call_map) = bb_defense_call_ranges {
         for (pos, range_str) in bb_call_map {
-            game_config_bb_call.insert(pos, parse_range_str(&range_str).unwrap());
+            game_config_bb_call.insert((pos, None), range_str.parse().unwrap());
         }
     }
 
     let mut game_config_bb_raise = HashMap::new();
     if let Some(bb_raise_map) = bb_defense_raise_ranges {
         for (pos, range_str) in bb_raise_map {
-            game_config_bb_raise.insert(pos, parse_range_str(&range_str).unwrap());
+            game_config_bb_raise.insert((pos, None), range_str.parse().unwrap());
         }
     }
 
     let default_allowed_spot_types = vec![
-        SpotType::Open {
-            position: Position::UTG,
-        },
-        SpotType::Open {
-            position: Position::MP,
-        },
-        SpotType::Open {
-            position: Position::CO,
-        },
-        SpotType::Open {
-            position: Position::BTN,
-        },
-        SpotType::Open {
-            position: Position::SB,
-        },
-        SpotType::BBDefense {
-            opener_position: Position::UTG,
-        },
-        SpotType::BBDefense {
-            opener_position: Position::MP,
-        },
-        SpotType::BBDefense {
-            opener_position: Position::CO,
-        },
-        SpotType::BBDefense {
-            opener_position: Position::BTN,
-        },
-        SpotType::BBDefense {
-            opener_position: Position::SB,
-        },
+        SpotType::open(Position::UTG),
+        SpotType::open(Position::MP),
+        SpotType::open(Position::CO),
+        SpotType::open(Position::BTN),
+        SpotType::open(Position::SB),
+        SpotType::bb_defense(Position::UTG),
+        SpotType::bb_defense(Position::MP),
+        SpotType::bb_defense(Position::CO),
+        SpotType::bb_defense(Position::BTN),
+        SpotType::bb_defense(Position::SB),
     ];
 
     GameConfig {
@@ -84,6 +67,7 @@ fn create_full_test_game_config(
         bb_defense_call_ranges: game_config_bb_call,
         bb_defense_raise_ranges: game_config_bb_raise,
         allowed_spot_types: allowed_spot_types.unwrap_or(default_allowed_spot_types),
+        ..Default::default()
     }
 }
 
@@ -102,9 +86,7 @@ fn test_bb_vs_btn_j8s_raise_correct_with_low_rng() {
         card1: c('J', 's'),
         card2: c('8', 's'),
     }; // J8s
-    let spot_type = SpotType::BBDefense {
-        opener_position: Position::BTN,
-    };
+    let spot_type = SpotType::bb_defense(Position::BTN);
     let user_action = UserAction::Raise;
     let rng_value = 49; // < 50, should be a raise
 
@@ -128,9 +110,7 @@ fn test_bb_vs_btn_j8s_call_correct_with_high_rng() {
         card1: c('J', 's'),
         card2: c('8', 's'),
     }; // J8s
-    let spot_type = SpotType::BBDefense {
-        opener_position: Position::BTN,
-    };
+    let spot_type = SpotType::bb_defense(Position::BTN);
     let user_action = UserAction::Call;
     let rng_value = 50; // >= 50 and < 100, should be a call
 
@@ -154,9 +134,7 @@ fn test_bb_vs_btn_j8s_raise_freq_mistake_with_high_rng() {
         card1: c('J', 's'),
         card2: c('8', 's'),
     }; // J8s
-    let spot_type = SpotType::BBDefense {
-        opener_position: Position::BTN,
-    };
+    let spot_type = SpotType::bb_defense(Position::BTN);
     let user_action = UserAction::Raise;
     let rng_value = 50; // >= 50, should be a call
 
@@ -180,9 +158,7 @@ fn test_bb_vs_btn_j8s_call_freq_mistake_with_low_rng() {
         card1: c('J', 's'),
         card2: c('8', 's'),
     }; // J8s
-    let spot_type = SpotType::BBDefense {
-        opener_position: Position::BTN,
-    };
+    let spot_type = SpotType::bb_defense(Position::BTN);
     let user_action = UserAction::Call;
     let rng_value = 49; // < 50, should be a raise
 
@@ -206,9 +182,7 @@ fn test_bb_vs_btn_j8s_fold_is_wrong_with_any_rng() {
         card1: c('J', 's'),
         card2: c('8', 's'),
     }; // J8s
-    let spot_type = SpotType::BBDefense {
-        opener_position: Position::BTN,
-    };
+    let spot_type = SpotType::bb_defense(Position::BTN);
     let user_action = UserAction::Fold;
 
     // Test with low RNG
@@ -229,3 +203,513 @@ fn test_bb_vs_btn_j8s_fold_is_wrong_with_any_rng() {
         "Should be Wrong to fold with high RNG"
     );
 }
+
+// --- Tests for float sums that should amount to exactly 1.0 ---
+
+#[test]
+fn test_bb_fold_is_wrong_when_raise_and_call_sum_to_one() {
+    // 0.7 + 0.3 is 1.0000001 in f32, 0.1 + 0.9 is 0.99999994; neither leaves room to fold.
+    for (raise_freq, call_freq) in [("0.5", "0.5"), ("0.7", "0.3"), ("0.1", "0.9")] {
+        let mut call_map = HashMap::new();
+        call_map.insert(Position::CO, format!("A5s:{}", call_freq));
+        let mut raise_map = HashMap::new();
+        raise_map.insert(Position::CO, format!("A5s:{}", raise_freq));
+        let config = create_full_test_game_config(None, Some(call_map), Some(raise_map), None);
+
+        let hand = Hand {
+            card1: c('A', 'h'),
+            card2: c('5', 'h'),
+        }; // A5s
+        let spot_type = SpotType::bb_defense(Position::CO);
+
+        for rng_value in [0, 49, 50, 99] {
+            let result = check_answer(&config, spot_type, hand, UserAction::Fold, rng_value);
+            assert_eq!(
+                result,
+                AnswerResult::Wrong,
+                "Folding A5s (raise {}, call {}) with RNG {} should be Wrong",
+                raise_freq,
+                call_freq,
+                rng_value
+            );
+        }
+    }
+}
+
+// --- Tests for the dual RNG model ---
+// Strategy for A9s vs CO: raise 30%, call 40%, fold 30%
+
+fn three_way_config() -> GameConfig {
+    let mut call_map = HashMap::new();
+    call_map.insert(Position::CO, "A9s:0.4".to_string());
+    let mut raise_map = HashMap::new();
+    raise_map.insert(Position::CO, "A9s:0.3".to_string());
+    create_full_test_game_config(None, Some(call_map), Some(raise_map), None)
+}
+
+fn correct_action_single(
+    config: &GameConfig,
+    hand: Hand,
+    spot_type: SpotType,
+    rng: u8,
+) -> UserAction {
+    [UserAction::Raise, UserAction::Call, UserAction::Fold]
+        .into_iter()
+        .find(|&action| check_answer(config, spot_type, hand, action, rng) == AnswerResult::Correct)
+        .unwrap()
+}
+
+fn correct_action_dual(
+    config: &GameConfig,
+    hand: Hand,
+    spot_type: SpotType,
+    raise_rng: u8,
+    rest_rng: u8,
+) -> UserAction {
+    [UserAction::Raise, UserAction::Call, UserAction::Fold]
+        .into_iter()
+        .find(|&action| {
+            check_answer_dual_rng(config, spot_type, hand, action, raise_rng, rest_rng)
+                == AnswerResult::Correct
+        })
+        .unwrap()
+}
+
+#[test]
+fn test_dual_rng_differs_from_stacked_single_rng() {
+    let config = three_way_config();
+    let hand = Hand {
+        card1: c('A', 'c'),
+        card2: c('9', 'c'),
+    }; // A9s
+    let spot_type = SpotType::bb_defense(Position::CO);
+
+    // Single RNG: 0-29 raise, 30-69 call, 70-99 fold
+    assert_eq!(
+        correct_action_single(&config, hand, spot_type, 10),
+        UserAction::Raise
+    );
+    assert_eq!(
+        correct_action_single(&config, hand, spot_type, 50),
+        UserAction::Call
+    );
+    assert_eq!(
+        correct_action_single(&config, hand, spot_type, 80),
+        UserAction::Fold
+    );
+
+    // Dual RNG: a low first value raises no matter the second one
+    assert_eq!(
+        correct_action_dual(&config, hand, spot_type, 10, 99),
+        UserAction::Raise
+    );
+    // Otherwise the second value splits call (4/7 of the rest) from fold
+    assert_eq!(
+        correct_action_dual(&config, hand, spot_type, 50, 20),
+        UserAction::Call
+    );
+    assert_eq!(
+        correct_action_dual(&config, hand, spot_type, 50, 80),
+        UserAction::Fold
+    );
+    // The same first value that calls in the single model can fold in the dual model
+    assert_eq!(
+        correct_action_dual(&config, hand, spot_type, 50, 60),
+        UserAction::Fold
+    );
+
+    // Off-strategy actions are still frequency mistakes, not wrong
+    assert_eq!(
+        check_answer_dual_rng(&config, spot_type, hand, UserAction::Call, 10, 99),
+        AnswerResult::FrequencyMistake
+    );
+}
+
+#[test]
+fn test_dual_rng_keeps_configured_frequencies() {
+    let config = three_way_config();
+    let hand = Hand {
+        card1: c('A', 'c'),
+        card2: c('9', 'c'),
+    }; // A9s
+    let spot_type = SpotType::bb_defense(Position::CO);
+
+    let (mut raises, mut calls, mut folds) = (0, 0, 0);
+    for raise_rng in 0..100 {
+        for rest_rng in 0..100 {
+            match correct_action_dual(&config, hand, spot_type, raise_rng, rest_rng) {
+                UserAction::Raise => raises += 1,
+                UserAction::Call => calls += 1,
+                UserAction::Limp => unreachable!("no limping facing a raise"),
+                UserAction::Fold => folds += 1,
+            }
+        }
+    }
+
+    // Out of 10000 combinations: 30% raise, ~40% call, ~30% fold
+    assert_eq!(raises, 3000);
+    assert!((3900..=4100).contains(&calls));
+    assert!((2900..=3100).contains(&folds));
+}
+
+#[test]
+fn test_generate_random_spot_dual_rng_values_in_range() {
+    let config = three_way_config();
+    let mut game = Game::new_with_seed(config, 7);
+    for _ in 0..100 {
+        let (_, _, raise_rng, rest_rng) = game.generate_random_spot_dual_rng().unwrap();
+        assert!(raise_rng < 100);
+        assert!(rest_rng < 100);
+    }
+}
+
+// --- Tests for size-dependent BB defense ranges ---
+
+// BB defense against an open of `tenths` tenths of a big blind
+fn sized_bb_defense(opener_position: Position, tenths: u16) -> SpotType {
+    SpotType::BBDefense {
+        opener_position,
+        open_size: Some(OpenSize(tenths)),
+        stack_depth: None,
+    }
+}
+
+fn sized_config() -> GameConfig {
+    let mut config = create_full_test_game_config(None, None, None, None);
+    let btn_2bb = (Position::BTN, Some(OpenSize(20)));
+    let btn_3bb = (Position::BTN, Some(OpenSize(30)));
+    config
+        .bb_defense_call_ranges
+        .insert(btn_2bb, "K9o".parse().unwrap());
+    config
+        .bb_defense_raise_ranges
+        .insert(btn_2bb, "AA".parse().unwrap());
+    config
+        .bb_defense_call_ranges
+        .insert(btn_3bb, "K9o:0.25".parse().unwrap());
+    config
+        .bb_defense_raise_ranges
+        .insert(btn_3bb, "AA".parse().unwrap());
+    config.allowed_spot_types = vec![
+        sized_bb_defense(Position::BTN, 20),
+        sized_bb_defense(Position::BTN, 30),
+    ];
+    config
+}
+
+#[test]
+fn test_open_sizes_produce_different_defense_frequencies() {
+    let config = sized_config();
+    let hand = Hand {
+        card1: c('K', 'c'),
+        card2: c('9', 'd'),
+    }; // K9o
+    let vs_2bb = sized_bb_defense(Position::BTN, 20);
+    let vs_3bb = sized_bb_defense(Position::BTN, 30);
+
+    assert_eq!(
+        get_action_frequencies(&config, vs_2bb, hand),
+        (0.0, 1.0, 0.0)
+    );
+    assert_eq!(
+        get_action_frequencies(&config, vs_3bb, hand),
+        (0.0, 0.25, 0.75)
+    );
+
+    // RNG 50: always a call vs 2bb, a fold vs 3bb
+    assert_eq!(
+        check_answer(&config, vs_2bb, hand, UserAction::Call, 50),
+        AnswerResult::Correct
+    );
+    assert_eq!(
+        check_answer(&config, vs_3bb, hand, UserAction::Fold, 50),
+        AnswerResult::Correct
+    );
+    assert_eq!(
+        check_answer(&config, vs_3bb, hand, UserAction::Call, 50),
+        AnswerResult::FrequencyMistake
+    );
+}
+
+#[test]
+fn test_unconfigured_open_size_falls_back_to_unsized_ranges() {
+    let mut config = sized_config();
+    config
+        .bb_defense_call_ranges
+        .insert((Position::CO, None), "K9o".parse().unwrap());
+    let hand = Hand {
+        card1: c('K', 'c'),
+        card2: c('9', 'd'),
+    }; // K9o
+    let spot_type = sized_bb_defense(Position::CO, 25);
+    assert_eq!(
+        get_action_frequencies(&config, spot_type, hand),
+        (0.0, 1.0, 0.0)
+    );
+}
+
+#[test]
+fn test_generated_sized_spots_carry_open_size() {
+    let mut game = Game::new_with_seed(sized_config(), 3);
+    for _ in 0..50 {
+        let (spot_type, _, _) = game.generate_random_spot().unwrap();
+        match spot_type {
+            SpotType::BBDefense {
+                opener_position: Position::BTN,
+                open_size: Some(open_size),
+                stack_depth: None,
+            } => assert!(open_size == OpenSize(20) || open_size == OpenSize(30)),
+            other => panic!("Unexpected spot type: {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_sized_spot_type_parsing_and_display() {
+    let spot_type = SpotType::from_str("BBDefense_BTN_2.5").unwrap();
+    assert_eq!(spot_type, sized_bb_defense(Position::BTN, 25));
+    assert_eq!(spot_type.to_string(), "BB vs Button 2.5bb Open");
+    let deep = spot_type.with_stack_depth(Some(StackDepth(40)));
+    assert_eq!(deep.to_config_string(), "BBDefense_BTN_2.5_40");
+    assert_eq!(SpotType::from_str("BBDefense_BTN_2.5_40"), Ok(deep));
+    assert_eq!(OpenSize::from_str("3bb").unwrap(), OpenSize(30));
+    assert_eq!(OpenSize(30).to_string(), "3bb");
+    assert!(OpenSize::from_str("0").is_err());
+    assert!(OpenSize::from_str("2.55").is_err());
+    assert!(SpotType::from_str("BBDefense_BTN_big").is_err());
+    assert!(SpotType::from_str("Open_BTN_2.5").is_err());
+}
+
+// --- Tests for the opener's hand in BB defense spots ---
+
+#[test]
+fn test_opener_hand_is_always_in_open_range() {
+    let mut ur_map = HashMap::new();
+    ur_map.insert(Position::CO, "AA,KK,AKs:0.5".to_string());
+    let mut call_map = HashMap::new();
+    call_map.insert(Position::CO, "AQs,KQs,QQ".to_string());
+    let config = create_full_test_game_config(
+        Some(ur_map),
+        Some(call_map),
+        None,
+        Some(vec![
+            SpotType::bb_defense(Position::CO),
+            SpotType::open(Position::CO),
+        ]),
+    );
+    let open_range = parse_range_str("AA,KK,AKs").unwrap();
+
+    let mut game = Game::new_with_seed(config, 11);
+    for _ in 0..500 {
+        let (spot_type, hand, _) = game.generate_random_spot().unwrap();
+        match spot_type {
+            SpotType::BBDefense { .. } => {
+                let opener_hand = game.last_opener_hand().unwrap();
+                assert!(open_range.contains(HandNotation::from_hand(opener_hand)));
+                assert!(!opener_hand.shares_card_with(&hand));
+            }
+            _ => assert_eq!(game.last_opener_hand(), None),
+        }
+    }
+}
+
+#[test]
+fn test_all_action_frequencies_covers_grid() {
+    let mut ur_map = HashMap::new();
+    ur_map.insert(Position::CO, "AA,KQs:0.5".to_string());
+    let mut call_map = HashMap::new();
+    call_map.insert(Position::CO, "A9s:0.4,KQo".to_string());
+    let mut raise_map = HashMap::new();
+    raise_map.insert(Position::CO, "A9s:0.3,AA".to_string());
+    let config = create_full_test_game_config(Some(ur_map), Some(call_map), Some(raise_map), None);
+
+    let bb_vs_co = SpotType::bb_defense(Position::CO);
+    let grid = all_action_frequencies(&config, bb_vs_co);
+    assert_eq!(grid.len(), 169);
+    let lookup = |s: &str| {
+        let target = HandNotation::from_str(s).unwrap();
+        grid.iter().find(|(hn, _)| *hn == target).unwrap().1
+    };
+    let a9s = lookup("A9s");
+    assert_eq!((a9s.raise, a9s.call), (0.3, 0.4));
+    assert!((a9s.fold - 0.3).abs() < 1e-6);
+    assert_eq!(
+        lookup("KQo"),
+        ActionFrequencies {
+            raise: 0.0,
+            call: 1.0,
+            fold: 0.0
+        }
+    );
+    assert_eq!(lookup("72o").fold, 1.0);
+
+    // Matches the per-hand lookup for every notation
+    for (hn, freqs) in &grid {
+        let hand = hn.combos()[0];
+        assert_eq!(
+            get_action_frequencies(&config, bb_vs_co, hand),
+            (freqs.raise, freqs.call, freqs.fold)
+        );
+    }
+
+    let co_open = all_action_frequencies(&config, SpotType::open(Position::CO));
+    let kqs = co_open
+        .iter()
+        .find(|(hn, _)| *hn == HandNotation::from_str("KQs").unwrap())
+        .unwrap()
+        .1;
+    assert_eq!((kqs.raise, kqs.call, kqs.fold), (0.5, 0.0, 0.5));
+}
+
+// --- Tests for boundary_favors ---
+
+#[test]
+fn test_boundary_rng_values_under_each_setting() {
+    let mut config = three_way_config();
+    let hand = Hand {
+        card1: c('A', 'c'),
+        card2: c('9', 'c'),
+    }; // A9s: raise 30%, call 40%, fold 30%
+    let spot_type = SpotType::bb_defense(Position::CO);
+
+    assert_eq!(config.boundary_favors, BoundaryFavors::Passive);
+    assert_eq!(
+        correct_action_single(&config, hand, spot_type, 30),
+        UserAction::Call
+    );
+    assert_eq!(
+        correct_action_single(&config, hand, spot_type, 70),
+        UserAction::Fold
+    );
+
+    config.boundary_favors = BoundaryFavors::Aggressive;
+    assert_eq!(
+        correct_action_single(&config, hand, spot_type, 30),
+        UserAction::Raise
+    );
+    assert_eq!(
+        correct_action_single(&config, hand, spot_type, 70),
+        UserAction::Call
+    );
+    // Off the boundary nothing changes
+    assert_eq!(
+        correct_action_single(&config, hand, spot_type, 31),
+        UserAction::Call
+    );
+    assert_eq!(
+        correct_action_single(&config, hand, spot_type, 71),
+        UserAction::Fold
+    );
+}
+
+#[test]
+fn test_boundary_rng_value_for_open_spot() {
+    let mut open_map = HashMap::new();
+    open_map.insert(Position::BTN, "KQo:0.5".to_string());
+    let mut config = create_full_test_game_config(Some(open_map), None, None, None);
+    let hand = Hand {
+        card1: c('K', 'h'),
+        card2: c('Q', 'd'),
+    };
+    let spot_type = SpotType::open(Position::BTN);
+
+    assert_eq!(
+        check_answer(&config, spot_type, hand, UserAction::Fold, 50),
+        AnswerResult::Correct
+    );
+    config.boundary_favors = BoundaryFavors::Aggressive;
+    assert_eq!(
+        check_answer(&config, spot_type, hand, UserAction::Raise, 50),
+        AnswerResult::Correct
+    );
+}
+
+#[test]
+fn test_thresholds_round_to_the_nearest_percent() {
+    // Truncating would make 0.337 a 33-value band and 0.0075 an empty one
+    for (raise_freq, last_raise_rng) in [(0.337, 33), (0.0075, 0), (0.335, 33)] {
+        assert_eq!(
+            action_for_rng(raise_freq, 0.0, last_raise_rng, BoundaryFavors::Passive),
+            UserAction::Raise,
+            "{}",
+            raise_freq
+        );
+        assert_eq!(
+            action_for_rng(raise_freq, 0.0, last_raise_rng + 1, BoundaryFavors::Passive),
+            UserAction::Fold,
+            "{}",
+            raise_freq
+        );
+    }
+    // The call band ends at the rounded total, not the sum of two rounded bands
+    assert_eq!(
+        action_for_rng(0.335, 0.335, 67, BoundaryFavors::Passive),
+        UserAction::Fold
+    );
+}
+
+#[test]
+fn test_rounded_boundary_for_open_and_bb_defense() {
+    let mut open_map = HashMap::new();
+    open_map.insert(Position::BTN, "KQo:0.337".to_string());
+    let mut call_map = HashMap::new();
+    call_map.insert(Position::CO, "A9s:0.4".to_string());
+    let mut raise_map = HashMap::new();
+    raise_map.insert(Position::CO, "A9s:0.337".to_string());
+    let mut config =
+        create_full_test_game_config(Some(open_map), Some(call_map), Some(raise_map), None);
+    let open = SpotType::open(Position::BTN);
+    let bb_defense = SpotType::bb_defense(Position::CO);
+    let king_queen = Hand {
+        card1: c('K', 'h'),
+        card2: c('Q', 'd'),
+    };
+    let ace_nine = Hand {
+        card1: c('A', 'c'),
+        card2: c('9', 'c'),
+    };
+    let open_action = |config: &GameConfig, rng| {
+        [UserAction::Raise, UserAction::Fold]
+            .into_iter()
+            .find(|&action| {
+                check_answer(config, open, king_queen, action, rng) == AnswerResult::Correct
+            })
+            .unwrap()
+    };
+
+    // Thresholds 34 (raise) and 74 (raise + call); rng == threshold is the next band
+    assert_eq!(open_action(&config, 33), UserAction::Raise);
+    assert_eq!(open_action(&config, 34), UserAction::Fold);
+    assert_eq!(
+        correct_action_single(&config, ace_nine, bb_defense, 33),
+        UserAction::Raise
+    );
+    assert_eq!(
+        correct_action_single(&config, ace_nine, bb_defense, 34),
+        UserAction::Call
+    );
+    assert_eq!(
+        correct_action_single(&config, ace_nine, bb_defense, 74),
+        UserAction::Fold
+    );
+
+    config.boundary_favors = BoundaryFavors::Aggressive;
+    assert_eq!(open_action(&config, 34), UserAction::Raise);
+    assert_eq!(
+        correct_action_single(&config, ace_nine, bb_defense, 34),
+        UserAction::Raise
+    );
+    assert_eq!(
+        correct_action_single(&config, ace_nine, bb_defense, 74),
+        UserAction::Call
+    );
+}
+
+#[test]
+fn test_boundary_never_picks_an_empty_band() {
+    for favors in [BoundaryFavors::Passive, BoundaryFavors::Aggressive] {
+        assert_eq!(action_for_rng(0.0, 0.5, 0, favors), UserAction::Call);
+        assert_ne!(action_for_rng(0.5, 0.0, 50, favors), UserAction::Call);
+        assert_eq!(action_for_rng(0.0, 0.0, 0, favors), UserAction::Fold);
+    }
+}
@@ -0,0 +1,134 @@
+use preflop_trainer_core::{
+    BoundaryFavors, GameConfig, HandNotation, OpenSize, Position, StackDepth, Suit, TableSize,
+    parse_config_str,
+};
+use std::str::FromStr;
+
+// Every table the exporter has to rebuild, with keys of each shape
+const CONFIG: &str = r##"
+[unopened_raise.CO]
+range = "22+,A2s+,KTs+,ATo+,K9o:0.4"
+
+[unopened_raise.SB]
+range = "AA,KK,K9o:0.4"
+
+[unopened_raise.BTN_40]
+range = "TT+,AJs+:0.5"
+
+[push_fold.BTN_10]
+range = "22+,A2s+,A2o+"
+
+[bb_defense.CO]
+call_range = "22-JJ,A2s+:25%"
+raise_range = "QQ+"
+
+[bb_defense."BTN_2.5"]
+call_range = "77-JJ"
+raise_range = ""
+
+[defense.BTN_CO]
+call_range = "99,88,AJs:0.5"
+raise_range = "TT+,AKs,AJs:0.3"
+
+[vs_3bet.CO_BTN]
+call_range = "TT-QQ"
+raise_range = "KK+,A5s:0.5"
+
+[squeeze.CO_BTN]
+call_range = "99"
+raise_range = "QQ+,AKs"
+
+[sb_limp]
+range = "K9o:0.3,T8s"
+
+[generic]
+allowed_spot_types = ["Open_CO", "BBDefense_CO", "Squeeze_CO_BTN"]
+coverage_first = true
+strict_scoring = true
+combo_weighting = false
+quiz_folds = false
+boundary_favors = "aggressive"
+table_size = 9
+
+[theme]
+card_colors = "two_color"
+suit_colors = { hearts = "#ff8800" }
+
+[notes]
+A5s = "Blocks AA and AK"
+"##;
+
+fn assert_same_config(original: &GameConfig, reparsed: &GameConfig) {
+    assert_eq!(
+        reparsed.unopened_raise_ranges,
+        original.unopened_raise_ranges
+    );
+    assert_eq!(
+        reparsed.unopened_raise_depth_ranges,
+        original.unopened_raise_depth_ranges
+    );
+    assert_eq!(reparsed.push_fold_ranges, original.push_fold_ranges);
+    assert_eq!(
+        reparsed.bb_defense_call_ranges,
+        original.bb_defense_call_ranges
+    );
+    assert_eq!(
+        reparsed.bb_defense_raise_ranges,
+        original.bb_defense_raise_ranges
+    );
+    assert_eq!(reparsed.defense_call_ranges, original.defense_call_ranges);
+    assert_eq!(reparsed.defense_raise_ranges, original.defense_raise_ranges);
+    assert_eq!(reparsed.vs_3bet_call_ranges, original.vs_3bet_call_ranges);
+    assert_eq!(reparsed.vs_3bet_raise_ranges, original.vs_3bet_raise_ranges);
+    assert_eq!(reparsed.squeeze_call_ranges, original.squeeze_call_ranges);
+    assert_eq!(reparsed.squeeze_raise_ranges, original.squeeze_raise_ranges);
+    assert_eq!(reparsed.sb_limp_ranges, original.sb_limp_ranges);
+    assert_eq!(reparsed.allowed_spot_types, original.allowed_spot_types);
+    assert_eq!(reparsed.coverage_first, original.coverage_first);
+    assert_eq!(reparsed.strict_scoring, original.strict_scoring);
+    assert_eq!(reparsed.combo_weighting, original.combo_weighting);
+    assert_eq!(reparsed.quiz_folds, original.quiz_folds);
+    assert_eq!(reparsed.boundary_favors, original.boundary_favors);
+    assert_eq!(reparsed.table_size, original.table_size);
+    assert_eq!(reparsed.theme, original.theme);
+    assert_eq!(reparsed.notes, original.notes);
+}
+
+#[test]
+fn test_exported_config_parses_back_to_the_same_config() {
+    let config = parse_config_str(CONFIG).unwrap();
+    // Make sure the fixture reaches every table
+    assert!(
+        config
+            .unopened_raise_depth_ranges
+            .contains_key(&(Position::BTN, StackDepth(40)))
+    );
+    assert!(
+        config
+            .bb_defense_call_ranges
+            .contains_key(&(Position::BTN, Some(OpenSize(25))))
+    );
+    assert_eq!(config.push_fold_ranges.len(), 1);
+    assert_eq!(config.boundary_favors, BoundaryFavors::Aggressive);
+    assert_eq!(config.table_size, TableSize::NineMax);
+    assert!(config.theme.suit_colors.contains_key(&Suit::Hearts));
+
+    let exported = config.to_toml_string().unwrap();
+    let reparsed = parse_config_str(&exported).unwrap();
+    assert_same_config(&config, &reparsed);
+    assert_eq!(
+        reparsed.note_for(HandNotation::from_str("A5s").unwrap()),
+        Some("Blocks AA and AK")
+    );
+    // Exporting is stable, so the file doesn't churn when saved twice
+    assert_eq!(reparsed.to_toml_string().unwrap(), exported);
+}
+
+#[test]
+fn test_exported_example_config_round_trips() {
+    let config = parse_config_str(include_str!("../../../ranges.toml.example")).unwrap();
+    let exported = config.to_toml_string().unwrap();
+    assert_same_config(&config, &parse_config_str(&exported).unwrap());
+    // Full-frequency hands are left implicit, like a hand-written config
+    assert!(exported.contains("[unopened_raise.UTG]\nrange = \"TT+,AJs+,AQo+\"\n"));
+}
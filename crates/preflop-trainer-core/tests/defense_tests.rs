@@ -0,0 +1,99 @@
+use preflop_trainer_core::{
+    AnswerResult, CoreError, Game, Hand, Position, SpotType, UserAction, check_answer,
+    get_action_frequencies, parse_config_str,
+};
+use std::str::FromStr;
+
+#[test]
+fn test_defense_hero_acts_after_the_opener() {
+    assert!(
+        SpotType::from_str("Defense_BTN_CO")
+            .unwrap()
+            .is_facing_raise()
+    );
+    assert!(SpotType::from_str("Defense_CO_BTN").is_err());
+    assert!(SpotType::from_str("Defense_CO_CO").is_err());
+    assert!(SpotType::from_str("Defense_SB_BB").is_err());
+    assert!(
+        parse_config_str(
+            "[unopened_raise.CO]\nrange = \"AA\"\n\n[defense.CO_BTN]\ncall_range = \"\"\nraise_range = \"AA\"\n"
+        )
+        .is_err()
+    );
+
+    // Hero in the big blind is the existing BB defense spot
+    assert_eq!(
+        SpotType::from_str("Defense_BB_CO").unwrap(),
+        SpotType::bb_defense(Position::CO)
+    );
+    assert_eq!(
+        SpotType::defense(Position::BB, Position::UTG),
+        SpotType::bb_defense(Position::UTG)
+    );
+}
+
+#[test]
+fn test_bb_keys_in_defense_table_fill_bb_defense_ranges() {
+    let config = parse_config_str(
+        "[unopened_raise.CO]\nrange = \"AA\"\n\n[defense.BB_BTN]\ncall_range = \"KQs\"\nraise_range = \"AA\"\n",
+    )
+    .unwrap();
+    assert_eq!(
+        config.bb_defense_call_ranges[&(Position::BTN, None)].len(),
+        1
+    );
+    assert!(config.defense_call_ranges.is_empty());
+
+    // But not on top of a [bb_defense] table for the same opener
+    assert!(
+        parse_config_str(
+            "[unopened_raise.CO]\nrange = \"AA\"\n\n[bb_defense.CO]\ncall_range = \"KQs\"\nraise_range = \"AA\"\n\n[defense.BB_CO]\ncall_range = \"KQs\"\nraise_range = \"AA\"\n"
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_defense_uses_stacked_frequencies() {
+    let config = parse_config_str(
+        "[unopened_raise.CO]\nrange = \"22+,A2s+\"\n\n[defense.BTN_CO]\ncall_range = \"99,88,AJs:0.5\"\nraise_range = \"TT+,AKs,AJs:0.3\"\n",
+    )
+    .unwrap();
+    let btn_vs_co = SpotType::defense(Position::BTN, Position::CO);
+    let hand = "AcJc".parse::<Hand>().unwrap();
+    let (raise, call, fold) = get_action_frequencies(&config, btn_vs_co, hand);
+    assert_eq!((raise, call), (0.3, 0.5));
+    assert!((fold - 0.2).abs() < 1e-6);
+
+    // 0-29 3-bet, 30-79 call, 80-99 fold
+    for (rng, correct) in [
+        (0, UserAction::Raise),
+        (29, UserAction::Raise),
+        (30, UserAction::Call),
+        (79, UserAction::Call),
+        (80, UserAction::Fold),
+        (99, UserAction::Fold),
+    ] {
+        assert_eq!(
+            check_answer(&config, btn_vs_co, hand, correct, rng),
+            AnswerResult::Correct,
+            "rng {}",
+            rng
+        );
+    }
+}
+
+#[test]
+fn test_generate_random_spot_rejects_hero_before_opener() {
+    let mut config = parse_config_str("[unopened_raise.CO]\nrange = \"AA\"\n").unwrap();
+    config.allowed_spot_types = vec![SpotType::Defense {
+        hero_position: Position::MP,
+        opener_position: Position::CO,
+        stack_depth: None,
+    }];
+    let mut game = Game::new_with_seed(config, 4);
+    assert!(matches!(
+        game.generate_random_spot(),
+        Err(CoreError::SpotGeneration(_))
+    ));
+}
@@ -0,0 +1,243 @@
+use preflop_trainer_core::{
+    ConfigError, CoreError, Game, GameConfig, Position, RangeParseError, load_config_from_reader,
+    load_config_from_str, parse_config_str, parse_range_str,
+};
+use std::error::Error;
+
+#[test]
+fn test_bad_range_is_range_parse_error() {
+    let err = parse_range_str("QQ+,AKx").unwrap_err();
+    assert_eq!(
+        err,
+        RangeParseError::InvalidHand {
+            token: "AKx".to_string(),
+            reason: "Invalid hand type char: x".to_string()
+        }
+    );
+    assert_eq!(err.to_string(), "Invalid hand type char: x (in \"AKx\")");
+
+    let err = parse_range_str("AKs:lots").unwrap_err();
+    assert!(matches!(&err, RangeParseError::InvalidFrequency { token, .. } if token == "AKs:lots"));
+    assert!(err.source().is_some());
+
+    assert_eq!(
+        parse_range_str("AA,,KK").unwrap_err(),
+        RangeParseError::EmptyToken {
+            field: String::new()
+        }
+    );
+    assert!(matches!(
+        parse_range_str("QQ+&").unwrap_err(),
+        RangeParseError::EmptyToken { .. }
+    ));
+    assert!(matches!(
+        parse_range_str("pairs,-22:0.5").unwrap_err(),
+        RangeParseError::RemovalWithFrequency { .. }
+    ));
+}
+
+#[test]
+fn test_conflicting_frequencies_are_duplicate_hand_errors() {
+    let err = parse_range_str("22+,KK:0.5").unwrap_err();
+    assert!(matches!(
+        err,
+        RangeParseError::DuplicateHand {
+            first: 1.0,
+            second: 0.5,
+            ..
+        }
+    ));
+    assert_eq!(
+        err.to_string(),
+        "KK is listed with two frequencies: 1 and 0.5"
+    );
+    // Repeating a hand at the same frequency is fine
+    assert_eq!(parse_range_str("22+,KK").unwrap().len(), 13);
+}
+
+#[test]
+fn test_bad_config_is_config_error() {
+    // Not TOML at all
+    let err = parse_config_str("this is = = not toml").unwrap_err();
+    assert!(matches!(err, ConfigError::TomlParse(_)));
+    assert!(err.source().is_some());
+
+    // Unknown position
+    let err = parse_config_str("[unopened_raise.XX]\nrange = \"AA\"\n").unwrap_err();
+    assert!(matches!(&err, ConfigError::UnknownPosition(position) if position == "XX"));
+    assert_eq!(err.to_string(), "Invalid position: XX");
+
+    // Unknown spot type
+    let err = parse_config_str(
+        "[unopened_raise.BTN]\nrange = \"AA\"\n[generic]\nallowed_spot_types = [\"Limp_BTN\"]\n",
+    )
+    .unwrap_err();
+    assert!(matches!(&err, ConfigError::UnknownSpotType(msg) if msg.contains("Open_BTN")));
+
+    // Callers working in CoreError still get the config error, as the source
+    let err = CoreError::from(err);
+    assert!(matches!(
+        &err,
+        CoreError::Config(ConfigError::UnknownSpotType(_))
+    ));
+    assert!(err.source().unwrap().is::<ConfigError>());
+}
+
+#[test]
+fn test_bad_range_in_config_is_invalid_range() {
+    let err = parse_config_str("[unopened_raise.BTN]\nrange = \"AZs\"\n").unwrap_err();
+    assert!(matches!(
+        &err,
+        ConfigError::InvalidRange(RangeParseError::InvalidHand { token, .. }) if token == "AZs"
+    ));
+    assert!(matches!(
+        CoreError::from(err),
+        CoreError::Config(ConfigError::InvalidRange(_))
+    ));
+}
+
+#[test]
+fn test_typed_errors_convert_to_core_error_with_question_mark() {
+    fn btn_range_size(config_str: &str, range_str: &str) -> Result<usize, CoreError> {
+        let config = load_config_from_str(config_str)?;
+        let range = parse_range_str(range_str)?;
+        Ok(config.unopened_raise_ranges[&Position::BTN].len() + range.len())
+    }
+
+    let config_str = "[unopened_raise.BTN]\nrange = \"AA\"\n";
+    assert_eq!(btn_range_size(config_str, "KK").unwrap(), 2);
+    let err = btn_range_size(config_str, "AKx").unwrap_err();
+    assert!(matches!(
+        &err,
+        CoreError::Parse(RangeParseError::InvalidHand { .. })
+    ));
+    assert!(err.source().unwrap().is::<RangeParseError>());
+    let err = btn_range_size("[unopened_raise.XX]\nrange = \"AA\"\n", "KK").unwrap_err();
+    assert!(matches!(
+        err,
+        CoreError::Config(ConfigError::UnknownPosition(_))
+    ));
+}
+
+#[test]
+fn test_out_of_range_frequencies_are_rejected() {
+    for field in ["AA:1.5", "AA:-0.2", "AA:150%", "AA:-5%"] {
+        let err = parse_range_str(field).unwrap_err();
+        assert!(
+            matches!(&err, RangeParseError::FrequencyOutOfRange { token, .. } if token == field),
+            "{}: {:?}",
+            field,
+            err
+        );
+    }
+    assert!(parse_range_str("AA:0,KK:1,QQ:0.0001").is_ok());
+
+    let err = parse_config_str("[unopened_raise.BTN]\nrange = \"AKs:1.01\"\n").unwrap_err();
+    assert!(matches!(
+        err,
+        ConfigError::InvalidRange(RangeParseError::FrequencyOutOfRange { .. })
+    ));
+}
+
+#[test]
+fn test_bb_defense_call_and_raise_over_100_percent_is_config_error() {
+    let err = parse_config_str(
+        "[unopened_raise.CO]\nrange = \"AA\"\n\n[bb_defense.CO]\ncall_range = \"AJs:0.6,KQs:0.5,T9s\"\nraise_range = \"AJs:0.5,KQs:0.5,AA\"\n",
+    )
+    .unwrap_err();
+    assert!(matches!(err, ConfigError::Invalid(_)));
+    assert_eq!(
+        err.to_string(),
+        "[bb_defense.CO] calls and raises more than 100% of: AJs (call 0.6 + raise 0.5)"
+    );
+
+    // Tables from [defense.BB_<Opener>] are checked too
+    let err = parse_config_str(
+        "[unopened_raise.CO]\nrange = \"AA\"\n\n[defense.BB_CO]\ncall_range = \"22+\"\nraise_range = \"QQ+\"\n",
+    )
+    .unwrap_err();
+    assert!(
+        err.to_string()
+            .starts_with("[defense.BB_CO] calls and raises"),
+        "{}",
+        err
+    );
+
+    // And every other call/raise table
+    for table in ["defense.BTN_CO", "vs_3bet.CO_BTN", "squeeze.CO_BTN"] {
+        let err = parse_config_str(&format!(
+            "[unopened_raise.CO]\nrange = \"AA\"\n\n[{}]\ncall_range = \"22+\"\nraise_range = \"QQ+\"\n",
+            table
+        ))
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with(&format!("[{}] calls and raises", table)),
+            "{}",
+            err
+        );
+    }
+}
+
+#[test]
+fn test_config_loads_from_a_string_or_reader() {
+    let contents = "[unopened_raise.BTN]\nrange = \"AA,KK\"\n";
+    let from_str = load_config_from_str(contents).unwrap();
+    let from_reader = load_config_from_reader(contents.as_bytes()).unwrap();
+    assert_eq!(from_str.unopened_raise_ranges[&Position::BTN].len(), 2);
+    assert_eq!(
+        from_reader.unopened_raise_ranges,
+        from_str.unopened_raise_ranges
+    );
+
+    // Bytes that aren't UTF-8 fail the read, before any TOML parsing
+    let err = load_config_from_reader(&[0xff, 0xfe, b'\n'][..]).unwrap_err();
+    assert!(matches!(err, ConfigError::Io(_)));
+    assert!(matches!(
+        load_config_from_reader("[unopened_raise.XX]\nrange = \"AA\"\n".as_bytes()),
+        Err(ConfigError::UnknownPosition(_))
+    ));
+}
+
+#[test]
+fn test_io_error_keeps_message_and_source() {
+    let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "ranges.toml not found");
+    let err = CoreError::from(io_err);
+    assert!(matches!(err, CoreError::Io(_)));
+    assert_eq!(err.to_string(), "ranges.toml not found");
+    assert!(err.source().is_some());
+}
+
+#[test]
+fn test_no_spot_types_is_spot_generation_error() {
+    let mut game = Game::new_with_seed(GameConfig::default(), 1);
+    let err = game.generate_random_spot().unwrap_err();
+    assert!(matches!(err, CoreError::SpotGeneration(_)));
+}
+
+#[test]
+fn test_allowed_spot_without_range_is_config_error() {
+    let err = parse_config_str(
+        "[unopened_raise.CO]\nrange = \"AA\"\n[generic]\nallowed_spot_types = [\"Open_CO\", \"BBDefense_CO\"]\n",
+    )
+    .unwrap_err();
+    assert!(matches!(err, ConfigError::Invalid(_)));
+    assert_eq!(
+        err.to_string(),
+        "allowed_spot_types lists BBDefense_CO, but its [bb_defense] range is missing or empty"
+    );
+
+    // An all-zero range counts as empty
+    let err = parse_config_str(
+        "[unopened_raise.CO]\nrange = \"AA:0\"\n[generic]\nallowed_spot_types = [\"Open_CO\"]\n",
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("Open_CO"), "{}", err);
+
+    assert!(
+        parse_config_str(
+            "[unopened_raise.CO]\nrange = \"AA\"\n[generic]\nallowed_spot_types = [\"Open_CO\"]\n",
+        )
+        .is_ok()
+    );
+}
@@ -1,8 +1,10 @@
 use preflop_trainer_core::{
-    AnswerResult, Card, Deck, GameConfig, Hand, Position, Rank, SpotType, Suit, UserAction,
-    check_answer, parse_range_str,
+    AnswerFeedback, AnswerResult, Card, Deck, GameConfig, Hand, HandNotation, Position, Rank,
+    SpotType, Suit, UserAction, check_answer, check_answer_detailed,
+    get_all_possible_hand_notations, get_correct_action,
 };
 use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
 
 // Helper to create a Card for tests
 fn c(rank_char: char, suit_char: char) -> Card {
@@ -28,55 +30,35 @@ fn create_full_test_game_config(
     let mut game_config_unopened_raise = HashMap::new();
     if let Some(ur_map) = unopened_raise_ranges {
         for (pos, range_str) in ur_map {
-            game_config_unopened_raise.insert(pos, parse_range_str(&range_str).unwrap());
+            game_config_unopened_raise.insert(pos, range_str.parse().unwrap());
         }
     }
 
     let mut game_config_bb_call = HashMap::new();
     if let Some(bb_call_map) = bb_defense_call_ranges {
         for (pos, range_str) in bb_call_map {
-            game_config_bb_call.insert(pos, parse_range_str(&range_str).unwrap());
+            game_config_bb_call.insert((pos, None), range_str.parse().unwrap());
         }
     }
 
     let mut game_config_bb_raise = HashMap::new();
     if let Some(bb_raise_map) = bb_defense_raise_ranges {
         for (pos, range_str) in bb_raise_map {
-            game_config_bb_raise.insert(pos, parse_range_str(&range_str).unwrap());
+            game_config_bb_raise.insert((pos, None), range_str.parse().unwrap());
         }
     }
 
     let default_allowed_spot_types = vec![
-        SpotType::Open {
-            position: Position::UTG,
-        },
-        SpotType::Open {
-            position: Position::MP,
-        },
-        SpotType::Open {
-            position: Position::CO,
-        },
-        SpotType::Open {
-            position: Position::BTN,
-        },
-        SpotType::Open {
-            position: Position::SB,
-        },
-        SpotType::BBDefense {
-            opener_position: Position::UTG,
-        },
-        SpotType::BBDefense {
-            opener_position: Position::MP,
-        },
-        SpotType::BBDefense {
-            opener_position: Position::CO,
-        },
-        SpotType::BBDefense {
-            opener_position: Position::BTN,
-        },
-        SpotType::BBDefense {
-            opener_position: Position::SB,
-        },
+        SpotType::open(Position::UTG),
+        SpotType::open(Position::MP),
+        SpotType::open(Position::CO),
+        SpotType::open(Position::BTN),
+        SpotType::open(Position::SB),
+        SpotType::bb_defense(Position::UTG),
+        SpotType::bb_defense(Position::MP),
+        SpotType::bb_defense(Position::CO),
+        SpotType::bb_defense(Position::BTN),
+        SpotType::bb_defense(Position::SB),
     ];
 
     GameConfig {
@@ -84,18 +66,16 @@ fn create_full_test_game_config(
         bb_defense_call_ranges: game_config_bb_call,
         bb_defense_raise_ranges: game_config_bb_raise,
         allowed_spot_types: allowed_spot_types.unwrap_or(default_allowed_spot_types),
+        ..Default::default()
     }
 }
 
 #[test]
 fn test_new_deck_has_52_unique_cards() {
     let deck = Deck::new();
-    assert_eq!(deck.cards.len(), 52);
+    assert_eq!(deck.len(), 52);
 
-    let mut unique_cards = HashSet::new();
-    for card in deck.cards {
-        unique_cards.insert(card);
-    }
+    let unique_cards: HashSet<Card> = deck.cards().iter().copied().collect();
     assert_eq!(unique_cards.len(), 52);
 }
 
@@ -103,21 +83,18 @@ fn test_new_deck_has_52_unique_cards() {
 fn test_shuffled_deck_retains_52_unique_cards() {
     let mut deck = Deck::new();
     deck.shuffle();
-    assert_eq!(deck.cards.len(), 52);
+    assert_eq!(deck.len(), 52);
 
-    let mut unique_cards = HashSet::new();
-    for card in deck.cards {
-        unique_cards.insert(card);
-    }
+    let unique_cards: HashSet<Card> = deck.cards().iter().copied().collect();
     assert_eq!(unique_cards.len(), 52);
 }
 
 #[test]
 fn test_deal_hand_removes_cards() {
     let mut deck = Deck::new();
-    let initial_len = deck.cards.len();
+    let initial_len = deck.len();
     let _hand = deck.deal_hand().expect("Should be able to deal a hand");
-    assert_eq!(deck.cards.len(), initial_len - 2);
+    assert_eq!(deck.len(), initial_len - 2);
 }
 
 #[test]
@@ -127,6 +104,55 @@ fn test_deal_hand_returns_two_distinct_cards() {
     assert_ne!(hand.card1, hand.card2); // Cards should be distinct
 }
 
+// What the combo index should hold: every pair of remaining cards, in deck order
+fn scanned_combos(deck: &Deck, notation: HandNotation) -> Vec<Hand> {
+    let mut combos = Vec::new();
+    for (i, &card1) in deck.cards().iter().enumerate() {
+        for &card2 in &deck.cards()[i + 1..] {
+            let hand = Hand { card1, card2 };
+            if HandNotation::from_hand(hand) == notation {
+                combos.push(hand);
+            }
+        }
+    }
+    combos
+}
+
+#[test]
+fn test_available_combos_follow_dealt_and_removed_cards() {
+    let mut deck = Deck::new();
+    deck.shuffle();
+    let check = |deck: &Deck| {
+        for notation in get_all_possible_hand_notations() {
+            assert_eq!(
+                deck.available_combos(notation),
+                scanned_combos(deck, notation).as_slice(),
+                "{}",
+                notation
+            );
+        }
+    };
+    check(&deck);
+
+    let aces: Vec<Card> = deck
+        .cards()
+        .iter()
+        .copied()
+        .filter(|card| card.rank == Rank::Ace)
+        .take(3)
+        .collect();
+    deck.remove_cards(&aces);
+    assert_eq!(deck.len(), 49);
+    check(&deck);
+
+    for _ in 0..10 {
+        deck.deal_hand().unwrap();
+        check(&deck);
+    }
+    let aa = HandNotation::from_str("AA").unwrap();
+    assert!(deck.available_combos(aa).is_empty());
+}
+
 #[test]
 fn test_deal_hand_empty_deck() {
     let mut deck = Deck::new();
@@ -138,6 +164,18 @@ fn test_deal_hand_empty_deck() {
     assert!(deck.deal_hand().is_none()); // Should return None when deck is empty
 }
 
+#[test]
+fn test_suit_to_symbol() {
+    assert_eq!(Suit::Spades.to_symbol(), '♠');
+    assert_eq!(Suit::Hearts.to_symbol(), '♥');
+    assert_eq!(Suit::Diamonds.to_symbol(), '♦');
+    assert_eq!(Suit::Clubs.to_symbol(), '♣');
+
+    // Every suit gets its own glyph
+    let symbols: HashSet<char> = Suit::VALUES.iter().map(|s| s.to_symbol()).collect();
+    assert_eq!(symbols.len(), 4);
+}
+
 #[test]
 fn test_check_answer_correct_raise_in_range_1_0_freq() {
     let mut ur_map = HashMap::new();
@@ -147,9 +185,7 @@ fn test_check_answer_correct_raise_in_range_1_0_freq() {
         card1: c('A', 's'),
         card2: c('A', 'c'),
     }; // AA
-    let spot_type = SpotType::Open {
-        position: Position::UTG,
-    };
+    let spot_type = SpotType::open(Position::UTG);
     let user_action = UserAction::Raise;
     let rng_value = 0; // Dummy value, not relevant for 1.0 frequency
 
@@ -170,9 +206,7 @@ fn test_check_answer_correct_raise_in_range_0_5_freq() {
         card1: c('K', 's'),
         card2: c('6', 's'),
     }; // K6s
-    let spot_type = SpotType::Open {
-        position: Position::UTG,
-    };
+    let spot_type = SpotType::open(Position::UTG);
     let user_action = UserAction::Raise;
     let rng_value = 20; // Will result in a raise
 
@@ -193,9 +227,7 @@ fn test_check_answer_correct_fold_not_in_range() {
         card1: c('2', 's'),
         card2: c('7', 'd'),
     }; // 72o
-    let spot_type = SpotType::Open {
-        position: Position::UTG,
-    };
+    let spot_type = SpotType::open(Position::UTG);
     let user_action = UserAction::Fold;
     let rng_value = 0; // Dummy value
 
@@ -216,9 +248,7 @@ fn test_check_answer_incorrect_fold_in_range() {
         card1: c('A', 's'),
         card2: c('K', 's'),
     }; // AKs
-    let spot_type = SpotType::Open {
-        position: Position::UTG,
-    };
+    let spot_type = SpotType::open(Position::UTG);
     let user_action = UserAction::Fold;
     let rng_value = 0; // Dummy value
 
@@ -239,9 +269,7 @@ fn test_check_answer_incorrect_raise_not_in_range() {
         card1: c('2', 's'),
         card2: c('2', 'c'),
     }; // 22
-    let spot_type = SpotType::Open {
-        position: Position::UTG,
-    };
+    let spot_type = SpotType::open(Position::UTG);
     let user_action = UserAction::Raise;
     let rng_value = 0; // Dummy value
 
@@ -262,9 +290,7 @@ fn test_check_answer_mixed_strategy_raise() {
         card1: c('K', 's'),
         card2: c('6', 's'),
     }; // K6s
-    let spot_type = SpotType::Open {
-        position: Position::UTG,
-    };
+    let spot_type = SpotType::open(Position::UTG);
     let user_action = UserAction::Raise;
     let rng_value = 20; // < 50, so should be a raise
 
@@ -285,9 +311,7 @@ fn test_check_answer_mixed_strategy_fold() {
         card1: c('K', 's'),
         card2: c('6', 's'),
     }; // K6s
-    let spot_type = SpotType::Open {
-        position: Position::UTG,
-    };
+    let spot_type = SpotType::open(Position::UTG);
     let user_action = UserAction::Fold;
     let rng_value = 70; // >= 50, so should be a fold
 
@@ -308,9 +332,7 @@ fn test_check_answer_mixed_strategy_zero_freq() {
         card1: c('K', 's'),
         card2: c('6', 's'),
     }; // K6s
-    let spot_type = SpotType::Open {
-        position: Position::UTG,
-    };
+    let spot_type = SpotType::open(Position::UTG);
     let user_action = UserAction::Fold;
     let rng_value = 10; // Irrelevant, should always be fold
 
@@ -322,6 +344,113 @@ fn test_check_answer_mixed_strategy_zero_freq() {
     );
 }
 
+#[test]
+fn test_get_correct_action_pins_the_rng_mapping() {
+    let mut ur_map = HashMap::new();
+    ur_map.insert(Position::UTG, "AA,QJs:0.35".to_string());
+    let mut bb_call_map = HashMap::new();
+    bb_call_map.insert(Position::SB, "QJs:0.5".to_string());
+    let mut bb_raise_map = HashMap::new();
+    bb_raise_map.insert(Position::SB, "QJs:0.3".to_string());
+    let config =
+        create_full_test_game_config(Some(ur_map), Some(bb_call_map), Some(bb_raise_map), None);
+    let queen_jack = Hand {
+        card1: c('Q', 'd'),
+        card2: c('J', 'd'),
+    };
+    let aces = Hand {
+        card1: c('A', 's'),
+        card2: c('A', 'h'),
+    };
+    let utg_open = SpotType::open(Position::UTG);
+    let bb_vs_sb = SpotType::bb_defense(Position::SB);
+
+    // Open: 0-34 raise, 35-99 fold
+    for (spot_type, hand, rng_value, expected) in [
+        (utg_open, queen_jack, 0, UserAction::Raise),
+        (utg_open, queen_jack, 34, UserAction::Raise),
+        (utg_open, queen_jack, 35, UserAction::Fold),
+        (utg_open, queen_jack, 99, UserAction::Fold),
+        (utg_open, aces, 99, UserAction::Raise),
+        // BB defense: 0-29 3-bet, 30-79 call, 80-99 fold
+        (bb_vs_sb, queen_jack, 0, UserAction::Raise),
+        (bb_vs_sb, queen_jack, 29, UserAction::Raise),
+        (bb_vs_sb, queen_jack, 30, UserAction::Call),
+        (bb_vs_sb, queen_jack, 79, UserAction::Call),
+        (bb_vs_sb, queen_jack, 80, UserAction::Fold),
+        (bb_vs_sb, queen_jack, 99, UserAction::Fold),
+    ] {
+        assert_eq!(
+            get_correct_action(&config, spot_type, hand, rng_value),
+            expected,
+            "{} rng {}",
+            spot_type,
+            rng_value
+        );
+    }
+
+    // check_answer grades exactly that action as correct
+    for spot_type in [utg_open, bb_vs_sb] {
+        for rng_value in 0..100 {
+            let correct = get_correct_action(&config, spot_type, queen_jack, rng_value);
+            for action in [UserAction::Raise, UserAction::Call, UserAction::Fold] {
+                let result = check_answer(&config, spot_type, queen_jack, action, rng_value);
+                assert_eq!(result == AnswerResult::Correct, action == correct);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_check_answer_detailed_explains_the_result() {
+    let mut ur_map = HashMap::new();
+    ur_map.insert(Position::UTG, "QJs:0.5".to_string());
+    let mut bb_call_map = HashMap::new();
+    bb_call_map.insert(Position::SB, "QJs:0.5".to_string());
+    let mut bb_raise_map = HashMap::new();
+    bb_raise_map.insert(Position::SB, "QJs:0.25".to_string());
+    let config =
+        create_full_test_game_config(Some(ur_map), Some(bb_call_map), Some(bb_raise_map), None);
+    let queen_jack = Hand {
+        card1: c('Q', 'd'),
+        card2: c('J', 'd'),
+    };
+    let utg_open = SpotType::open(Position::UTG);
+
+    // Raised, but 72 is past the 50% raise band
+    let feedback = check_answer_detailed(&config, utg_open, queen_jack, UserAction::Raise, 72);
+    assert_eq!(
+        feedback,
+        AnswerFeedback {
+            result: AnswerResult::FrequencyMistake,
+            correct_action: UserAction::Fold,
+            raise_freq: 0.5,
+            call_freq: 0.0,
+            fold_freq: 0.5,
+            rng_value: 72,
+        }
+    );
+
+    let bb_vs_sb = SpotType::bb_defense(Position::SB);
+    let feedback = check_answer_detailed(&config, bb_vs_sb, queen_jack, UserAction::Call, 30);
+    assert_eq!(feedback.result, AnswerResult::Correct);
+    assert_eq!(feedback.correct_action, UserAction::Call);
+    assert_eq!(
+        (feedback.raise_freq, feedback.call_freq, feedback.fold_freq),
+        (0.25, 0.5, 0.25)
+    );
+
+    // check_answer is the same result without the breakdown
+    for rng_value in [0, 24, 25, 74, 75, 99] {
+        for action in [UserAction::Raise, UserAction::Call, UserAction::Fold] {
+            assert_eq!(
+                check_answer(&config, bb_vs_sb, queen_jack, action, rng_value),
+                check_answer_detailed(&config, bb_vs_sb, queen_jack, action, rng_value).result
+            );
+        }
+    }
+}
+
 // --- New BBDefense tests for QJs (JdQd) vs SB Open ---
 
 #[test]
@@ -334,9 +463,7 @@ fn test_check_answer_bb_sb_open_qjs_raise_mixed_correct() {
         card1: c('J', 'd'),
         card2: c('Q', 'd'),
     }; // Jd Qd is QJs
-    let spot_type = SpotType::BBDefense {
-        opener_position: Position::SB,
-    };
+    let spot_type = SpotType::bb_defense(Position::SB);
     let user_action = UserAction::Raise;
     let rng_value = 20; // < 50, so it should hit the raise frequency
 
@@ -358,9 +485,7 @@ fn test_check_answer_bb_sb_open_qjs_raise_mixed_freq_mistake() {
         card1: c('J', 'd'),
         card2: c('Q', 'd'),
     }; // Jd Qd is QJs
-    let spot_type = SpotType::BBDefense {
-        opener_position: Position::SB,
-    };
+    let spot_type = SpotType::bb_defense(Position::SB);
     let user_action = UserAction::Raise;
     let rng_value = 70; // >= 50, so it should miss the raise frequency and expect a fold
 
@@ -382,9 +507,7 @@ fn test_check_answer_bb_sb_open_qjs_fold_mixed_correct() {
         card1: c('J', 'd'),
         card2: c('Q', 'd'),
     }; // Jd Qd is QJs
-    let spot_type = SpotType::BBDefense {
-        opener_position: Position::SB,
-    };
+    let spot_type = SpotType::bb_defense(Position::SB);
     let user_action = UserAction::Fold;
     let rng_value = 70; // >= 50, so it should miss the raise frequency and expect a fold
 
@@ -406,9 +529,7 @@ fn test_check_answer_bb_sb_open_qjs_call_when_raise_freq_non_zero() {
         card1: c('J', 'd'),
         card2: c('Q', 'd'),
     }; // Jd Qd is QJs
-    let spot_type = SpotType::BBDefense {
-        opener_position: Position::SB,
-    };
+    let spot_type = SpotType::bb_defense(Position::SB);
     let user_action = UserAction::Call;
     let rng_value = 20; // < 50, so should hit raise frequency
 
@@ -430,9 +551,7 @@ fn test_check_answer_bb_sb_open_qjs_call_when_raise_freq_zero() {
         card1: c('J', 'd'),
         card2: c('Q', 'd'),
     }; // Jd Qd is QJs
-    let spot_type = SpotType::BBDefense {
-        opener_position: Position::SB,
-    };
+    let spot_type = SpotType::bb_defense(Position::SB);
     let user_action = UserAction::Call;
     let rng_value = 20; // < 50, so should hit call frequency
 
@@ -454,9 +573,7 @@ fn test_check_answer_bb_sb_open_qjs_fold_when_call_freq_zero_mixed_freq_mistake(
         card1: c('J', 'd'),
         card2: c('Q', 'd'),
     }; // Jd Qd is QJs
-    let spot_type = SpotType::BBDefense {
-        opener_position: Position::SB,
-    };
+    let spot_type = SpotType::bb_defense(Position::SB);
     let user_action = UserAction::Fold;
     let rng_value = 20; // < 50, so should hit call frequency, expect call
 
@@ -467,3 +584,51 @@ fn test_check_answer_bb_sb_open_qjs_fold_when_call_freq_zero_mixed_freq_mistake(
         "Should be FrequencyMistake for folding QJs (0.5 call freq) with RNG < 50 in BB vs SB"
     );
 }
+
+#[test]
+fn test_check_answer_nearly_pure_frequencies_are_pure() {
+    let mut ur_map = HashMap::new();
+    ur_map.insert(Position::UTG, "AA:0.999,KK:0.001".to_string());
+    let config = create_full_test_game_config(Some(ur_map), None, None, None);
+    let spot_type = SpotType::open(Position::UTG);
+    let aces = Hand {
+        card1: c('A', 's'),
+        card2: c('A', 'h'),
+    };
+    let kings = Hand {
+        card1: c('K', 's'),
+        card2: c('K', 'h'),
+    };
+
+    for rng_value in [0, 50, 99] {
+        assert_eq!(
+            check_answer(&config, spot_type, aces, UserAction::Fold, rng_value),
+            AnswerResult::Wrong,
+            "folding AA:0.999 at rng {}",
+            rng_value
+        );
+        assert_eq!(
+            check_answer(&config, spot_type, aces, UserAction::Raise, rng_value),
+            AnswerResult::Correct
+        );
+        assert_eq!(
+            check_answer(&config, spot_type, kings, UserAction::Raise, rng_value),
+            AnswerResult::Wrong,
+            "raising KK:0.001 at rng {}",
+            rng_value
+        );
+    }
+
+    let mut bb_call_map = HashMap::new();
+    bb_call_map.insert(Position::SB, "QJs:0.9999".to_string());
+    let config = create_full_test_game_config(None, Some(bb_call_map), None, None);
+    let queen_jack = Hand {
+        card1: c('Q', 'd'),
+        card2: c('J', 'd'),
+    };
+    let bb_vs_sb = SpotType::bb_defense(Position::SB);
+    assert_eq!(
+        check_answer(&config, bb_vs_sb, queen_jack, UserAction::Fold, 99),
+        AnswerResult::Wrong
+    );
+}
@@ -1,7 +1,8 @@
 use preflop_trainer_core::{
-    Game, GameConfig, HandNotation, HandType, Position, Rank, SpotType, parse_range_str,
+    Game, GameConfig, HandNotation, HandType, Position, Rank, SpotType, TrainerSession, UserAction,
 };
 use std::collections::HashMap;
+use std::str::FromStr;
 
 // Helper to create a GameConfig for testing
 fn create_full_test_game_config(
@@ -13,55 +14,35 @@ fn create_full_test_game_config(
     let mut game_config_unopened_raise = HashMap::new();
     if let Some(ur_map) = unopened_raise_ranges {
         for (pos, range_str) in ur_map {
-            game_config_unopened_raise.insert(pos, parse_range_str(&range_str).unwrap());
+            game_config_unopened_raise.insert(pos, range_str.parse().unwrap());
         }
     }
 
     let mut game_config_bb_call = HashMap::new();
     if let Some(bb_call_map) = bb_defense_call_ranges {
         for (pos, range_str) in bb_call_map {
-            game_config_bb_call.insert(pos, parse_range_str(&range_str).unwrap());
+            game_config_bb_call.insert((pos, None), range_str.parse().unwrap());
         }
     }
 
     let mut game_config_bb_raise = HashMap::new();
     if let Some(bb_raise_map) = bb_defense_raise_ranges {
         for (pos, range_str) in bb_raise_map {
-            game_config_bb_raise.insert(pos, parse_range_str(&range_str).unwrap());
+            game_config_bb_raise.insert((pos, None), range_str.parse().unwrap());
         }
     }
 
     let default_allowed_spot_types = vec![
-        SpotType::Open {
-            position: Position::UTG,
-        },
-        SpotType::Open {
-            position: Position::MP,
-        },
-        SpotType::Open {
-            position: Position::CO,
-        },
-        SpotType::Open {
-            position: Position::BTN,
-        },
-        SpotType::Open {
-            position: Position::SB,
-        },
-        SpotType::BBDefense {
-            opener_position: Position::UTG,
-        },
-        SpotType::BBDefense {
-            opener_position: Position::MP,
-        },
-        SpotType::BBDefense {
-            opener_position: Position::CO,
-        },
-        SpotType::BBDefense {
-            opener_position: Position::BTN,
-        },
-        SpotType::BBDefense {
-            opener_position: Position::SB,
-        },
+        SpotType::open(Position::UTG),
+        SpotType::open(Position::MP),
+        SpotType::open(Position::CO),
+        SpotType::open(Position::BTN),
+        SpotType::open(Position::SB),
+        SpotType::bb_defense(Position::UTG),
+        SpotType::bb_defense(Position::MP),
+        SpotType::bb_defense(Position::CO),
+        SpotType::bb_defense(Position::BTN),
+        SpotType::bb_defense(Position::SB),
     ];
 
     GameConfig {
@@ -69,6 +50,7 @@ fn create_full_test_game_config(
         bb_defense_call_ranges: game_config_bb_call,
         bb_defense_raise_ranges: game_config_bb_raise,
         allowed_spot_types: allowed_spot_types.unwrap_or(default_allowed_spot_types),
+        ..Default::default()
     }
 }
 
@@ -78,7 +60,7 @@ fn test_game_new_deck_is_full() {
     ur_map.insert(Position::UTG, "AA".to_string());
     let config = create_full_test_game_config(Some(ur_map), None, None, None);
     let mut game = Game::new(config);
-    assert!(game.generate_random_spot().is_some());
+    assert!(game.generate_random_spot().is_ok());
 }
 
 #[test]
@@ -91,7 +73,7 @@ fn test_generate_random_spot_depletes_deck() {
     // Deal 26 hands (deplete the deck)
     for i in 0..26 {
         assert!(
-            game.generate_random_spot().is_some(),
+            game.generate_random_spot().is_ok(),
             "Should be able to deal hand {} from the first deck",
             i + 1
         );
@@ -100,14 +82,14 @@ fn test_generate_random_spot_depletes_deck() {
     // and the next call should trigger a reshuffle and deal a new hand.
     let next_spot = game.generate_random_spot();
     assert!(
-        next_spot.is_some(),
+        next_spot.is_ok(),
         "Game did not reshuffle and deal a new hand after deck was exhausted."
     );
 
     // And we should be able to deal more hands from the reshuffled deck
     for i in 0..26 {
         assert!(
-            game.generate_random_spot().is_some(),
+            game.generate_random_spot().is_ok(),
             "Should be able to deal hand {} from the reshuffled deck",
             i + 1
         );
@@ -123,17 +105,17 @@ fn test_deck_reshuffles_and_continues() {
 
     // Exhaust the first deck
     for _ in 0..26 {
-        game.generate_random_spot();
+        game.generate_random_spot().unwrap();
     }
 
     // The 27th hand should be valid after a reshuffle
     let spot = game.generate_random_spot();
-    assert!(spot.is_some());
+    assert!(spot.is_ok());
 
     // We can't easily check that the deck is *different*, but we can check that we can keep dealing
     let mut successful_deals = 0;
     for _ in 0..26 {
-        if let Some((_, _, _)) = game.generate_random_spot() {
+        if let Ok((_, _, _)) = game.generate_random_spot() {
             successful_deals += 1;
         }
     }
@@ -144,72 +126,76 @@ fn test_deck_reshuffles_and_continues() {
 }
 
 #[test]
-
 fn test_weighted_random_hand_selection() {
-    // Define a very specific range for UTG: only AA
-
+    // Only AA is in UTG's range, and UTG's open is the only spot
+    let utg_open = SpotType::open(Position::UTG);
     let mut ur_map = HashMap::new();
     ur_map.insert(Position::UTG, "AA".to_string());
+    let config = create_full_test_game_config(Some(ur_map), None, None, Some(vec![utg_open]));
+    let mut game = Game::new_with_seed(config, 42);
 
-    let config = create_full_test_game_config(Some(ur_map), None, None, None);
-
-    let mut game = Game::new(config);
-
+    let aa_notation = HandNotation {
+        rank1: Rank::Ace,
+        rank2: Rank::Ace,
+        hand_type: HandType::Pair,
+    };
+    let iterations = 10000;
     let mut aa_count = 0;
-
-    let iterations = 10000; // Increased iterations for better statistical significance
-
     for _ in 0..iterations {
-        if let Some((
-            SpotType::Open {
-                position: Position::UTG,
-            },
-            hand,
-            _,
-        )) = game.generate_random_spot()
-        {
-            // Only count AA if it's an Open spot from UTG, as configured.
-            let hn = HandNotation::from_hand(hand);
-
-            let aa_notation = HandNotation {
-                rank1: Rank::Ace,
-                rank2: Rank::Ace,
-                hand_type: HandType::Pair,
-            };
-
-            if hn == aa_notation {
-                aa_count += 1;
-            }
+        let (spot_type, hand, _) = game.generate_random_spot().unwrap();
+        assert_eq!(spot_type, utg_open);
+        if HandNotation::from_hand(hand) == aa_notation {
+            aa_count += 1;
         }
     }
 
-    // Recalculate expected percentage more accurately based on the weights.
-
-    // AA has weight 50 (for 1.0 freq). Other 168 hands have weight 20.
-
-    // Total weighted "units" for any hand being drawn: 50 (for AA) + (168 * 20) = 3410.
-
-    // Probability of drawing AA in an Open spot from UTG (where it's the only 1.0 freq hand): 50 / 3410 = ~0.0146.
-
-    // Since generate_random_spot has a 50% chance of being an Open spot,
-
-    // and there are 5 possible opening positions, the probability of an Open spot from UTG is 0.5 * (1/5) = 0.1.
-
-    // So, the expected AA count in 10000 iterations from UTG Open spots is:
-
-    // 10000 (iterations) * (50 / 3410) (prob of AA in weighted list) * 0.1 (prob of UTG Open spot) = ~14.6
-
-    // Let's set a conservative lower bound for actual_aa_percentage.
+    // AA has weight 50 (for 1.0 freq) times its 6 combos, 300. The other 168 hands have
+    // weight 20 times their combos, 1320 in all, so AA is drawn with p = 300 / 26700.
+    // Allow four standard deviations of the binomial count either side.
+    let p = 300.0 / 26700.0;
+    let n = iterations as f64;
+    let expected = n * p;
+    let margin = 4.0 * (n * p * (1.0 - p)).sqrt();
+    assert!(
+        (aa_count as f64 - expected).abs() <= margin,
+        "Expected about {:.0} AA out of {}, got {}",
+        expected,
+        iterations,
+        aa_count
+    );
+}
 
-    let min_expected_aa_percentage = (50.0 / 3410.0) * (1.0 / 5.0) * 0.5 * 0.5; // (Prob AA in list) * (Prob Open from UTG) * safety margin (50%)
+#[test]
+fn test_generate_random_notation_uses_the_same_weights() {
+    let utg_open = SpotType::open(Position::UTG);
+    let mut ur_map = HashMap::new();
+    ur_map.insert(Position::UTG, "AA".to_string());
+    let config = create_full_test_game_config(Some(ur_map), None, None, Some(vec![utg_open]));
+    let mut game = Game::new_with_seed(config, 42);
 
-    let actual_aa_percentage = aa_count as f32 / iterations as f32;
+    let aa_notation = HandNotation::from_str("AA").unwrap();
+    let iterations = 10000;
+    let mut aa_count = 0;
+    for _ in 0..iterations {
+        let (spot_type, notation, rng_value) = game.generate_random_notation().unwrap();
+        assert_eq!(spot_type, utg_open);
+        assert!(rng_value < 100);
+        if notation == aa_notation {
+            aa_count += 1;
+        }
+    }
 
+    // Same weights as test_weighted_random_hand_selection, without dealing any cards
+    let p = 300.0 / 26700.0;
+    let n = iterations as f64;
+    let expected = n * p;
+    let margin = 4.0 * (n * p * (1.0 - p)).sqrt();
     assert!(
-        actual_aa_percentage >= min_expected_aa_percentage,
-        "Expected AA percentage to be at least {:.2}%, but got {:.2}%",
-        min_expected_aa_percentage * 100.0,
-        actual_aa_percentage * 100.0
+        (aa_count as f64 - expected).abs() <= margin,
+        "Expected about {:.0} AA out of {}, got {}",
+        expected,
+        iterations,
+        aa_count
     );
 }
 
@@ -226,19 +212,17 @@ fn test_weighted_random_hand_selection_with_adjusted_weights() {
         Some(ur_map),
         None,
         None,
-        Some(vec![SpotType::Open {
-            position: Position::UTG,
-        }]),
+        Some(vec![SpotType::open(Position::UTG)]),
     );
 
-    let mut game = Game::new(config);
+    let mut game = Game::new_with_seed(config, 7);
 
     let mut aa_count = 0;
     let mut other_count = 0;
     let iterations = 20000; // More iterations for statistical reliability
 
     for _ in 0..iterations {
-        if let Some((_, hand, _)) = game.generate_random_spot() {
+        if let Ok((_, hand, _)) = game.generate_random_spot() {
             let hn = HandNotation::from_hand(hand);
             let aa_notation = HandNotation {
                 rank1: Rank::Ace,
@@ -254,16 +238,16 @@ fn test_weighted_random_hand_selection_with_adjusted_weights() {
         }
     }
 
-    // With the new weights:
-    // Weight of AA (in-range) = 50
-    // Weight of any other hand (out-of-range) = 20
-    // Total hands = 169. 1 is in-range, 168 are out-of-range.
-    // Total weight = 50 * 1 + 20 * 168 = 50 + 3360 = 3410.
-    // Probability of AA = 50 / 3410 = ~0.01466
-    // Probability of not AA = 3360 / 3410 = ~0.98534
+    // With the new weights, each scaled by the notation's combo count:
+    // Weight of AA (in-range) = 50 * 6 = 300
+    // Weight of any other hand (out-of-range) = 20 * its combos
+    // The other 168 notations have 1326 - 6 = 1320 combos between them.
+    // Total weight = 300 + 20 * 1320 = 300 + 26400 = 26700.
+    // Probability of AA = 300 / 26700 = ~0.01124
+    // Probability of not AA = 26400 / 26700 = ~0.98876
 
     // Expected ratio of other hands to AA hands:
-    let expected_ratio = (168.0 * 20.0) / 50.0; // Expected to be 67.2
+    let expected_ratio = (1320.0 * 20.0) / 300.0; // Expected to be 88
 
     let actual_ratio = if aa_count > 0 {
         other_count as f32 / aa_count as f32
@@ -284,3 +268,189 @@ fn test_weighted_random_hand_selection_with_adjusted_weights() {
         upper_bound
     );
 }
+
+#[test]
+fn test_same_seed_reproduces_same_first_spot() {
+    let mut ur_map = HashMap::new();
+    ur_map.insert(Position::UTG, "AA,KK,AKs:0.5".to_string());
+    let config = create_full_test_game_config(Some(ur_map), None, None, None);
+
+    let mut game1 = Game::new_with_seed(config.clone(), 1234);
+    let mut game2 = Game::new_with_seed(config, 1234);
+
+    assert_eq!(
+        game1.generate_random_spot().unwrap(),
+        game2.generate_random_spot().unwrap()
+    );
+}
+
+#[test]
+fn test_same_seed_reproduces_same_spot_sequence() {
+    let mut ur_map = HashMap::new();
+    ur_map.insert(Position::UTG, "AA,KK,AKs:0.5".to_string());
+    let mut call_map = HashMap::new();
+    call_map.insert(Position::UTG, "QQ,AQs:0.5".to_string());
+    let config = create_full_test_game_config(
+        Some(ur_map),
+        Some(call_map),
+        None,
+        Some(vec![
+            SpotType::open(Position::UTG),
+            SpotType::bb_defense(Position::UTG),
+        ]),
+    );
+
+    let sequence = |seed: u64| {
+        let mut game = Game::new_with_seed(config.clone(), seed);
+        (0..200)
+            .map(|_| game.generate_random_spot().unwrap())
+            .collect::<Vec<_>>()
+    };
+    // 200 spots use up the deck several times, so reshuffles are covered too
+    assert_eq!(sequence(42), sequence(42));
+    assert_ne!(sequence(42), sequence(43));
+}
+
+#[test]
+fn test_peek_next_spot_matches_generated_spot() {
+    let mut ur_map = HashMap::new();
+    ur_map.insert(Position::UTG, "AA,KK,AKs:0.5".to_string());
+    let mut config = create_full_test_game_config(Some(ur_map), None, None, None);
+    config.coverage_first = true;
+    let mut game = Game::new_with_seed(config, 99);
+
+    for _ in 0..60 {
+        let peeked = game.peek_next_spot().unwrap();
+        assert_eq!(game.peek_next_spot().unwrap(), peeked);
+        assert_eq!(game.generate_random_spot().unwrap(), peeked);
+    }
+}
+
+#[test]
+fn test_deal_notation_exact_returns_requested_notation() {
+    let config = create_full_test_game_config(None, None, None, None);
+    let mut game = Game::new_with_seed(config.clone(), 5);
+    let target = HandNotation::from_str("AKs").unwrap();
+
+    // Every suited AK is dealt once, then the deck runs out of them
+    for _ in 0..4 {
+        let hand = game.deal_notation_exact(target).unwrap();
+        assert_eq!(HandNotation::from_hand(hand), target);
+    }
+    assert_eq!(game.deal_notation_exact(target), None);
+
+    // The same deck state always yields the same hand
+    let mut first = Game::new_with_seed(config.clone(), 9);
+    let mut second = Game::new_with_seed(config, 9);
+    let pair = HandNotation::from_str("77").unwrap();
+    assert_eq!(
+        first.deal_notation_exact(pair),
+        second.deal_notation_exact(pair)
+    );
+}
+
+#[test]
+fn test_reset_deals_from_a_full_deck_again() {
+    let config = create_full_test_game_config(None, None, None, None);
+    let mut game = Game::new_with_seed(config, 5);
+    let target = HandNotation::from_str("AKs").unwrap();
+    for _ in 0..4 {
+        game.deal_notation_exact(target).unwrap();
+    }
+    assert_eq!(game.deal_notation_exact(target), None);
+
+    game.reset();
+    for _ in 0..4 {
+        let hand = game.deal_notation_exact(target).unwrap();
+        assert_eq!(HandNotation::from_hand(hand), target);
+    }
+}
+
+#[test]
+fn test_set_config_serves_only_the_new_ranges() {
+    let mut ur_map = HashMap::new();
+    ur_map.insert(Position::UTG, "AA,KK".to_string());
+    let utg_open = SpotType::open(Position::UTG);
+    let mut config = create_full_test_game_config(Some(ur_map), None, None, Some(vec![utg_open]));
+    config.quiz_folds = false;
+    let mut game = Game::new_with_seed(config, 21);
+    for _ in 0..30 {
+        game.generate_random_spot().unwrap();
+    }
+
+    let mut btn_map = HashMap::new();
+    btn_map.insert(Position::BTN, "T9s,98s".to_string());
+    let btn_open = SpotType::open(Position::BTN);
+    let mut new_config =
+        create_full_test_game_config(Some(btn_map), None, None, Some(vec![btn_open]));
+    new_config.quiz_folds = false;
+    new_config.coverage_first = true;
+    game.set_config(new_config);
+    let new_hands = [
+        HandNotation::from_str("T9s").unwrap(),
+        HandNotation::from_str("98s").unwrap(),
+    ];
+    for _ in 0..100 {
+        let (spot_type, hand, _) = game.generate_random_spot().unwrap();
+        assert_eq!(spot_type, btn_open);
+        assert!(new_hands.contains(&HandNotation::from_hand(hand)));
+    }
+}
+
+#[test]
+fn test_session_set_config_narrows_dealt_spots() {
+    let mut ur_map = HashMap::new();
+    ur_map.insert(Position::UTG, "AA,KK".to_string());
+    ur_map.insert(Position::BTN, "22+".to_string());
+    let utg_open = SpotType::open(Position::UTG);
+    let btn_open = SpotType::open(Position::BTN);
+    let config =
+        create_full_test_game_config(Some(ur_map), None, None, Some(vec![utg_open, btn_open]));
+    let mut session = TrainerSession::new(Game::new_with_seed(config.clone(), 5), config.clone());
+    let (spot_type, hand, rng) = session.next_spot().unwrap();
+    session.answer(spot_type, hand, UserAction::Fold, rng);
+
+    let mut btn_only = config.clone();
+    btn_only.allowed_spot_types = vec![btn_open];
+    session.set_config(btn_only);
+    for _ in 0..50 {
+        let (spot_type, _, _) = session.next_spot().unwrap();
+        assert_eq!(spot_type, btn_open);
+    }
+    assert_eq!(session.stats().totals.overall.answered, 1);
+    assert_eq!(session.export_spot_list().len(), 51);
+}
+
+#[test]
+fn test_session_undo_last_takes_back_the_answer_and_the_next_deal() {
+    let mut ur_map = HashMap::new();
+    ur_map.insert(Position::UTG, "AA,KK".to_string());
+    let utg_open = SpotType::open(Position::UTG);
+    let config = create_full_test_game_config(Some(ur_map), None, None, Some(vec![utg_open]));
+    let mut session = TrainerSession::new(Game::new_with_seed(config.clone(), 5), config.clone());
+    assert!(!session.undo_last());
+
+    let (spot_type, hand, rng) = session.next_spot().unwrap();
+    session.answer(spot_type, hand, UserAction::Fold, rng);
+    let stats_before = session.stats().clone();
+    let (spot_type, hand, rng) = session.next_spot().unwrap();
+    session.answer(spot_type, hand, UserAction::Raise, rng);
+    let next = session.next_spot().unwrap();
+    assert!(session.can_undo());
+
+    assert!(session.undo_last());
+    assert_eq!(session.stats(), &stats_before);
+    assert_eq!(session.export_spot_list().len(), 2);
+    // Only one level
+    assert!(!session.can_undo());
+    assert!(!session.undo_last());
+    assert_eq!(session.stats().totals.overall.answered, 1);
+
+    // Answering again deals the same next spot as before
+    session.answer(spot_type, hand, UserAction::Raise, rng);
+    assert_eq!(session.next_spot().unwrap(), next);
+
+    // Clearing the stats drops the undo with them
+    session.clear_stats();
+    assert!(!session.undo_last());
+}
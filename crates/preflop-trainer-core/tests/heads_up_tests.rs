@@ -0,0 +1,120 @@
+use preflop_trainer_core::{
+    ConfigError, CoreError, Game, Position, SpotType, TableSize, parse_config_str,
+};
+use std::str::FromStr;
+
+const CONFIG: &str = r#"
+[generic]
+table_size = "heads_up"
+
+[unopened_raise.SB]
+range = "22+,A2s+,K9o+"
+
+[sb_limp]
+range = "K2o+,Q8o+"
+
+[bb_defense.SB]
+call_range = "22-99,A2s+"
+raise_range = "TT+,AKo"
+"#;
+
+#[test]
+fn test_table_size_parses_names_and_seat_counts() {
+    assert_eq!(TableSize::from_str("heads_up"), Ok(TableSize::HeadsUp));
+    assert_eq!(TableSize::from_str("nine_max"), Ok(TableSize::NineMax));
+    assert!(TableSize::from_str("full_ring").is_err());
+    assert_eq!(TableSize::from_seats(2), Some(TableSize::HeadsUp));
+    assert_eq!(TableSize::HeadsUp.positions(), [Position::SB, Position::BB]);
+
+    for (toml, table_size) in [
+        ("table_size = 2", TableSize::HeadsUp),
+        ("table_size = \"six_max\"", TableSize::SixMax),
+        ("table_size = 9", TableSize::NineMax),
+    ] {
+        let config = parse_config_str(&format!(
+            "[generic]\n{}\n\n[unopened_raise.SB]\nrange = \"AA\"\n",
+            toml
+        ))
+        .unwrap();
+        assert_eq!(config.table_size, table_size, "{}", toml);
+    }
+    assert!(parse_config_str("[generic]\ntable_size = 4\n").is_err());
+}
+
+#[test]
+fn test_heads_up_defaults_to_sb_and_bb_spots() {
+    let config = parse_config_str(CONFIG).unwrap();
+    assert_eq!(config.table_size, TableSize::HeadsUp);
+    assert_eq!(
+        config.allowed_spot_types,
+        vec![SpotType::SbLimp, SpotType::bb_defense(Position::SB)]
+    );
+
+    // The cutoff isn't at a heads-up table, to list or to give ranges
+    let listed = CONFIG.replace(
+        "table_size = \"heads_up\"",
+        "table_size = \"heads_up\"\nallowed_spot_types = [\"Open_CO\"]",
+    );
+    assert!(matches!(
+        parse_config_str(&listed),
+        Err(ConfigError::Invalid(_))
+    ));
+    let with_co = format!("{}\n[unopened_raise.CO]\nrange = \"22+\"\n", CONFIG);
+    assert_eq!(
+        parse_config_str(&with_co).unwrap_err().to_string(),
+        "[unopened_raise.CO] names CO, which isn't a seat at a 2-handed table"
+    );
+}
+
+#[test]
+fn test_table_keys_and_spots_only_use_seats_of_one_table() {
+    // Hijack is a 9-max seat and MP a 6-max one
+    assert!(SpotType::from_str("Defense_HJ_MP").is_err());
+    assert!(SpotType::from_str("Squeeze_MP_HJ").is_err());
+    assert!(SpotType::from_str("Defense_HJ_LJ").is_ok());
+
+    for (table_size, table) in [
+        (
+            "six_max",
+            "[defense.HJ_CO]\ncall_range = \"AA\"\nraise_range = \"\"",
+        ),
+        ("nine_max", "[unopened_raise.MP]\nrange = \"AA\""),
+        ("nine_max", "[push_fold.MP_10]\nrange = \"AA\""),
+    ] {
+        let config = format!(
+            "[generic]\ntable_size = \"{}\"\n\n[unopened_raise.CO]\nrange = \"AA\"\n\n{}\n",
+            table_size, table
+        );
+        assert!(
+            matches!(parse_config_str(&config), Err(ConfigError::Invalid(_))),
+            "{}",
+            config
+        );
+    }
+}
+
+#[test]
+fn test_heads_up_only_generates_sb_and_bb_spots() {
+    let config = parse_config_str(CONFIG).unwrap();
+    let mut game = Game::new_with_seed(config, 21);
+    for _ in 0..200 {
+        let (spot_type, _, _) = game.generate_random_spot().unwrap();
+        assert!(
+            spot_type
+                .positions()
+                .iter()
+                .all(|position| matches!(position, Position::SB | Position::BB)),
+            "{}",
+            spot_type.to_config_string()
+        );
+    }
+
+    // Even when the allowed list is edited after loading
+    let mut config = parse_config_str(CONFIG).unwrap();
+    config.allowed_spot_types = vec![SpotType::open(Position::CO)];
+    let mut game = Game::new_with_seed(config, 21);
+    assert!(matches!(
+        game.generate_random_spot(),
+        Err(CoreError::SpotGeneration(_))
+    ));
+}
@@ -1,4 +1,7 @@
-use preflop_trainer_core::{HandNotation, HandType, Rank, parse_range_str};
+use preflop_trainer_core::{
+    Card, CardColorScheme, Hand, HandNotation, HandType, RangeParseError, Rank, RgbColor, Suit,
+    config_json_schema, get_all_possible_hand_notations, parse_config_str, parse_range_str,
+};
 use std::str::FromStr;
 
 // Helper to create a HandNotation for tests
@@ -13,9 +16,9 @@ fn test_parse_range_str_simple() {
     let range_map = parse_range_str(range_str).unwrap();
 
     assert_eq!(range_map.len(), expected_len);
-    assert_eq!(range_map.get(&hn("AA")), Some(&1.0));
-    assert_eq!(range_map.get(&hn("KQs")), Some(&1.0));
-    assert_eq!(range_map.get(&hn("T9o")), Some(&1.0));
+    assert_eq!(range_map.get(hn("AA")), Some(1.0));
+    assert_eq!(range_map.get(hn("KQs")), Some(1.0));
+    assert_eq!(range_map.get(hn("T9o")), Some(1.0));
 }
 
 #[test]
@@ -24,9 +27,9 @@ fn test_parse_range_str_with_frequencies() {
     let range_map = parse_range_str(range_str).unwrap();
 
     assert_eq!(range_map.len(), 3);
-    assert_eq!(range_map.get(&hn("AA")), Some(&1.0));
-    assert_eq!(range_map.get(&hn("KQs")), Some(&0.5));
-    assert_eq!(range_map.get(&hn("T9o")), Some(&0.25));
+    assert_eq!(range_map.get(hn("AA")), Some(1.0));
+    assert_eq!(range_map.get(hn("KQs")), Some(0.5));
+    assert_eq!(range_map.get(hn("T9o")), Some(0.25));
 }
 
 #[test]
@@ -35,9 +38,9 @@ fn test_parse_range_str_with_whitespace() {
     let range_map = parse_range_str(range_str).unwrap();
 
     assert_eq!(range_map.len(), 3);
-    assert!(range_map.contains_key(&hn("AA")));
-    assert!(range_map.contains_key(&hn("KQs")));
-    assert!(range_map.contains_key(&hn("T9o")));
+    assert!(range_map.contains(hn("AA")));
+    assert!(range_map.contains(hn("KQs")));
+    assert!(range_map.contains(hn("T9o")));
 }
 
 #[test]
@@ -47,6 +50,31 @@ fn test_parse_range_str_empty() {
     assert!(range_map.is_empty());
 }
 
+#[test]
+fn test_parse_range_str_rejects_empty_entries() {
+    for range_str in ["AA,,KK", "AA,", ",AA", "AA, ,KK"] {
+        let err = parse_range_str(range_str).unwrap_err();
+        assert_eq!(
+            err,
+            RangeParseError::EmptyToken {
+                field: String::new()
+            },
+            "{}",
+            range_str
+        );
+        assert!(err.to_string().contains("trailing comma"), "{}", err);
+    }
+    assert!(matches!(
+        parse_range_str(":0.5"),
+        Err(RangeParseError::EmptyToken { .. })
+    ));
+    assert!(matches!(
+        parse_range_str("AA:0.5:0.3"),
+        Err(RangeParseError::InvalidFrequency { .. })
+    ));
+    assert!(parse_range_str("   ").unwrap().is_empty());
+}
+
 #[test]
 fn test_parse_range_str_invalid_hand() {
     let range_str = "AA,InvalidHand,KK";
@@ -101,6 +129,26 @@ fn test_hand_notation_from_str() {
     );
 }
 
+#[test]
+fn test_hand_notation_display_round_trips() {
+    let all_notations = get_all_possible_hand_notations();
+    assert_eq!(all_notations.len(), 169);
+    for notation in all_notations {
+        assert_eq!(HandNotation::from_str(&notation.to_string()), Ok(notation));
+    }
+    assert_eq!(hn("AA").to_string(), "AA");
+    assert_eq!(hn("KAs").to_string(), "AKs");
+    assert_eq!(hn("9To").to_string(), "T9o");
+
+    // High rank first even when the fields are filled in low-high
+    let reversed = HandNotation {
+        rank1: Rank::Nine,
+        rank2: Rank::Ten,
+        hand_type: HandType::Offsuit,
+    };
+    assert_eq!(reversed.to_string(), "T9o");
+}
+
 #[test]
 fn test_hand_notation_from_str_invalid() {
     assert!(HandNotation::from_str("AXs").is_err());
@@ -110,22 +158,97 @@ fn test_hand_notation_from_str_invalid() {
     assert!(HandNotation::from_str("AK").is_err());
 }
 
+#[test]
+fn test_card_from_str() {
+    assert_eq!(
+        Card::from_str("As"),
+        Ok(Card {
+            rank: Rank::Ace,
+            suit: Suit::Spades
+        })
+    );
+    assert_eq!(
+        Card::from_str("Td"),
+        Ok(Card {
+            rank: Rank::Ten,
+            suit: Suit::Diamonds
+        })
+    );
+    assert_eq!(Card::from_str("2C"), Card::from_str("2c"));
+
+    for rank in Rank::VALUES {
+        for suit in Suit::VALUES {
+            let card = Card { rank, suit };
+            assert_eq!(Card::from_str(&card.to_string()), Ok(card));
+        }
+    }
+}
+
+#[test]
+fn test_card_from_str_invalid() {
+    for input in ["", "A", "Asd", "10s"] {
+        let err = Card::from_str(input).unwrap_err();
+        assert!(err.contains("expected a rank and a suit"), "{}", err);
+    }
+    assert!(
+        Card::from_str("Ax")
+            .unwrap_err()
+            .contains("expected s, h, d or c")
+    );
+    assert!(
+        Card::from_str("Xs")
+            .unwrap_err()
+            .contains("Invalid rank character")
+    );
+}
+
+#[test]
+fn test_hand_from_str() {
+    let card = |s: &str| Card::from_str(s).unwrap();
+
+    let suited = Hand::from_str("Td9d").unwrap();
+    assert_eq!((suited.card1, suited.card2), (card("Td"), card("9d")));
+    assert_eq!(HandNotation::from_hand(suited), hn("T9s"));
+
+    let offsuit = Hand::from_str("AsKh").unwrap();
+    assert_eq!(HandNotation::from_hand(offsuit), hn("AKo"));
+    // The Display form parses back
+    assert_eq!(Hand::from_str(&offsuit.to_string()), Ok(offsuit));
+
+    let paired = Hand::from_str("7c7D").unwrap();
+    assert_eq!((paired.card1, paired.card2), (card("7c"), card("7d")));
+    assert_eq!(HandNotation::from_hand(paired), hn("77"));
+}
+
+#[test]
+fn test_hand_from_str_invalid() {
+    assert_eq!(
+        Hand::from_str("AsAs").unwrap_err(),
+        "Hand uses As twice".to_string()
+    );
+    for input in ["", "AsK", "AsKhQ", "AKs"] {
+        let err = Hand::from_str(input).unwrap_err();
+        assert!(err.contains("expected two cards"), "{}", err);
+    }
+    assert!(Hand::from_str("AsKx").is_err());
+}
+
 #[test]
 fn test_parse_range_str_plus_notation_pairs() {
     let range_str = "22+";
     let range_map = parse_range_str(range_str).unwrap();
 
     // Should be in range
-    assert!(range_map.contains_key(&hn("22")));
-    assert!(range_map.contains_key(&hn("44")));
-    assert!(range_map.contains_key(&hn("55")));
-    assert!(range_map.contains_key(&hn("AA")));
+    assert!(range_map.contains(hn("22")));
+    assert!(range_map.contains(hn("44")));
+    assert!(range_map.contains(hn("55")));
+    assert!(range_map.contains(hn("AA")));
 
     // Should NOT be in range
-    assert!(!range_map.contains_key(&hn("23s")));
-    assert!(!range_map.contains_key(&hn("23o")));
-    assert!(!range_map.contains_key(&hn("A2s")));
-    assert!(!range_map.contains_key(&hn("A2o"))); // Assuming "A2" meant A2s or A2o
+    assert!(!range_map.contains(hn("23s")));
+    assert!(!range_map.contains(hn("23o")));
+    assert!(!range_map.contains(hn("A2s")));
+    assert!(!range_map.contains(hn("A2o"))); // Assuming "A2" meant A2s or A2o
 }
 
 #[test]
@@ -134,14 +257,14 @@ fn test_parse_range_str_plus_notation_suited() {
     let range_map = parse_range_str(range_str).unwrap();
 
     // Should be in range
-    assert!(range_map.contains_key(&hn("A3s")));
-    assert!(range_map.contains_key(&hn("A4s")));
-    assert!(range_map.contains_key(&hn("AKs")));
+    assert!(range_map.contains(hn("A3s")));
+    assert!(range_map.contains(hn("A4s")));
+    assert!(range_map.contains(hn("AKs")));
 
     // Should NOT be in range
-    assert!(!range_map.contains_key(&hn("A2s")));
-    assert!(!range_map.contains_key(&hn("AA")));
-    assert!(!range_map.contains_key(&hn("A3o")));
+    assert!(!range_map.contains(hn("A2s")));
+    assert!(!range_map.contains(hn("AA")));
+    assert!(!range_map.contains(hn("A3o")));
 }
 
 #[test]
@@ -150,12 +273,272 @@ fn test_parse_range_str_plus_notation_offsuit() {
     let range_map = parse_range_str(range_str).unwrap();
 
     // Should be in range
-    assert!(range_map.contains_key(&hn("KTo")));
-    assert!(range_map.contains_key(&hn("KJo")));
-    assert!(range_map.contains_key(&hn("KQo")));
+    assert!(range_map.contains(hn("KTo")));
+    assert!(range_map.contains(hn("KJo")));
+    assert!(range_map.contains(hn("KQo")));
 
     // Should NOT be in range
-    assert!(!range_map.contains_key(&hn("K9o")));
-    assert!(!range_map.contains_key(&hn("K2o")));
-    assert!(!range_map.contains_key(&hn("KTs")));
+    assert!(!range_map.contains(hn("K9o")));
+    assert!(!range_map.contains(hn("K2o")));
+    assert!(!range_map.contains(hn("KTs")));
+}
+
+#[test]
+fn test_parse_range_str_combined_token() {
+    let range_map = parse_range_str("QQ+&AQs+").unwrap();
+
+    assert_eq!(range_map.len(), 5);
+    for hand in ["QQ", "KK", "AA", "AQs", "AKs"] {
+        assert_eq!(range_map.get(hn(hand)), Some(1.0), "{} missing", hand);
+    }
+    assert!(!range_map.contains(hn("JJ")));
+    assert!(!range_map.contains(hn("AJs")));
+    assert!(!range_map.contains(hn("AQo")));
+}
+
+#[test]
+fn test_parse_range_str_combined_token_with_frequency() {
+    let range_map = parse_range_str("AA, JJ & KQo+ & A5s:0.5").unwrap();
+
+    assert_eq!(range_map.len(), 4);
+    assert_eq!(range_map.get(hn("AA")), Some(1.0));
+    assert_eq!(range_map.get(hn("JJ")), Some(0.5));
+    assert_eq!(range_map.get(hn("KQo")), Some(0.5));
+    assert_eq!(range_map.get(hn("A5s")), Some(0.5));
+}
+
+#[test]
+fn test_parse_range_str_combined_token_invalid_part() {
+    assert!(parse_range_str("QQ+&AXs").is_err());
+    assert!(parse_range_str("QQ+&").is_err());
+}
+
+#[test]
+fn test_parse_theme_suit_colors() {
+    let config = parse_config_str(
+        "[unopened_raise.BTN]\nrange = \"AA\"\n\n[theme]\ncard_colors = \"two_color\"\n\n[theme.suit_colors]\nclubs = \"#12843a\"\n",
+    )
+    .unwrap();
+    assert_eq!(config.theme.card_colors, CardColorScheme::TwoColor);
+    assert_eq!(
+        config.theme.suit_color(Suit::Clubs),
+        RgbColor::new(0x12, 0x84, 0x3a)
+    );
+    assert_eq!(
+        config.theme.suit_color(Suit::Diamonds),
+        RgbColor::new(255, 0, 0)
+    );
+
+    let default_theme = parse_config_str("[unopened_raise.BTN]\nrange = \"AA\"\n")
+        .unwrap()
+        .theme;
+    assert_eq!(default_theme.card_colors, CardColorScheme::FourColor);
+    assert_eq!(
+        default_theme.suit_color(Suit::Diamonds),
+        RgbColor::new(0, 0, 255)
+    );
+
+    assert!(
+        parse_config_str(
+            "[unopened_raise.BTN]\nrange = \"AA\"\n\n[theme.suit_colors]\nclubs = \"green\"\n"
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_parse_notes_table() {
+    let config = parse_config_str(
+        "[unopened_raise.BTN]\nrange = \"AA\"\n\n[notes]\nA5s = \"bluff 3-bet candidate\"\n",
+    )
+    .unwrap();
+    assert_eq!(config.note_for(hn("A5s")), Some("bluff 3-bet candidate"));
+    assert_eq!(config.note_for(hn("A5o")), None);
+
+    assert!(
+        parse_config_str("[unopened_raise.BTN]\nrange = \"AA\"\n\n[notes]\nA5x = \"typo\"\n")
+            .is_err()
+    );
+}
+
+#[test]
+fn test_config_json_schema_is_valid_json() {
+    let schema: serde_json::Value = serde_json::from_str(&config_json_schema()).unwrap();
+    let properties = &schema["properties"];
+    for key in ["unopened_raise", "bb_defense", "generic", "theme", "notes"] {
+        assert!(properties.get(key).is_some(), "schema is missing {}", key);
+    }
+    assert_eq!(schema["required"], serde_json::json!(["unopened_raise"]));
+    assert_eq!(
+        properties["unopened_raise"]["propertyNames"]["pattern"],
+        serde_json::json!("^(UTG|UTG1|UTG2|LJ|MP|HJ|CO|BTN|SB)(_[0-9]+(bb)?)?$")
+    );
+    let spot_types = properties["generic"]["properties"]["allowed_spot_types"]["items"]["anyOf"][0]
+        ["enum"]
+        .as_array()
+        .unwrap();
+    assert!(spot_types.contains(&serde_json::json!("BBDefense_CO")));
+    assert!(schema["$defs"]["range"]["description"].is_string());
+}
+
+#[test]
+fn test_parse_range_str_removals() {
+    let range_map = parse_range_str("pairs,-22").unwrap();
+    assert_eq!(range_map.len(), 12);
+    assert!(range_map.hands().all(|hn| hn.hand_type == HandType::Pair));
+    assert!(!range_map.contains(hn("22")));
+
+    // Fields apply left to right: a removal only takes out hands added before it
+    let range_map = parse_range_str("-55-22,pairs:0.5").unwrap();
+    assert_eq!(range_map.len(), 13);
+    assert_eq!(range_map.get(hn("55")), Some(0.5));
+    let range_map = parse_range_str("22+,-55,55").unwrap();
+    assert_eq!(range_map.len(), 13);
+    assert_eq!(range_map.get(hn("55")), Some(1.0));
+    // A removed hand can come back at another frequency
+    let range_map = parse_range_str("22+,-55,55:0.5").unwrap();
+    assert_eq!(range_map.get(hn("55")), Some(0.5));
+
+    let range_map = parse_range_str("A2s+,-A5s-A3s&AKs").unwrap();
+    assert_eq!(range_map.len(), 8);
+    assert!(range_map.contains(hn("A2s")));
+    assert!(!range_map.contains(hn("A4s")));
+
+    assert!(parse_range_str("AA,-KK:0.5").is_err());
+    assert!(parse_range_str("A5s-K2s").is_err());
+}
+
+#[test]
+fn test_parse_range_str_removes_exactly_the_subtracted_hands() {
+    let all_pairs = parse_range_str("22+").unwrap();
+    let gapped = parse_range_str("22+,-55-77").unwrap();
+    let mut removed: Vec<String> = all_pairs
+        .hands()
+        .filter(|&hn| !gapped.contains(hn))
+        .map(|hn| hn.to_string())
+        .collect();
+    removed.sort();
+    assert_eq!(removed, ["55", "66", "77"]);
+    assert_eq!(gapped.len(), 10);
+    assert_eq!(
+        parse_range_str("22+,-55,-66").unwrap(),
+        parse_range_str("77+,44-22").unwrap()
+    );
+
+    // Hands listed at 0 are left alone when nothing removes them
+    let range_map = parse_range_str("AA,72o:0,-KK").unwrap();
+    assert_eq!(range_map.get(hn("72o")), Some(0.0));
+}
+
+#[test]
+fn test_parse_range_str_dash_ranges() {
+    let range_map = parse_range_str("JJ-88").unwrap();
+    assert_eq!(range_map.len(), 4);
+    assert!(range_map.contains(hn("TT")));
+
+    // Either order works
+    assert_eq!(
+        parse_range_str("K2o-K5o").unwrap(),
+        parse_range_str("K5o-K2o").unwrap()
+    );
+    assert_eq!(parse_range_str("K5o-K2o").unwrap().len(), 4);
+}
+
+#[test]
+fn test_parse_range_str_percent_frequencies() {
+    let range_map = parse_range_str("AA, K6s:50%, Q9s:0.25").unwrap();
+    assert_eq!(range_map.get(hn("AA")), Some(1.0));
+    assert_eq!(range_map.get(hn("K6s")), Some(0.5));
+    assert_eq!(range_map.get(hn("Q9s")), Some(0.25));
+
+    assert_eq!(parse_range_str("K6s:0%").unwrap().get(hn("K6s")), Some(0.0));
+    assert_eq!(
+        parse_range_str("K6s:100%").unwrap().get(hn("K6s")),
+        Some(1.0)
+    );
+    assert_eq!(
+        parse_range_str("QQ+&AQs+:12.5%").unwrap().get(hn("AKs")),
+        Some(0.125)
+    );
+
+    let err = parse_range_str("K6s:150%").unwrap_err();
+    assert!(matches!(
+        &err,
+        RangeParseError::FrequencyOutOfRange { token, frequency: 1.5 } if token == "K6s:150%"
+    ));
+    assert!(matches!(
+        parse_range_str("K6s:half%"),
+        Err(RangeParseError::InvalidFrequency { .. })
+    ));
+}
+
+#[test]
+fn test_parse_range_str_bounded_kicker_runs() {
+    let range_map = parse_range_str("A5s-A2s").unwrap();
+    assert_eq!(range_map.len(), 4);
+    for hand in ["A5s", "A4s", "A3s", "A2s"] {
+        assert!(range_map.contains(hn(hand)), "{}", hand);
+    }
+    assert_eq!(range_map, parse_range_str("A2s-A5s").unwrap());
+
+    let range_map = parse_range_str("KQo-K9o").unwrap();
+    assert_eq!(range_map.len(), 4);
+    for hand in ["KQo", "KJo", "KTo", "K9o"] {
+        assert!(range_map.contains(hn(hand)), "{}", hand);
+    }
+    assert_eq!(range_map, parse_range_str("K9o-KQo").unwrap());
+
+    let range_map = parse_range_str("A5s-A2s:0.25").unwrap();
+    assert_eq!(range_map.len(), 4);
+    assert!(range_map.iter().all(|(_, &freq)| freq == 0.25));
+
+    // Both ends share the high card and hand type
+    for mismatched in ["A5s-K2s", "A5s-A2o", "K9o-AKo"] {
+        let err = parse_range_str(mismatched).unwrap_err();
+        assert!(
+            matches!(&err, RangeParseError::InvalidHand { token, .. } if token == mismatched),
+            "{}",
+            mismatched
+        );
+        assert!(err.to_string().contains("same high card"), "{}", err);
+    }
+}
+
+#[test]
+fn test_parse_range_str_bounded_pair_runs() {
+    let range_map = parse_range_str("77-JJ").unwrap();
+    assert_eq!(range_map.len(), 5);
+    for pair in ["77", "88", "99", "TT", "JJ"] {
+        assert_eq!(range_map.get(hn(pair)), Some(1.0), "{}", pair);
+    }
+    assert_eq!(range_map, parse_range_str("JJ-77").unwrap());
+
+    let range_map = parse_range_str("77-JJ:0.5").unwrap();
+    assert_eq!(range_map.len(), 5);
+    assert!(range_map.iter().all(|(_, &freq)| freq == 0.5));
+
+    assert_eq!(
+        parse_range_str("55-55")
+            .unwrap()
+            .hands()
+            .collect::<Vec<_>>(),
+        [hn("55")]
+    );
+
+    for malformed in ["77-", "QQ+&-JJ", "77-JJ-QQ", "77-AKs"] {
+        assert!(
+            matches!(
+                parse_range_str(malformed),
+                Err(RangeParseError::InvalidHand { .. })
+            ),
+            "{}",
+            malformed
+        );
+    }
+    assert!(
+        parse_range_str("77-")
+            .unwrap_err()
+            .to_string()
+            .contains("Missing a hand on one side")
+    );
 }
@@ -0,0 +1,69 @@
+use preflop_trainer_core::{Position, SpotType, TableSize, parse_config_str};
+use std::str::FromStr;
+
+#[test]
+fn test_nine_max_positions_round_trip() {
+    for (key, position, label) in [
+        ("UTG1", Position::UTG1, "UTG+1"),
+        ("UTG2", Position::UTG2, "UTG+2"),
+        ("LJ", Position::LJ, "Lojack"),
+        ("HJ", Position::HJ, "Hijack"),
+    ] {
+        assert_eq!(Position::from_str(key), Ok(position));
+        assert_eq!(position.to_string(), label);
+        assert!(position.is_opener());
+        let open = SpotType::open(position);
+        assert_eq!(open.to_config_string(), format!("Open_{}", key));
+        assert_eq!(SpotType::from_str(&open.to_config_string()), Ok(open));
+    }
+    assert_eq!(Position::from_str("UTG+1"), Ok(Position::UTG1));
+    assert!(!Position::BB.is_opener());
+}
+
+#[test]
+fn test_for_table_size() {
+    assert_eq!(
+        Position::for_table_size(6),
+        [
+            Position::UTG,
+            Position::MP,
+            Position::CO,
+            Position::BTN,
+            Position::SB,
+            Position::BB
+        ]
+    );
+    let nine_max = Position::for_table_size(9);
+    assert_eq!(nine_max.len(), 9);
+    assert!(!nine_max.contains(&Position::MP));
+    // Preflop order
+    assert!(nine_max.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn test_nine_max_config_loads() {
+    let config = parse_config_str(
+        "[generic]\ntable_size = 9\n\n[unopened_raise.UTG1]\nrange = \"77+,AJs+\"\n\n[bb_defense.HJ]\ncall_range = \"22-JJ\"\nraise_range = \"QQ+\"\n",
+    )
+    .unwrap();
+    assert_eq!(config.table_size, TableSize::NineMax);
+    assert_eq!(config.unopened_raise_ranges[&Position::UTG1].len(), 11);
+    // 8 opens and 8 BB defenses
+    assert_eq!(config.allowed_spot_types.len(), 16);
+    assert!(
+        config
+            .allowed_spot_types
+            .contains(&SpotType::bb_defense(Position::HJ))
+    );
+    assert!(
+        !config
+            .allowed_spot_types
+            .contains(&SpotType::open(Position::MP))
+    );
+
+    let six_max = parse_config_str("[unopened_raise.CO]\nrange = \"AA\"\n").unwrap();
+    assert_eq!(six_max.table_size, TableSize::SixMax);
+    assert_eq!(six_max.allowed_spot_types, SpotType::standard_spot_types());
+
+    assert!(parse_config_str("[generic]\ntable_size = 8\n").is_err());
+}
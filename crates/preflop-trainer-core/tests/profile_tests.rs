@@ -0,0 +1,71 @@
+use preflop_trainer_core::{Game, HandNotation, load_profiles};
+use std::collections::HashSet;
+
+const CASH: &str = r#"
+[unopened_raise.BTN]
+range = "AA,KK,QQ"
+
+[generic]
+quiz_folds = false
+"#;
+
+const MTT: &str = r#"
+[unopened_raise.BTN]
+range = "76s,65s"
+
+[generic]
+quiz_folds = false
+"#;
+
+// The notations of 100 served spots
+fn served_notations(game: &mut Game) -> HashSet<HandNotation> {
+    (0..100)
+        .map(|_| HandNotation::from_hand(game.generate_random_spot().unwrap().1))
+        .collect()
+}
+
+#[test]
+fn test_profiles_load_by_file_name_and_switch_live() {
+    let dir = std::env::temp_dir().join(format!("preflop_trainer_profiles_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("cash_100bb.toml"), CASH).unwrap();
+    std::fs::write(dir.join("mtt_40bb.toml"), MTT).unwrap();
+    std::fs::write(dir.join("notes.txt"), "not a profile").unwrap();
+    let profiles = load_profiles(&dir);
+    std::fs::remove_dir_all(&dir).ok();
+    let mut profiles = profiles.unwrap();
+
+    let mut names: Vec<&String> = profiles.keys().collect();
+    names.sort();
+    assert_eq!(names, ["cash_100bb", "mtt_40bb"]);
+
+    let mut game = Game::new_with_seed(profiles.remove("cash_100bb").unwrap(), 5);
+    let cash_hands: HashSet<HandNotation> = ["AA", "KK", "QQ"]
+        .iter()
+        .map(|s| s.parse().unwrap())
+        .collect();
+    assert_eq!(served_notations(&mut game), cash_hands);
+
+    game.set_config(profiles.remove("mtt_40bb").unwrap());
+    let mtt_hands: HashSet<HandNotation> =
+        ["76s", "65s"].iter().map(|s| s.parse().unwrap()).collect();
+    assert_eq!(served_notations(&mut game), mtt_hands);
+}
+
+#[test]
+fn test_bad_profile_fails_to_load() {
+    let dir = std::env::temp_dir().join(format!(
+        "preflop_trainer_bad_profiles_{}",
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("broken.toml"),
+        "[unopened_raise.XX]\nrange = \"AA\"\n",
+    )
+    .unwrap();
+    let result = load_profiles(&dir);
+    std::fs::remove_dir_all(&dir).ok();
+    assert!(result.is_err());
+    assert!(load_profiles(&dir).is_err());
+}
@@ -0,0 +1,71 @@
+use preflop_trainer_core::{
+    AnswerResult, Hand, Position, SpotType, StackDepth, UserAction, check_answer, parse_config_str,
+};
+use std::str::FromStr;
+
+#[test]
+fn test_push_fold_needs_a_first_in_seat_and_a_stack() {
+    let btn_10 = SpotType::from_str("PushFold_BTN_10").unwrap();
+    assert_eq!(btn_10.stack_depth(), Some(StackDepth(10)));
+    assert!(!btn_10.is_facing_raise());
+
+    assert!(SpotType::from_str("PushFold_BB_10").is_err());
+    assert!(SpotType::from_str("PushFold_BTN_0").is_err());
+    assert!(SpotType::from_str("PushFold_BTN_300").is_err());
+    assert!(
+        parse_config_str(
+            "[unopened_raise.BTN]\nrange = \"AA\"\n\n[push_fold.BTN]\nrange = \"AA\"\n"
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_folding_a_pure_shove_is_wrong() {
+    let config = parse_config_str(
+        "[unopened_raise.BTN]\nrange = \"22+,A2s+\"\n\n[push_fold.BTN_10]\nrange = \"22+,A2s+,K9o+\"\n",
+    )
+    .unwrap();
+    let btn_10 = SpotType::from_str("PushFold_BTN_10").unwrap();
+    let hand = "Ks9h".parse::<Hand>().unwrap();
+    for rng in [0, 50, 99] {
+        assert_eq!(
+            check_answer(&config, btn_10, hand, UserAction::Fold, rng),
+            AnswerResult::Wrong
+        );
+        assert_eq!(
+            check_answer(&config, btn_10, hand, UserAction::Raise, rng),
+            AnswerResult::Correct
+        );
+        // Nothing to call or limp into
+        assert_eq!(
+            check_answer(&config, btn_10, hand, UserAction::Call, rng),
+            AnswerResult::Wrong
+        );
+    }
+
+    // K9o isn't in the deeper BTN open range
+    let btn = SpotType::open(Position::BTN);
+    assert_eq!(
+        check_answer(&config, btn, hand, UserAction::Fold, 0),
+        AnswerResult::Correct
+    );
+}
+
+#[test]
+fn test_mixed_shove_uses_the_rng() {
+    let config = parse_config_str(
+        "[unopened_raise.BTN]\nrange = \"22+\"\n\n[push_fold.BTN_10]\nrange = \"22+,QTo:0.5\"\n",
+    )
+    .unwrap();
+    let btn_10 = SpotType::from_str("PushFold_BTN_10").unwrap();
+    let hand = "QsTh".parse::<Hand>().unwrap();
+    assert_eq!(
+        check_answer(&config, btn_10, hand, UserAction::Raise, 10),
+        AnswerResult::Correct
+    );
+    assert_eq!(
+        check_answer(&config, btn_10, hand, UserAction::Raise, 90),
+        AnswerResult::FrequencyMistake
+    );
+}
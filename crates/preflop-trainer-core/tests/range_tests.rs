@@ -0,0 +1,629 @@
+use preflop_trainer_core::{
+    Card, Hand, HandNotation, RangeParseError, RangeTable, Rank, Suit, blocker_feedback,
+    get_all_possible_hand_notations, get_all_possible_hand_notations_grid_order,
+    hand_strength_rank, hands_by_strength, import_range_grid_csv, import_range_piosolver,
+    normalize_range_str, parse_range_str, percent_to_range, range_combo_count, remaining_combos,
+    tighten_range, to_range_string, widen_range,
+};
+use std::collections::HashSet;
+use std::str::FromStr;
+
+// Helper to create a HandNotation for tests
+fn hn(s: &str) -> HandNotation {
+    HandNotation::from_str(s).unwrap()
+}
+
+#[test]
+fn test_hand_notation_display() {
+    assert_eq!(hn("AA").to_string(), "AA");
+    assert_eq!(hn("KAs").to_string(), "AKs");
+    assert_eq!(hn("T9o").to_string(), "T9o");
+}
+
+#[test]
+fn test_to_range_string_orders_hands_and_keeps_frequencies() {
+    let range = parse_range_str("AKo,K6s:0.5,22,AA,AKs").unwrap();
+    assert_eq!(to_range_string(&range), "AA,22,AKs,AKo,K6s:0.5");
+}
+
+#[test]
+fn test_to_range_string_round_trips() {
+    let range = parse_range_str("22+,A2s+,KTo+:0.25,T9s,54s:0.75").unwrap();
+    let reparsed = parse_range_str(&to_range_string(&range)).unwrap();
+    assert_eq!(reparsed, range);
+}
+
+#[test]
+fn test_to_range_string_empty() {
+    let range = parse_range_str("").unwrap();
+    assert_eq!(to_range_string(&range), "");
+}
+
+#[test]
+fn test_combo_count() {
+    assert_eq!(hn("AA").combo_count(), 6);
+    assert_eq!(hn("AKs").combo_count(), 4);
+    assert_eq!(hn("AKo").combo_count(), 12);
+
+    let total: u32 = get_all_possible_hand_notations()
+        .iter()
+        .map(|hn| hn.combo_count() as u32)
+        .sum();
+    assert_eq!(total, 1326);
+}
+
+#[test]
+fn test_hands_by_strength_covers_every_hand_once() {
+    let ordered = hands_by_strength();
+    assert_eq!(ordered.len(), 169);
+    let unique: HashSet<HandNotation> = ordered.iter().copied().collect();
+    assert_eq!(unique.len(), 169);
+
+    assert_eq!(ordered[0], hn("AA"));
+    assert_eq!(ordered[168], hn("32o"));
+    assert_eq!(hand_strength_rank(hn("AA")), 0);
+    assert!(hand_strength_rank(hn("AKs")) < hand_strength_rank(hn("AKo")));
+    assert!(hand_strength_rank(hn("KK")) < hand_strength_rank(hn("72o")));
+}
+
+#[test]
+fn test_range_combo_count_uses_frequencies() {
+    let range = parse_range_str("AA,AKs,AKo:0.5").unwrap();
+    assert_eq!(range_combo_count(&range), 6.0 + 4.0 + 6.0);
+}
+
+#[test]
+fn test_tighten_range_reduces_combos_by_roughly_the_fraction() {
+    let range = parse_range_str("22+,A2s+,K9s+,QTs+,JTs,T9s,ATo+,KJo+").unwrap();
+    let total = range_combo_count(&range);
+
+    let tightened = tighten_range(&range, 0.1);
+    let removed_fraction = 1.0 - range_combo_count(&tightened) / total;
+
+    // Whole hands are removed, so we may overshoot by at most one offsuit hand
+    assert!(
+        (0.1..=0.1 + 12.0 / total).contains(&removed_fraction),
+        "Removed {:.3} of the range's combos",
+        removed_fraction
+    );
+}
+
+#[test]
+fn test_tighten_range_removes_the_weakest_hands() {
+    let range = parse_range_str("AA,KK,AKs,T9s,54s,32o").unwrap();
+    let tightened = tighten_range(&range, 0.15);
+
+    assert!(tightened.contains(hn("AA")));
+    assert!(tightened.contains(hn("AKs")));
+    assert!(!tightened.contains(hn("32o")));
+
+    let weakest_kept = tightened.hands().map(hand_strength_rank).max().unwrap();
+    for removed in range.hands().filter(|&h| !tightened.contains(h)) {
+        assert!(hand_strength_rank(removed) > weakest_kept);
+    }
+}
+
+#[test]
+fn test_widen_range_adds_the_strongest_missing_hands() {
+    let range = parse_range_str("AA,KK,QQ,AKs").unwrap();
+    let total = range_combo_count(&range);
+
+    let widened = widen_range(&range, 0.25);
+
+    // 22 combos, so a quarter more means adding 5.5 combos: JJ (6)
+    assert!(range_combo_count(&widened) >= total * 1.25);
+    assert_eq!(widened.get(hn("JJ")), Some(1.0));
+    assert_eq!(widened.len(), range.len() + 1);
+}
+
+#[test]
+fn test_percent_to_range_takes_the_top_combos() {
+    let range = percent_to_range(15.0);
+    // 15% of 1326 combos is 198.9
+    let combos = range_combo_count(&range);
+    assert!((190.0..=208.0).contains(&combos), "{} combos", combos);
+    assert!(range.iter().all(|(_, &frequency)| frequency == 1.0));
+
+    // Every hand in the range is stronger than every hand left out
+    let weakest_in = range.hands().map(hand_strength_rank).max().unwrap();
+    assert_eq!(weakest_in, range.len() - 1);
+
+    let range_str = to_range_string(&range);
+    assert_eq!(parse_range_str(&range_str).unwrap(), range);
+
+    assert!(percent_to_range(0.0).is_empty());
+    assert_eq!(percent_to_range(100.0).len(), 169);
+}
+
+#[test]
+fn test_top_percent_range_token() {
+    let range = parse_range_str("top:100%").unwrap();
+    assert_eq!(range.len(), 169);
+    assert!(range.iter().all(|(_, &frequency)| frequency == 1.0));
+
+    assert_eq!(parse_range_str("top:15%").unwrap(), percent_to_range(15.0));
+    assert_eq!(parse_range_str("top:15").unwrap(), percent_to_range(15.0));
+
+    // Mixes with other fields; hands already in the top 5% may repeat at full frequency
+    let range = parse_range_str("top:5%,AA,K9s:0.5").unwrap();
+    assert_eq!(range.len(), percent_to_range(5.0).len() + 1);
+    assert_eq!(range.get(hn("K9s")), Some(0.5));
+    let range = parse_range_str("top:10%,-AA").unwrap();
+    assert!(!range.contains(hn("AA")));
+
+    for bad in ["top:lots", "top:120%", "top:-5%", "top:"] {
+        assert!(
+            matches!(
+                parse_range_str(bad),
+                Err(RangeParseError::InvalidPercent { .. })
+            ),
+            "{}",
+            bad
+        );
+    }
+}
+
+fn hand(rank1: Rank, suit1: Suit, rank2: Rank, suit2: Suit) -> Hand {
+    Hand {
+        card1: Card {
+            rank: rank1,
+            suit: suit1,
+        },
+        card2: Card {
+            rank: rank2,
+            suit: suit2,
+        },
+    }
+}
+
+#[test]
+fn test_combos_match_notation() {
+    for notation in get_all_possible_hand_notations() {
+        let combos = notation.combos();
+        assert_eq!(combos.len(), notation.combo_count() as usize);
+        assert!(
+            combos
+                .iter()
+                .all(|&h| HandNotation::from_hand(h) == notation)
+        );
+    }
+}
+
+#[test]
+fn test_combos_counts_and_pocket_aces() {
+    assert_eq!(hn("AA").combos().len(), 6);
+    assert_eq!(hn("AKs").combos().len(), 4);
+    assert_eq!(hn("AKo").combos().len(), 12);
+    assert_eq!(
+        (
+            hn("AA").combo_count(),
+            hn("AKs").combo_count(),
+            hn("AKo").combo_count()
+        ),
+        (6, 4, 12)
+    );
+
+    let aces = hn("AA").combos();
+    let unordered: HashSet<(Suit, Suit)> = aces
+        .iter()
+        .map(|&Hand { card1, card2 }| {
+            assert_eq!((card1.rank, card2.rank), (Rank::Ace, Rank::Ace));
+            assert_ne!(card1.suit, card2.suit);
+            if (card1.suit as u8) < (card2.suit as u8) {
+                (card1.suit, card2.suit)
+            } else {
+                (card2.suit, card1.suit)
+            }
+        })
+        .collect();
+    assert_eq!(unordered.len(), 6);
+}
+
+#[test]
+fn test_holding_an_ace_blocks_villain_ak() {
+    let villain = parse_range_str("AKs,AKo").unwrap();
+    let hero_no_blocker = hand(Rank::Queen, Suit::Spades, Rank::Jack, Suit::Hearts);
+    let hero_ace = hand(Rank::Ace, Suit::Spades, Rank::Two, Suit::Hearts);
+    let hero_ace_king = hand(Rank::Ace, Suit::Spades, Rank::King, Suit::Hearts);
+
+    assert_eq!(remaining_combos(hero_no_blocker, &villain), 16);
+    // One ace leaves 3 of the 4 aces: 3 AKs + 9 AKo
+    assert_eq!(remaining_combos(hero_ace, &villain), 12);
+    // An ace and a king leave 3 x 3 combos, of which 2 are suited
+    assert_eq!(remaining_combos(hero_ace_king, &villain), 9);
+
+    assert_eq!(blocker_feedback(hero_no_blocker, &villain), None);
+    assert_eq!(
+        blocker_feedback(hero_ace, &villain).unwrap(),
+        "You block 1 of villain's 4 AKs combos, 3 of 12 AKo combos (12 of 16 combos remain)"
+    );
+}
+
+#[test]
+fn test_normalize_range_str_collapses_and_sorts() {
+    assert_eq!(normalize_range_str("KK,22+,AA").unwrap(), "22+");
+    assert_eq!(
+        normalize_range_str("AKo, TT+ ,A9s+,KQs:0.5,AKo").unwrap(),
+        "TT+,A9s+,AKo,KQs:0.5"
+    );
+    // A run only collapses while hands share a frequency
+    assert_eq!(
+        normalize_range_str("QQ+:0.5,JJ,TT").unwrap(),
+        "QQ+:0.5,JJ,TT"
+    );
+    assert!(matches!(
+        normalize_range_str("AXs"),
+        Err(RangeParseError::InvalidHand { token, .. }) if token == "AXs"
+    ));
+    assert!(matches!(
+        normalize_range_str("AA:1.5"),
+        Err(RangeParseError::FrequencyOutOfRange { .. })
+    ));
+    assert!(matches!(
+        normalize_range_str("AA,AA:0.5"),
+        Err(RangeParseError::DuplicateHand { .. })
+    ));
+    assert!(matches!(
+        normalize_range_str("AA,,KK"),
+        Err(RangeParseError::EmptyToken { .. })
+    ));
+}
+
+#[test]
+fn test_to_range_string_compact_round_trips_every_run() {
+    for notation in get_all_possible_hand_notations() {
+        let range = parse_range_str(&format!("{}+", notation)).unwrap();
+        assert_eq!(parse_range_str(&to_range_string(&range)).unwrap(), range);
+    }
+}
+
+#[test]
+fn test_range_table_lookups_default_to_zero() {
+    let range = RangeTable::from_str("TT+,AKs:0.5,72o:0").unwrap();
+    assert_eq!(range.frequency(hn("AA")), 1.0);
+    assert_eq!(range.frequency(hn("AKs")), 0.5);
+    assert_eq!(range.frequency(hn("KQs")), 0.0);
+    assert!(range.contains(hn("AKs")));
+    // Listed at 0 is the same as not listed
+    assert!(!range.contains(hn("72o")));
+    assert!(!range.contains(hn("KQs")));
+    // 5 pairs of 6 combos, plus half of AKs's 4
+    assert_eq!(range.combo_count(), 32.0);
+
+    assert_eq!(range.to_string(), "TT+,AKs:0.5,72o:0");
+    assert_eq!(RangeTable::from_str(&range.to_string()).unwrap(), range);
+    assert!(matches!(
+        RangeTable::from_str("AZs"),
+        Err(RangeParseError::InvalidHand { .. })
+    ));
+}
+
+#[test]
+fn test_range_table_insert_and_iter() {
+    let mut range = RangeTable::default();
+    assert!(range.is_empty());
+    assert_eq!(range.insert(hn("AA"), 1.0), None);
+    assert_eq!(range.insert(hn("AKs"), 0.25), None);
+    assert_eq!(range.insert(hn("AKs"), 0.5), Some(0.25));
+    assert_eq!(range.len(), 2);
+    assert!(!range.is_empty());
+
+    let mut listed: Vec<(String, f32)> = range
+        .iter()
+        .map(|(hn, &frequency)| (hn.to_string(), frequency))
+        .collect();
+    listed.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(listed, [("AA".to_string(), 1.0), ("AKs".to_string(), 0.5)]);
+    assert_eq!(range, RangeTable::from_str("AA,AKs:0.5").unwrap());
+}
+
+#[test]
+fn test_range_table_difference_removes_hands() {
+    let range = RangeTable::from_str("22+").unwrap();
+    let middle_pairs = RangeTable::from_str("55-77").unwrap();
+    let rest = range.difference(&middle_pairs);
+    let expected: HashSet<HandNotation> =
+        ["22", "33", "44", "88", "99", "TT", "JJ", "QQ", "KK", "AA"]
+            .iter()
+            .map(|s| hn(s))
+            .collect();
+    assert_eq!(rest.hands().collect::<HashSet<_>>(), expected);
+    assert_eq!(rest.to_string(), "88+,44,33,22");
+
+    // Frequencies subtract, and a hand taken down to 0 drops out
+    let mixed = RangeTable::from_str("AKs,AQs:0.5").unwrap();
+    let taken = RangeTable::from_str("AKs:0.25,AQs").unwrap();
+    assert_eq!(mixed.difference(&taken).to_string(), "AKs:0.75");
+}
+
+#[test]
+fn test_range_table_union_and_intersection_combine_frequencies() {
+    let call = RangeTable::from_str("99,AKs:0.5,AQs:0.75").unwrap();
+    let raise = RangeTable::from_str("TT+,AKs:0.5,AQs:0.5").unwrap();
+    let played = call.union(&raise);
+    assert_eq!(played.to_string(), "99+,AQs+");
+    assert_eq!(call.intersection(&raise).to_string(), "AQs+:0.5");
+    assert!(
+        call.intersection(&RangeTable::from_str("22").unwrap())
+            .is_empty()
+    );
+
+    // The fold range is everything that isn't called or raised
+    let fold = RangeTable::all().difference(&played);
+    assert_eq!(fold.len(), 169 - 8);
+    assert!(!fold.contains(hn("AKs")));
+    assert_eq!(fold.frequency(hn("88")), 1.0);
+    assert_eq!(
+        RangeTable::from_str("AQs:0.5")
+            .unwrap()
+            .union(&RangeTable::from_str("AQs:0.25").unwrap())
+            .frequency(hn("AQs")),
+        0.75
+    );
+}
+
+#[test]
+fn test_range_grid_string_snapshot() {
+    let grid = RangeTable::from_str("AA,AKs,AKo").unwrap().to_grid_string();
+    let lines: Vec<&str> = grid.lines().collect();
+    assert_eq!(lines.len(), 13);
+    assert_eq!(
+        lines[0],
+        "AA  100 AKs 100 AQs   . AJs   . ATs   . A9s   . A8s   . A7s   . A6s   . A5s   . A4s   . A3s   . A2s   ."
+    );
+    assert_eq!(
+        lines[1],
+        "AKo 100 KK    . KQs   . KJs   . KTs   . K9s   . K8s   . K7s   . K6s   . K5s   . K4s   . K3s   . K2s   ."
+    );
+    assert_eq!(
+        lines[12],
+        "A2o   . K2o   . Q2o   . J2o   . T2o   . 92o   . 82o   . 72o   . 62o   . 52o   . 42o   . 32o   . 22    ."
+    );
+    assert!(
+        RangeTable::from_str("KQs:0.5")
+            .unwrap()
+            .to_grid_string()
+            .contains("KQs  50")
+    );
+}
+
+#[test]
+fn test_range_grid_string_with_styles_each_cell() {
+    let range = RangeTable::from_str("AA,KQs:0.5").unwrap();
+    let styled = range.to_grid_string_with(|hand_notation, frequency, cell| {
+        if frequency > 0.0 {
+            format!("[{}|{}]", hand_notation, cell)
+        } else {
+            cell
+        }
+    });
+    assert!(styled.starts_with("[AA|AA  100] AKs   ."));
+    assert!(styled.contains("[KQs|KQs  50]"));
+    assert_eq!(styled.matches('[').count(), 2);
+    assert_eq!(
+        range.to_grid_string_with(|_, _, cell| cell),
+        range.to_grid_string()
+    );
+}
+
+#[test]
+fn test_grid_positions_round_trip() {
+    assert_eq!(hn("AA").grid_position(), (0, 0));
+    assert_eq!(hn("AKs").grid_position(), (0, 1));
+    assert_eq!(hn("AKo").grid_position(), (1, 0));
+    assert_eq!(hn("22").grid_position(), (12, 12));
+    assert_eq!(hn("32o").grid_position(), (12, 11));
+    assert_eq!(HandNotation::from_grid_position(0, 1), hn("AKs"));
+    assert_eq!(HandNotation::from_grid_position(1, 0), hn("AKo"));
+
+    let mut cells = HashSet::new();
+    for row in 0..13 {
+        for col in 0..13 {
+            let hand_notation = HandNotation::from_grid_position(row, col);
+            assert_eq!(hand_notation.grid_position(), (row, col));
+            cells.insert(hand_notation);
+        }
+    }
+    assert_eq!(cells.len(), 169);
+}
+
+#[test]
+fn test_grid_order_lists_rows_left_to_right() {
+    let notations = get_all_possible_hand_notations_grid_order();
+    assert_eq!(notations.len(), 169);
+    let first_row: Vec<String> = notations[..13].iter().map(ToString::to_string).collect();
+    assert_eq!(
+        first_row,
+        [
+            "AA", "AKs", "AQs", "AJs", "ATs", "A9s", "A8s", "A7s", "A6s", "A5s", "A4s", "A3s",
+            "A2s"
+        ]
+    );
+    assert_eq!(notations[13], hn("AKo"));
+    assert_eq!(notations[168], hn("22"));
+    // The same hands as the original list, just reordered
+    let grid_order: HashSet<HandNotation> = notations.into_iter().collect();
+    let original: HashSet<HandNotation> = get_all_possible_hand_notations().into_iter().collect();
+    assert_eq!(grid_order, original);
+}
+
+#[test]
+fn test_range_grid_string_parses_back() {
+    let range = RangeTable::from_str("TT+,AJs+,KQs:0.5,AQo+,72o:0").unwrap();
+    let grid = range.to_grid_string();
+    let parsed = RangeTable::from_grid_str(&grid).unwrap();
+    assert_eq!(parsed, range);
+    assert_eq!(parsed.to_grid_string(), grid);
+}
+
+#[test]
+fn test_range_grid_of_bare_frequencies() {
+    // Spaces between numbers, and blank tab-separated cells, as copied from a spreadsheet
+    let mut rows = vec![vec!["0"; 13]; 13];
+    rows[0][0] = "100";
+    rows[0][1] = "50%";
+    rows[1][0] = "25";
+    let spaced: Vec<String> = rows.iter().map(|row| row.join(" ")).collect();
+    let range = RangeTable::from_grid_str(&spaced.join("\n")).unwrap();
+    assert_eq!(range.len(), 169);
+    assert_eq!(range.frequency(hn("AA")), 1.0);
+    assert_eq!(range.frequency(hn("AKs")), 0.5);
+    assert_eq!(range.frequency(hn("AKo")), 0.25);
+
+    let tabbed: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|&cell| if cell == "0" { "" } else { cell })
+                .collect::<Vec<_>>()
+                .join("\t")
+        })
+        .collect();
+    let range = RangeTable::from_grid_str(&format!("\n{}\n\n", tabbed.join("\n"))).unwrap();
+    assert_eq!(range.to_string(), "AA,AKs:0.5,AKo:0.25");
+}
+
+#[test]
+fn test_malformed_range_grids_are_rejected() {
+    let grid = RangeTable::from_str("AA").unwrap().to_grid_string();
+    let lines: Vec<&str> = grid.lines().collect();
+    assert_eq!(
+        RangeTable::from_grid_str(&lines[..12].join("\n")),
+        Err(RangeParseError::GridRowCount { rows: 12 })
+    );
+
+    let short_row = grid.replacen(" A2s   .", "", 1);
+    let err = RangeTable::from_grid_str(&short_row).unwrap_err();
+    assert!(matches!(
+        err,
+        RangeParseError::InvalidGridRow { row: 1, .. }
+    ));
+    assert_eq!(err.to_string(), "Grid row 1: expected 13 cells, found 12");
+
+    let missing_frequency = grid.replacen("AKs   .", "AKs", 1);
+    assert!(matches!(
+        RangeTable::from_grid_str(&missing_frequency),
+        Err(RangeParseError::InvalidGridRow { row: 1, .. })
+    ));
+
+    let mislabelled = grid.replacen("AKs", "AKo", 1);
+    assert!(
+        RangeTable::from_grid_str(&mislabelled)
+            .unwrap_err()
+            .to_string()
+            .contains("cell 2 is labelled AKo, but that position is AKs")
+    );
+    assert!(matches!(
+        RangeTable::from_grid_str(&grid.replacen("AA  100", "AA  150", 1)),
+        Err(RangeParseError::FrequencyOutOfRange { .. })
+    ));
+    assert!(matches!(
+        RangeTable::from_grid_str(&grid.replacen("AA  100", "AA  lots", 1)),
+        Err(RangeParseError::InvalidFrequency { .. })
+    ));
+}
+
+// A PioSOLVER range export: notations and explicit combos, some weighted
+const PIOSOLVER_EXPORT: &str = "AA:1.0;KK:1.0;QQ:0.5;AKs:0.75;AKo;\
+AhQh:1.0;AdQd:1.0;AsQs:0.5;\
+KsQh:0.25;";
+
+#[test]
+fn test_import_range_piosolver_export() {
+    let range = import_range_piosolver(PIOSOLVER_EXPORT).unwrap();
+    assert_eq!(range.len(), 7);
+    assert_eq!(range.frequency(hn("AA")), 1.0);
+    assert_eq!(range.frequency(hn("QQ")), 0.5);
+    assert_eq!(range.frequency(hn("AKs")), 0.75);
+    assert_eq!(range.frequency(hn("AKo")), 1.0);
+    // 2.5 of AQs's 4 combos and a quarter of one of KQo's 12
+    assert_eq!(range.frequency(hn("AQs")), 0.625);
+    assert_eq!(range.frequency(hn("KQo")), 0.25 / 12.0);
+
+    // GTO+ style: one entry per line, commas also accepted
+    let lines = import_range_piosolver("AA: 1\r\nKhAh:1\nAhKh,QQ:50%\n\n").unwrap();
+    assert_eq!(lines.to_string(), "AA,QQ:0.5,AKs:0.25");
+}
+
+#[test]
+fn test_import_range_piosolver_rejects_bad_entries() {
+    assert!(matches!(
+        import_range_piosolver("AA;AKx:1"),
+        Err(RangeParseError::InvalidHand { .. })
+    ));
+    assert!(matches!(
+        import_range_piosolver("AA:1.5"),
+        Err(RangeParseError::FrequencyOutOfRange { .. })
+    ));
+    assert!(matches!(
+        import_range_piosolver("AA:1;AA:0.5"),
+        Err(RangeParseError::DuplicateHand { .. })
+    ));
+    let err = import_range_piosolver("AKs;AhKh").unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("AKs is listed both as a hand and as combos")
+    );
+    assert!(matches!(
+        import_range_piosolver("AhKh:1;KhAh:0.5"),
+        Err(RangeParseError::InvalidHand { .. })
+    ));
+}
+
+// The grid as Flopzilla writes it: a percentage per cell, blank when not listed
+fn grid_csv(range: &RangeTable) -> String {
+    get_all_possible_hand_notations_grid_order()
+        .chunks(13)
+        .map(|row| {
+            row.iter()
+                .map(|&hand| match range.get(hand) {
+                    Some(frequency) => format!("{}", frequency * 100.0),
+                    None => String::new(),
+                })
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_import_range_grid_csv_round_trips() {
+    let range = RangeTable::from_str("TT+,AJs+,KQs:0.5,AQo+,72o:0").unwrap();
+    let csv = grid_csv(&range);
+    assert!(csv.starts_with("100,100,100,100,,,,,,,,,"));
+    let imported = import_range_grid_csv(&csv).unwrap();
+    assert_eq!(imported, range);
+    assert_eq!(imported.to_grid_string(), range.to_grid_string());
+
+    // Quoted cells, CRLF line endings and a trailing blank line
+    let quoted = csv.replace("100", "\"100\"").replace('\n', "\r\n") + "\r\n";
+    assert_eq!(import_range_grid_csv(&quoted).unwrap(), range);
+    // A UTF-8 byte order mark, as Excel writes
+    let with_bom = format!("\u{feff}{}", quoted);
+    assert_eq!(import_range_grid_csv(&with_bom).unwrap(), range);
+    assert_eq!(
+        import_range_grid_csv(&with_bom).unwrap(),
+        RangeTable::from_grid_str(&csv).unwrap()
+    );
+}
+
+#[test]
+fn test_import_range_grid_csv_validates_shape() {
+    let csv = grid_csv(&RangeTable::from_str("AA").unwrap());
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        import_range_grid_csv(&lines[..12].join("\n")),
+        Err(RangeParseError::GridRowCount { rows: 12 })
+    );
+    let err = import_range_grid_csv(&csv.replacen(",", "", 1)).unwrap_err();
+    assert_eq!(err.to_string(), "Grid row 1: expected 13 cells, found 12");
+    assert!(matches!(
+        import_range_grid_csv(&csv.replacen("100", "101", 1)),
+        Err(RangeParseError::FrequencyOutOfRange { .. })
+    ));
+    assert!(matches!(
+        import_range_grid_csv(&csv.replacen("100", "AA", 1)),
+        Err(RangeParseError::InvalidFrequency { .. })
+    ));
+}
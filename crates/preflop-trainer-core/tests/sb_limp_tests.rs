@@ -0,0 +1,126 @@
+use preflop_trainer_core::{
+    AnswerResult, Hand, Position, SessionStats, SpotType, UserAction, check_answer,
+    get_sb_action_frequencies, parse_config_str, recommended_play,
+};
+use std::str::FromStr;
+
+#[test]
+fn test_sb_limp_spot_limps_from_the_small_blind_only() {
+    assert_eq!(SpotType::SbLimp.passive_action(), UserAction::Limp);
+    assert!(!SpotType::SbLimp.is_facing_raise());
+    assert!(SpotType::from_str("Open_BTN_Limp").is_err());
+}
+
+#[test]
+fn test_sb_limp_table_replaces_the_sb_open_by_default() {
+    let config = parse_config_str(
+        "[unopened_raise.SB]\nrange = \"AA,KK\"\n\n[sb_limp]\nrange = \"K9o:0.3,T8s\"\n",
+    )
+    .unwrap();
+    assert_eq!(config.sb_limp_ranges.len(), 2);
+    assert!(config.allowed_spot_types.contains(&SpotType::SbLimp));
+    assert!(
+        !config
+            .allowed_spot_types
+            .contains(&SpotType::open(Position::SB))
+    );
+
+    let without_limps = parse_config_str("[unopened_raise.SB]\nrange = \"AA\"\n").unwrap();
+    assert!(!without_limps.allowed_spot_types.contains(&SpotType::SbLimp));
+}
+
+#[test]
+fn test_raise_limp_fold_stacked_frequencies() {
+    let config = parse_config_str(
+        "[unopened_raise.SB]\nrange = \"AA,KK,K9o:0.4\"\n\n[sb_limp]\nrange = \"K9o:0.3,T8s\"\n",
+    )
+    .unwrap();
+    let hand = "Ks9h".parse::<Hand>().unwrap();
+    let (raise, limp, fold) = get_sb_action_frequencies(&config, hand);
+    assert_eq!((raise, limp), (0.4, 0.3));
+    assert!((fold - 0.3).abs() < 1e-6);
+
+    // 0-39 raise, 40-69 limp, 70-99 fold
+    for (rng, correct) in [
+        (0, UserAction::Raise),
+        (39, UserAction::Raise),
+        (40, UserAction::Limp),
+        (69, UserAction::Limp),
+        (70, UserAction::Fold),
+        (99, UserAction::Fold),
+    ] {
+        assert_eq!(
+            check_answer(&config, SpotType::SbLimp, hand, correct, rng),
+            AnswerResult::Correct,
+            "rng {}",
+            rng
+        );
+        for other in [UserAction::Raise, UserAction::Limp, UserAction::Fold] {
+            if other != correct {
+                assert_eq!(
+                    check_answer(&config, SpotType::SbLimp, hand, other, rng),
+                    AnswerResult::FrequencyMistake,
+                    "rng {} {:?}",
+                    rng,
+                    other
+                );
+            }
+        }
+        // There's nothing to call in an unopened pot
+        assert_eq!(
+            check_answer(&config, SpotType::SbLimp, hand, UserAction::Call, rng),
+            AnswerResult::Wrong
+        );
+    }
+    assert_eq!(
+        recommended_play(&config, SpotType::SbLimp, hand),
+        "Raise 40%, Limp 30%, Fold 30%"
+    );
+}
+
+#[test]
+fn test_limp_is_wrong_outside_the_sb_limp_spot() {
+    let config = parse_config_str(
+        "[unopened_raise.BTN]\nrange = \"AA,K9o:0.5\"\n\n[sb_limp]\nrange = \"K9o:0.3\"\n",
+    )
+    .unwrap();
+    let hand = "Ks9h".parse::<Hand>().unwrap();
+    let btn_open = SpotType::open(Position::BTN);
+    for rng in [0, 99] {
+        assert_eq!(
+            check_answer(&config, btn_open, hand, UserAction::Limp, rng),
+            AnswerResult::Wrong
+        );
+    }
+}
+
+#[test]
+fn test_confusion_report_adds_limp_once_limps_are_recorded() {
+    let mut stats = SessionStats::default();
+    let hand = "Ks9h".parse::<Hand>().unwrap();
+    stats.record(
+        SpotType::open(Position::BTN),
+        hand,
+        UserAction::Raise,
+        UserAction::Raise,
+        AnswerResult::Correct,
+        20,
+    );
+    assert!(!stats.confusion_report()[0].contains("Limp"));
+
+    stats.record(
+        SpotType::SbLimp,
+        hand,
+        UserAction::Limp,
+        UserAction::Fold,
+        AnswerResult::FrequencyMistake,
+        55,
+    );
+    let report = stats.confusion_report();
+    assert_eq!(report.len(), 5);
+    assert!(report[0].contains("Limp"));
+    assert_eq!(
+        report[3],
+        format!("{:<17}{:>7}{:>7}{:>7}{:>7}", "Limp", 0, 0, 0, 1)
+    );
+}
@@ -0,0 +1,72 @@
+use preflop_trainer_core::{
+    AnswerResult, HandNotation, HandType, Position, Rank, SpotType, Suit, UserAction,
+};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+// Serializes to `json`, and `json` parses back to the same value
+fn assert_round_trips<T>(value: T, json: &str)
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    assert_eq!(serde_json::to_string(&value).unwrap(), json);
+    assert_eq!(serde_json::from_str::<T>(json).unwrap(), value);
+}
+
+#[test]
+fn test_poker_enums_serialize_as_strings() {
+    assert_round_trips(HandNotation::from_str("AKs").unwrap(), "\"AKs\"");
+    assert_round_trips(HandNotation::from_str("72o").unwrap(), "\"72o\"");
+    assert_round_trips(SpotType::open(Position::BTN), "\"Open_BTN\"");
+    assert_round_trips(SpotType::bb_defense(Position::CO), "\"BBDefense_CO\"");
+    assert_round_trips(SpotType::SbLimp, "\"Open_SB_Limp\"");
+    assert_round_trips(UserAction::Raise, "\"Raise\"");
+    assert_round_trips(UserAction::Limp, "\"Limp\"");
+    assert_round_trips(AnswerResult::FrequencyMistake, "\"FrequencyMistake\"");
+    assert_round_trips(Position::UTG1, "\"UTG1\"");
+    assert_round_trips(Position::BTN, "\"BTN\"");
+    assert_round_trips(Rank::Ten, "\"T\"");
+    assert_round_trips(Rank::Ace, "\"A\"");
+    assert_round_trips(Suit::Hearts, "\"h\"");
+    assert_round_trips(HandType::Offsuit, "\"offsuit\"");
+}
+
+#[test]
+fn test_every_value_round_trips() {
+    for position in Position::VALUES {
+        let json = serde_json::to_string(&position).unwrap();
+        assert_eq!(serde_json::from_str::<Position>(&json).unwrap(), position);
+    }
+    for rank in Rank::VALUES {
+        let json = serde_json::to_string(&rank).unwrap();
+        assert_eq!(serde_json::from_str::<Rank>(&json).unwrap(), rank);
+    }
+    for suit in Suit::VALUES {
+        let json = serde_json::to_string(&suit).unwrap();
+        assert_eq!(serde_json::from_str::<Suit>(&json).unwrap(), suit);
+    }
+    for hand_notation in preflop_trainer_core::get_all_possible_hand_notations() {
+        let json = serde_json::to_string(&hand_notation).unwrap();
+        assert_eq!(
+            serde_json::from_str::<HandNotation>(&json).unwrap(),
+            hand_notation
+        );
+    }
+    for spot_type in SpotType::standard_spot_types() {
+        let json = serde_json::to_string(&spot_type).unwrap();
+        assert_eq!(serde_json::from_str::<SpotType>(&json).unwrap(), spot_type);
+    }
+}
+
+#[test]
+fn test_invalid_strings_fail_to_deserialize() {
+    assert!(serde_json::from_str::<HandNotation>("\"AKx\"").is_err());
+    assert!(serde_json::from_str::<SpotType>("\"Open_XX\"").is_err());
+    assert!(serde_json::from_str::<Position>("\"Button\"").is_err());
+    assert!(serde_json::from_str::<Rank>("\"AK\"").is_err());
+    assert!(serde_json::from_str::<Suit>("\"x\"").is_err());
+    assert!(serde_json::from_str::<HandType>("\"Pair\"").is_err());
+    assert!(serde_json::from_str::<UserAction>("\"raise\"").is_err());
+}
@@ -0,0 +1,68 @@
+use preflop_trainer_core::{
+    Game, GameConfig, TrainerSession, UserAction, load_spot_list, parse_config_str,
+    parse_spot_list, save_spot_list, spot_list_to_string,
+};
+
+fn example_config() -> GameConfig {
+    parse_config_str(include_str!("../../../ranges.toml.example")).unwrap()
+}
+
+#[test]
+fn test_exported_session_replays_identically() {
+    let config = example_config();
+    let mut session = TrainerSession::new(Game::new_with_seed(config.clone(), 11), config.clone());
+    let mut results = Vec::new();
+    for _ in 0..25 {
+        let (spot_type, hand, rng) = session.next_spot().unwrap();
+        results.push(session.answer(spot_type, hand, UserAction::Raise, rng));
+    }
+    let exported = session.export_spot_list();
+    assert_eq!(exported.len(), 25);
+
+    let path = std::env::temp_dir().join(format!(
+        "preflop_trainer_spot_list_{}.txt",
+        std::process::id()
+    ));
+    save_spot_list(&path, &exported).unwrap();
+    let imported = load_spot_list(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    assert_eq!(imported, exported);
+
+    let mut replay = TrainerSession::new(Game::new_scripted(config.clone(), imported), config);
+    for (served, result) in exported.iter().zip(results) {
+        let (spot_type, hand, rng) = replay.next_spot().unwrap();
+        assert_eq!((spot_type, hand, rng), *served);
+        assert_eq!(
+            replay.answer(spot_type, hand, UserAction::Raise, rng),
+            result
+        );
+    }
+    assert!(replay.next_spot().is_err());
+    assert_eq!(replay.export_spot_list(), exported);
+}
+
+#[test]
+fn test_spot_list_text_format() {
+    let spots =
+        parse_spot_list("# shared pack\n\nOpen_BTN As Kd 42\nBBDefense_CO 7h 7c 0\n").unwrap();
+    assert_eq!(spots.len(), 2);
+    assert_eq!(
+        spot_list_to_string(&spots),
+        "Open_BTN As Kd 42\nBBDefense_CO 7h 7c 0\n"
+    );
+}
+
+#[test]
+fn test_spot_list_rejects_bad_lines() {
+    for contents in [
+        "Open_BTN As Kd",
+        "Open_BTN As Kd 100",
+        "Open_BTN As As 5",
+        "Open_BTN Ax Kd 5",
+        "Open_XX As Kd 5",
+    ] {
+        assert!(parse_spot_list(contents).is_err(), "{}", contents);
+    }
+    let err = parse_spot_list("Open_BTN As Kd 1\n\nOpen_BTN As 5").unwrap_err();
+    assert!(err.to_string().contains("line 3"), "{}", err);
+}
@@ -1,7 +1,10 @@
 use preflop_trainer_core::{
-    Game, GameConfig, HandNotation, Position, SpotType, get_all_possible_hand_notations,
+    CoreError, Game, GameConfig, HandNotation, HandType, OpenSize, Position, RangeTable,
+    SPOT_TYPE_FORMATS, SpotKind, SpotType, get_all_possible_hand_notations, parse_config_str,
+    parse_spot_type_with_hint, unique_spot_count,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
 
 // Helper to create a GameConfig for tests
 fn create_test_config(allowed_spot_types: Vec<SpotType>) -> GameConfig {
@@ -11,26 +14,24 @@ fn create_test_config(allowed_spot_types: Vec<SpotType>) -> GameConfig {
 
     // Populate with some dummy data to ensure ranges are not empty
     let all_notations = get_all_possible_hand_notations();
-    let dummy_range: HashMap<HandNotation, f32> =
-        all_notations.iter().take(5).map(|&hn| (hn, 1.0)).collect();
+    let dummy_range: RangeTable = all_notations.iter().take(5).map(|&hn| (hn, 1.0)).collect();
 
     unopened_raise_ranges.insert(Position::UTG, dummy_range.clone());
-    bb_defense_call_ranges.insert(Position::UTG, dummy_range.clone());
-    bb_defense_raise_ranges.insert(Position::UTG, dummy_range.clone());
+    bb_defense_call_ranges.insert((Position::UTG, None), dummy_range.clone());
+    bb_defense_raise_ranges.insert((Position::UTG, None), dummy_range.clone());
 
     GameConfig {
         unopened_raise_ranges,
         bb_defense_call_ranges,
         bb_defense_raise_ranges,
         allowed_spot_types,
+        ..Default::default()
     }
 }
 
 #[test]
 fn test_generate_random_spot_only_open() {
-    let config = create_test_config(vec![SpotType::Open {
-        position: Position::UTG,
-    }]);
+    let config = create_test_config(vec![SpotType::open(Position::UTG)]);
     let mut game = Game::new(config);
 
     for _ in 0..100 {
@@ -46,9 +47,7 @@ fn test_generate_random_spot_only_open() {
 
 #[test]
 fn test_generate_random_spot_only_bb_defense() {
-    let config = create_test_config(vec![SpotType::BBDefense {
-        opener_position: Position::UTG,
-    }]);
+    let config = create_test_config(vec![SpotType::bb_defense(Position::UTG)]);
     let mut game = Game::new(config);
 
     for _ in 0..100 {
@@ -65,12 +64,8 @@ fn test_generate_random_spot_only_bb_defense() {
 #[test]
 fn test_generate_random_spot_all_allowed() {
     let config = create_test_config(vec![
-        SpotType::Open {
-            position: Position::UTG,
-        },
-        SpotType::BBDefense {
-            opener_position: Position::UTG,
-        },
+        SpotType::open(Position::UTG),
+        SpotType::bb_defense(Position::UTG),
     ]);
     let mut game = Game::new(config);
 
@@ -83,6 +78,11 @@ fn test_generate_random_spot_all_allowed() {
         match spot_type {
             SpotType::Open { .. } => open_count += 1,
             SpotType::BBDefense { .. } => bb_defense_count += 1,
+            SpotType::Defense { .. }
+            | SpotType::Vs3Bet { .. }
+            | SpotType::Squeeze { .. }
+            | SpotType::PushFold { .. }
+            | SpotType::SbLimp => {}
         }
     }
 
@@ -92,11 +92,374 @@ fn test_generate_random_spot_all_allowed() {
 }
 
 #[test]
-#[should_panic(expected = "No valid spot types configured or able to be generated")]
 fn test_generate_random_spot_empty_allowed_list() {
     let config = create_test_config(vec![]); // Empty allowed list
     let mut game = Game::new(config);
 
-    // This should panic because no spots can be generated
-    game.generate_random_spot();
+    // This should fail because no spots can be generated
+    let err = game.generate_random_spot().unwrap_err();
+    assert!(matches!(err, CoreError::SpotGeneration(_)));
+    assert!(
+        err.to_string()
+            .contains("No valid spot types configured or able to be generated")
+    );
+}
+
+#[test]
+fn test_unique_spot_count_counts_every_weighted_hand_once_per_spot() {
+    let config = create_test_config(vec![
+        SpotType::open(Position::UTG),
+        SpotType::bb_defense(Position::UTG),
+        // Listing a spot twice doesn't add new spots
+        SpotType::open(Position::UTG),
+    ]);
+
+    // Every one of the 169 notations has a nonzero weight in each spot
+    assert_eq!(unique_spot_count(&config), 2 * 169);
+}
+
+#[test]
+fn test_unique_spot_count_empty_allowed_list() {
+    let config = create_test_config(vec![]);
+    assert_eq!(unique_spot_count(&config), 0);
+}
+
+#[test]
+fn test_invalid_spot_string_error_lists_valid_options() {
+    let err = parse_spot_type_with_hint("Open_Button").unwrap_err();
+    assert!(err.contains("Invalid position: Button"), "{}", err);
+    for valid in ["Open_UTG", "Open_SB", "BBDefense_CO", "BBDefense_BTN"] {
+        assert!(err.contains(valid), "{} missing from {}", valid, err);
+    }
+
+    // Valid strings parse as usual
+    assert_eq!(
+        parse_spot_type_with_hint("BBDefense_CO").unwrap(),
+        SpotType::bb_defense(Position::CO)
+    );
+}
+
+#[test]
+fn test_every_spot_type_format_example_parses() {
+    let mut variants = HashSet::new();
+    for &(format, example) in SPOT_TYPE_FORMATS {
+        let spot_type = parse_spot_type_with_hint(example)
+            .unwrap_or_else(|e| panic!("{} example {}: {}", format, example, e));
+        // Adding a variant breaks this match until it has a format listed
+        variants.insert(match spot_type {
+            SpotType::Open { .. } => "Open",
+            SpotType::PushFold { .. } => "PushFold",
+            SpotType::BBDefense { .. } => "BBDefense",
+            SpotType::Defense { .. } => "Defense",
+            SpotType::Vs3Bet { .. } => "Vs3Bet",
+            SpotType::Squeeze { .. } => "Squeeze",
+            SpotType::SbLimp => "SbLimp",
+        });
+    }
+    assert_eq!(variants.len(), 7);
+
+    let err = parse_spot_type_with_hint("Squeeze_CO").unwrap_err();
+    assert!(
+        err.contains("expected Squeeze_<Opener>_<Caller> or"),
+        "{}",
+        err
+    );
+    assert!(err.contains("Vs3Bet_<Opener>_<3-Bettor>_<Depth>, e.g. Vs3Bet_CO_BTN_40"));
+}
+
+#[test]
+fn test_config_strings_round_trip() {
+    let mut spot_types = SpotType::standard_spot_types();
+    spot_types.push(SpotType::BBDefense {
+        opener_position: Position::BTN,
+        open_size: Some(OpenSize(25)),
+        stack_depth: None,
+    });
+    assert_eq!(spot_types[0].to_config_string(), "Open_UTG");
+    assert_eq!(spot_types[10].to_config_string(), "BBDefense_BTN_2.5");
+    for spot_type in spot_types {
+        assert_eq!(
+            SpotType::from_str(&spot_type.to_config_string()).unwrap(),
+            spot_type
+        );
+    }
+}
+
+#[test]
+fn test_coverage_first_serves_every_notation_before_repeating() {
+    let utg_open = SpotType::open(Position::UTG);
+    let mut config = create_test_config(vec![utg_open]);
+    config.coverage_first = true;
+    let in_range: Vec<HandNotation> = config.unopened_raise_ranges[&Position::UTG]
+        .hands()
+        .collect();
+    let mut game = Game::new_with_seed(config, 21);
+
+    // Two full cycles: within each, no notation repeats and every one shows up
+    for _ in 0..2 {
+        let mut seen = HashSet::new();
+        for _ in 0..169 {
+            let (_, hand, _) = game.generate_random_spot().unwrap();
+            assert!(
+                seen.insert(HandNotation::from_hand(hand)),
+                "Repeat before cycle end"
+            );
+        }
+        assert!(in_range.iter().all(|hn| seen.contains(hn)));
+    }
+}
+
+#[test]
+fn test_without_coverage_first_notations_repeat() {
+    let config = create_test_config(vec![SpotType::open(Position::UTG)]);
+    let mut game = Game::new_with_seed(config, 21);
+    let mut seen = HashSet::new();
+    let repeated = (0..169).any(|_| {
+        let (_, hand, _) = game.generate_random_spot().unwrap();
+        !seen.insert(HandNotation::from_hand(hand))
+    });
+    assert!(repeated);
+}
+
+#[test]
+fn test_every_spot_type_has_a_description() {
+    let mut spot_types = SpotType::standard_spot_types();
+    for &position in &Position::VALUES {
+        spot_types.push(SpotType::open(position));
+        spot_types.push(SpotType::bb_defense(position));
+        spot_types.push(SpotType::BBDefense {
+            opener_position: position,
+            open_size: Some(OpenSize(25)),
+            stack_depth: None,
+        });
+    }
+    for spot_type in spot_types {
+        assert!(
+            !spot_type.description().is_empty(),
+            "{:?} has no description",
+            spot_type
+        );
+    }
+    assert!(
+        SpotType::bb_defense(Position::CO)
+            .description()
+            .contains("cutoff")
+    );
+}
+
+fn suited_offsuit_counts(combo_weighting: bool) -> (u32, u32) {
+    let mut config = GameConfig {
+        allowed_spot_types: vec![SpotType::open(Position::UTG)],
+        ..Default::default()
+    };
+    config.combo_weighting = combo_weighting;
+    let mut game = Game::new_with_seed(config, 17);
+    let (mut suited, mut offsuit) = (0, 0);
+    for _ in 0..20000 {
+        let (_, hand, _) = game.generate_random_spot().unwrap();
+        match HandNotation::from_hand(hand).hand_type {
+            HandType::Suited => suited += 1,
+            HandType::Offsuit => offsuit += 1,
+            HandType::Pair => {}
+        }
+    }
+    (suited, offsuit)
+}
+
+#[test]
+fn test_combo_weighting_deals_offsuit_three_times_as_often() {
+    // 78 suited and 78 offsuit notations, so the totals compare per-notation rates
+    let (suited, offsuit) = suited_offsuit_counts(true);
+    let ratio = offsuit as f32 / suited as f32;
+    assert!(
+        (2.6..3.4).contains(&ratio),
+        "offsuit/suited ratio {}",
+        ratio
+    );
+
+    let (suited, offsuit) = suited_offsuit_counts(false);
+    let ratio = offsuit as f32 / suited as f32;
+    assert!(
+        (0.8..1.25).contains(&ratio),
+        "offsuit/suited ratio {}",
+        ratio
+    );
+}
+
+#[test]
+fn test_equal_weights_deal_hand_types_by_combos() {
+    // Combo weighting is on by default; with an empty range every notation is a fold
+    // with the same base weight
+    let config = GameConfig {
+        allowed_spot_types: vec![SpotType::open(Position::UTG)],
+        ..Default::default()
+    };
+    assert!(config.combo_weighting);
+    let mut game = Game::new_with_seed(config, 23);
+    let (mut pairs, mut suited, mut offsuit) = (0, 0, 0);
+    for _ in 0..30000 {
+        let (_, hand, _) = game.generate_random_spot().unwrap();
+        match HandNotation::from_hand(hand).hand_type {
+            HandType::Pair => pairs += 1,
+            HandType::Suited => suited += 1,
+            HandType::Offsuit => offsuit += 1,
+        }
+    }
+    // Per-notation rates: 13 pairs, 78 suited and 78 offsuit notations
+    let per_pair = pairs as f32 / 13.0;
+    let per_suited = suited as f32 / 78.0;
+    let per_offsuit = offsuit as f32 / 78.0;
+    for (name, ratio, expected) in [
+        ("offsuit/suited", per_offsuit / per_suited, 3.0),
+        ("pair/suited", per_pair / per_suited, 1.5),
+    ] {
+        assert!(
+            (ratio - expected).abs() < expected * 0.15,
+            "{} ratio {}, expected about {}",
+            name,
+            ratio,
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_call_and_raise_hand_is_weighted_as_mixed() {
+    let spot_type = SpotType::bb_defense(Position::CO);
+    let mut config = GameConfig {
+        allowed_spot_types: vec![spot_type],
+        quiz_folds: false,
+        combo_weighting: false,
+        ..Default::default()
+    };
+    // AKs calls half the time and raises half the time; KQs is a pure call that's
+    // listed in the raise range at 0
+    config
+        .bb_defense_call_ranges
+        .insert((Position::CO, None), "AKs:0.5,KQs".parse().unwrap());
+    config
+        .bb_defense_raise_ranges
+        .insert((Position::CO, None), "AKs:0.5,KQs:0".parse().unwrap());
+
+    let mut game = Game::new_with_seed(config, 5);
+    let (mut mixed, mut pure_call) = (0, 0);
+    let iterations = 2000;
+    for _ in 0..iterations {
+        let (_, hand, _) = game.generate_random_spot().unwrap();
+        match HandNotation::from_hand(hand).to_string().as_str() {
+            "AKs" => mixed += 1,
+            "KQs" => pure_call += 1,
+            other => panic!("{} served", other),
+        }
+    }
+    // Mixed hands weigh 5000 against 50 for pure ones, so AKs is ~99% of spots
+    assert!(mixed > iterations * 95 / 100, "AKs served {} times", mixed);
+    assert!(pure_call > 0);
+}
+
+#[test]
+fn test_quiz_folds_off_never_serves_pure_folds() {
+    let mut config = GameConfig {
+        allowed_spot_types: vec![
+            SpotType::open(Position::BTN),
+            SpotType::bb_defense(Position::CO),
+        ],
+        ..Default::default()
+    };
+    assert!(config.quiz_folds);
+    config
+        .unopened_raise_ranges
+        .insert(Position::BTN, "AA,KQs:0.5,72o:0".parse().unwrap());
+    config
+        .bb_defense_call_ranges
+        .insert((Position::CO, None), "JTs".parse().unwrap());
+    config.quiz_folds = false;
+    assert_eq!(unique_spot_count(&config), 3);
+
+    let mut game = Game::new_with_seed(config.clone(), 3);
+    let mut served = HashSet::new();
+    for _ in 0..500 {
+        let (spot_type, hand, _) = game.generate_random_spot().unwrap();
+        let (raise, call, _) =
+            preflop_trainer_core::get_action_frequencies(&config, spot_type, hand);
+        assert!(
+            raise + call > 0.0,
+            "pure fold served: {} {}",
+            spot_type,
+            hand
+        );
+        served.insert(HandNotation::from_hand(hand));
+    }
+    assert_eq!(served.len(), 3);
+}
+
+#[test]
+fn test_quiz_folds_off_with_only_folds_is_an_error() {
+    let config = GameConfig {
+        allowed_spot_types: vec![SpotType::open(Position::UTG)],
+        quiz_folds: false,
+        ..Default::default()
+    };
+    let mut game = Game::new_with_seed(config, 1);
+    assert!(matches!(
+        game.generate_random_spot(),
+        Err(CoreError::SpotGeneration(_))
+    ));
+}
+
+#[test]
+fn test_filtered_spot_types_by_position_and_kind() {
+    let config = parse_config_str(include_str!("../../../ranges.toml.example")).unwrap();
+    let btn_open = SpotType::open(Position::BTN);
+
+    assert_eq!(
+        config.filtered_spot_types(Some(Position::BTN), Some(SpotKind::Open)),
+        [btn_open]
+    );
+    // BTN opens and nothing else: hero isn't on the button in a BB defense against it
+    assert_eq!(
+        config.filtered_spot_types(Some(Position::BTN), None),
+        [btn_open]
+    );
+    let bb_defense = config.filtered_spot_types(None, Some(SpotKind::Defense));
+    assert_eq!(bb_defense.len(), 5);
+    assert!(
+        bb_defense
+            .iter()
+            .all(|spot_type| matches!(spot_type, SpotType::BBDefense { .. }))
+    );
+    assert_eq!(
+        config.filtered_spot_types(Some(Position::BB), Some(SpotKind::Defense)),
+        bb_defense
+    );
+    assert_eq!(
+        config.filtered_spot_types(None, None),
+        config.allowed_spot_types
+    );
+    // No configured spot has hero in the cutoff facing a raise
+    assert!(
+        config
+            .filtered_spot_types(Some(Position::CO), Some(SpotKind::Defense))
+            .is_empty()
+    );
+
+    let mut filtered = config.clone();
+    filtered.allowed_spot_types = bb_defense;
+    let mut game = Game::new_with_seed(filtered, 3);
+    for _ in 0..100 {
+        let (spot_type, _, _) = game.generate_random_spot().unwrap();
+        assert_eq!(spot_type.kind(), SpotKind::Defense);
+        assert_eq!(spot_type.hero_position(), Position::BB);
+    }
+}
+
+#[test]
+fn test_spot_kind_from_str() {
+    assert_eq!(SpotKind::from_str("open"), Ok(SpotKind::Open));
+    assert_eq!(SpotKind::from_str("Defense"), Ok(SpotKind::Defense));
+    assert!(
+        SpotKind::from_str("3bet")
+            .unwrap_err()
+            .contains("expected open or defense")
+    );
 }
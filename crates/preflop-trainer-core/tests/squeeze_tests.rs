@@ -0,0 +1,89 @@
+use preflop_trainer_core::{
+    AnswerResult, Hand, SpotType, UserAction, check_answer, get_action_frequencies,
+    parse_config_str,
+};
+use std::str::FromStr;
+
+#[test]
+fn test_squeeze_caller_sits_between_the_opener_and_the_big_blind() {
+    for key in [
+        "Squeeze_CO_BTN",
+        "Squeeze_UTG_MP",
+        "Squeeze_MP_SB",
+        "Squeeze_BTN_SB",
+    ] {
+        assert!(
+            SpotType::from_str(key).unwrap().is_facing_raise(),
+            "{}",
+            key
+        );
+    }
+    assert!(SpotType::from_str("Squeeze_BTN_CO").is_err());
+    assert!(SpotType::from_str("Squeeze_CO_BB").is_err());
+    assert!(SpotType::from_str("Squeeze_CO_CO").is_err());
+    assert!(
+        parse_config_str(
+            "[unopened_raise.CO]\nrange = \"AA\"\n\n[squeeze.CO_BB]\ncall_range = \"\"\nraise_range = \"AA\"\n"
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_squeeze_uses_stacked_frequencies() {
+    let config = parse_config_str(
+        "[unopened_raise.CO]\nrange = \"22+,A2s+\"\n\n[squeeze.CO_BTN]\ncall_range = \"99,88,AJs:0.4\"\nraise_range = \"QQ+,AKs,A5s:0.5,AJs:0.6\"\n",
+    )
+    .unwrap();
+    let co_and_btn = SpotType::from_str("Squeeze_CO_BTN").unwrap();
+
+    // A5s: 0-49 squeeze, 50-99 fold, never call
+    let hand = "As5s".parse::<Hand>().unwrap();
+    let (raise, call, fold) = get_action_frequencies(&config, co_and_btn, hand);
+    assert_eq!((raise, call, fold), (0.5, 0.0, 0.5));
+    for (rng, correct, mistake) in [
+        (0, UserAction::Raise, UserAction::Fold),
+        (49, UserAction::Raise, UserAction::Fold),
+        (50, UserAction::Fold, UserAction::Raise),
+        (99, UserAction::Fold, UserAction::Raise),
+    ] {
+        assert_eq!(
+            check_answer(&config, co_and_btn, hand, correct, rng),
+            AnswerResult::Correct,
+            "rng {}",
+            rng
+        );
+        assert_eq!(
+            check_answer(&config, co_and_btn, hand, mistake, rng),
+            AnswerResult::FrequencyMistake,
+            "rng {}",
+            rng
+        );
+        assert_eq!(
+            check_answer(&config, co_and_btn, hand, UserAction::Call, rng),
+            AnswerResult::Wrong,
+            "rng {}",
+            rng
+        );
+    }
+
+    // AJs: 0-59 squeeze, 60-99 call, never fold
+    let hand = "AsJs".parse::<Hand>().unwrap();
+    for (rng, correct) in [
+        (0, UserAction::Raise),
+        (59, UserAction::Raise),
+        (60, UserAction::Call),
+        (99, UserAction::Call),
+    ] {
+        assert_eq!(
+            check_answer(&config, co_and_btn, hand, correct, rng),
+            AnswerResult::Correct,
+            "rng {}",
+            rng
+        );
+    }
+    assert_eq!(
+        check_answer(&config, co_and_btn, hand, UserAction::Fold, 70),
+        AnswerResult::Wrong
+    );
+}
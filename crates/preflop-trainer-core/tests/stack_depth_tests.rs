@@ -0,0 +1,315 @@
+use preflop_trainer_core::{
+    AnswerResult, Game, GenericConfig, Hand, HandNotation, Position, SpotType, StackDepth,
+    UserAction, check_answer, parse_config_str, villain_range,
+};
+use std::str::FromStr;
+
+#[test]
+fn test_stack_depth_parses_with_or_without_bb() {
+    assert_eq!(StackDepth::from_str("40"), Ok(StackDepth(40)));
+    assert_eq!(StackDepth::from_str("40bb"), Ok(StackDepth(40)));
+    assert_eq!(StackDepth(40).to_string(), "40bb");
+    assert!(StackDepth::from_str("0").is_err());
+    assert!(StackDepth::from_str("12.5").is_err());
+
+    assert_eq!(
+        SpotType::from_str("Open_BTN_40bb"),
+        SpotType::from_str("Open_BTN_40")
+    );
+    assert_eq!(SpotType::open(Position::BTN).stack_depth(), None);
+}
+
+#[test]
+fn test_depth_keys_fill_depth_ranges_and_default_spots() {
+    let config = parse_config_str(
+        "[unopened_raise.BTN]\nrange = \"22+,A2s+,K9o+\"\n\n[unopened_raise.BTN_40]\nrange = \"55+\"\n\n[unopened_raise.BTN_20bb]\nrange = \"TT+,AQs+\"\n",
+    )
+    .unwrap();
+    assert_eq!(config.unopened_raise_ranges[&Position::BTN].len(), 29);
+    assert_eq!(
+        config.unopened_raise_depth_ranges[&(Position::BTN, StackDepth(20))].len(),
+        7
+    );
+    for spot_type in ["Open_BTN_20", "Open_BTN_40"] {
+        assert!(
+            config
+                .allowed_spot_types
+                .contains(&SpotType::from_str(spot_type).unwrap())
+        );
+    }
+    assert!(parse_config_str("[unopened_raise.BTN_deep]\nrange = \"AA\"\n").is_err());
+}
+
+#[test]
+fn test_check_answer_uses_the_depth_range() {
+    let config = parse_config_str(
+        "[unopened_raise.BTN]\nrange = \"22+,A2s+,K9o+\"\n\n[unopened_raise.BTN_40]\nrange = \"55+,A8s+,KJo+\"\n",
+    )
+    .unwrap();
+    let hand = "KcTd".parse::<Hand>().unwrap();
+    let btn = SpotType::open(Position::BTN);
+    let btn_40 = btn.with_stack_depth(Some(StackDepth(40)));
+    assert_eq!(
+        check_answer(&config, btn, hand, UserAction::Raise, 0),
+        AnswerResult::Correct
+    );
+    assert_eq!(
+        check_answer(&config, btn_40, hand, UserAction::Raise, 0),
+        AnswerResult::Wrong
+    );
+    assert_eq!(
+        check_answer(&config, btn_40, hand, UserAction::Fold, 0),
+        AnswerResult::Correct
+    );
+    // A depth without its own range doesn't borrow the default one
+    let btn_100 = btn.with_stack_depth(Some(StackDepth(100)));
+    assert_eq!(
+        check_answer(&config, btn_100, hand, UserAction::Fold, 0),
+        AnswerResult::Correct
+    );
+}
+
+#[test]
+fn test_generate_random_spot_picks_among_configured_depths() {
+    let mut config = parse_config_str(
+        "[unopened_raise.BTN]\nrange = \"22+\"\n\n[unopened_raise.BTN_40]\nrange = \"55+\"\n\n[unopened_raise.BTN_20]\nrange = \"TT+\"\n",
+    )
+    .unwrap();
+    let btn = SpotType::open(Position::BTN);
+    config.allowed_spot_types = vec![
+        btn.with_stack_depth(Some(StackDepth(20))),
+        btn.with_stack_depth(Some(StackDepth(40))),
+    ];
+    let mut game = Game::new_with_seed(config, 5);
+    let mut depths = Vec::new();
+    for _ in 0..50 {
+        let (spot_type, _, _) = game.generate_random_spot().unwrap();
+        depths.push(spot_type.stack_depth().unwrap());
+    }
+    assert!(depths.contains(&StackDepth(20)));
+    assert!(depths.contains(&StackDepth(40)));
+}
+
+// Call/raise tables at 40bb next to plain ones; the CO opens tighter at 40bb
+const CALL_RAISE_CONFIG: &str = r#"
+[unopened_raise.CO]
+range = "22+,A2s+,KTs+,ATo+"
+
+[unopened_raise.CO_40]
+range = "55+,A8s+,KJo+"
+
+[unopened_raise.BTN]
+range = "22+,A2s+,K9o+"
+
+[bb_defense.CO]
+call_range = "22-99,A2s-AJs"
+raise_range = "TT+,AQs+"
+
+[defense.BB_CO_40]
+call_range = "55-99"
+raise_range = "TT+,A8s+"
+
+[defense.BTN_CO_40]
+call_range = "TT-88"
+raise_range = "JJ+,AKs"
+
+[vs_3bet.CO_BTN_40]
+call_range = "QQ-TT,AQs"
+raise_range = "KK+,AKs"
+
+[squeeze.CO_BTN_40]
+call_range = "88-66"
+raise_range = "QQ+,AKs"
+"#;
+
+#[test]
+fn test_call_raise_depth_spots_keep_their_seats() {
+    for spot_type in [
+        "Defense_BB_CO_40",
+        "Defense_BTN_CO_40",
+        "Vs3Bet_CO_BTN_40",
+        "Squeeze_CO_BTN_40",
+    ] {
+        let spot_type = SpotType::from_str(spot_type).unwrap();
+        assert_eq!(spot_type.stack_depth(), Some(StackDepth(40)));
+        assert!(spot_type.is_facing_raise());
+        assert_eq!(
+            spot_type.hero_position(),
+            spot_type.with_stack_depth(None).hero_position()
+        );
+    }
+    // Hero in the big blind is a BB defense at any depth
+    let bb_vs_co_40 = SpotType::bb_defense(Position::CO).with_stack_depth(Some(StackDepth(40)));
+    assert_eq!(
+        SpotType::defense(Position::BB, Position::CO).with_stack_depth(Some(StackDepth(40))),
+        bb_vs_co_40
+    );
+    assert_eq!(SpotType::from_str("Defense_BB_CO_40bb"), Ok(bb_vs_co_40));
+    // The seat order rules of the spots without depth still apply
+    assert!(SpotType::from_str("Vs3Bet_BTN_CO_40").is_err());
+    assert!(SpotType::from_str("Squeeze_CO_BTN_0").is_err());
+}
+
+#[test]
+fn test_depth_keys_fill_call_raise_depth_ranges_and_default_spots() {
+    let config = parse_config_str(CALL_RAISE_CONFIG).unwrap();
+    let bb_key = (Position::BB, Position::CO, StackDepth(40));
+    assert_eq!(config.defense_depth_raise_ranges[&bb_key].len(), 11);
+    // A big blind depth table doesn't replace the plain [bb_defense.CO] one
+    assert_eq!(
+        config.bb_defense_call_ranges[&(Position::CO, None)].len(),
+        18
+    );
+    assert!(config.defense_call_ranges.is_empty());
+    assert_eq!(
+        config.vs_3bet_depth_call_ranges[&(Position::CO, Position::BTN, StackDepth(40))].len(),
+        4
+    );
+    assert_eq!(
+        config.squeeze_depth_raise_ranges[&(Position::CO, Position::BTN, StackDepth(40))].len(),
+        4
+    );
+    for spot_type in [
+        "Defense_BB_CO_40",
+        "Defense_BTN_CO_40",
+        "Vs3Bet_CO_BTN_40",
+        "Squeeze_CO_BTN_40",
+    ] {
+        assert!(
+            config
+                .allowed_spot_types
+                .contains(&SpotType::from_str(spot_type).unwrap()),
+            "{} isn't allowed by default",
+            spot_type
+        );
+    }
+
+    for key in ["CO_BTN_deep", "BTN_CO_40", "CO_BTN_40_2"] {
+        let config = format!(
+            "[unopened_raise.CO]\nrange = \"AA\"\n\n[vs_3bet.{}]\ncall_range = \"\"\nraise_range = \"AA\"\n",
+            key
+        );
+        assert!(parse_config_str(&config).is_err(), "{} was accepted", key);
+    }
+}
+
+#[test]
+fn test_ranges_mut_reaches_the_depth_tables() {
+    let mut config = parse_config_str(CALL_RAISE_CONFIG).unwrap();
+    assert_eq!(
+        config
+            .ranges_mut()
+            .filter(|range| !range.is_empty())
+            .count(),
+        13
+    );
+}
+
+#[test]
+fn test_check_answer_uses_the_depth_call_raise_ranges() {
+    let config = parse_config_str(CALL_RAISE_CONFIG).unwrap();
+    let hand = Hand::from_str("As9s").unwrap();
+    let bb_vs_co = SpotType::bb_defense(Position::CO);
+    let bb_vs_co_40 = bb_vs_co.with_stack_depth(Some(StackDepth(40)));
+    assert_eq!(
+        check_answer(&config, bb_vs_co, hand, UserAction::Call, 0),
+        AnswerResult::Correct
+    );
+    assert_eq!(
+        check_answer(&config, bb_vs_co_40, hand, UserAction::Raise, 0),
+        AnswerResult::Correct
+    );
+    assert_eq!(
+        check_answer(&config, bb_vs_co_40, hand, UserAction::Call, 0),
+        AnswerResult::Wrong
+    );
+    // A depth without its own ranges doesn't borrow the plain ones
+    assert_eq!(
+        check_answer(
+            &config,
+            bb_vs_co.with_stack_depth(Some(StackDepth(100))),
+            hand,
+            UserAction::Fold,
+            0
+        ),
+        AnswerResult::Correct
+    );
+}
+
+#[test]
+fn test_depth_defense_opener_opens_the_depth_range() {
+    let config = parse_config_str(CALL_RAISE_CONFIG).unwrap();
+    let depth_open = &config.unopened_raise_depth_ranges[&(Position::CO, StackDepth(40))];
+    let bb_vs_co = SpotType::bb_defense(Position::CO);
+    let bb_vs_co_40 = bb_vs_co.with_stack_depth(Some(StackDepth(40)));
+    assert_eq!(villain_range(&config, bb_vs_co_40), Some(depth_open));
+    // Without an open range at that depth, the plain one is the villain's
+    assert_eq!(
+        villain_range(&config, bb_vs_co.with_stack_depth(Some(StackDepth(100)))),
+        config.unopened_raise_ranges.get(&Position::CO)
+    );
+
+    let mut game_config = config.clone();
+    game_config.allowed_spot_types = vec![bb_vs_co_40];
+    let mut game = Game::new_with_seed(game_config, 3);
+    for _ in 0..30 {
+        game.generate_random_spot().unwrap();
+        let notation = HandNotation::from_hand(game.last_opener_hand().unwrap());
+        assert!(depth_open.contains(notation), "CO opened {}", notation);
+    }
+}
+
+#[test]
+fn test_fixed_stack_depth_round_trips_through_toml() {
+    let generic: GenericConfig = toml::from_str("stack_depth = 40").unwrap();
+    assert_eq!(generic.stack_depth, Some(StackDepth(40)));
+    assert_eq!(
+        toml::to_string(&generic).unwrap().trim(),
+        "stack_depth = 40"
+    );
+    assert!(toml::from_str::<GenericConfig>("stack_depth = -1").is_err());
+
+    let config = parse_config_str(&format!(
+        "{}\n[generic]\nstack_depth = 40\n",
+        CALL_RAISE_CONFIG
+    ))
+    .unwrap();
+    assert_eq!(config.stack_depth, Some(StackDepth(40)));
+    assert_eq!(config.allowed_spot_types.len(), 5);
+    assert!(
+        config
+            .allowed_spot_types
+            .iter()
+            .all(|spot_type| spot_type.stack_depth() == Some(StackDepth(40)))
+    );
+
+    let reparsed = parse_config_str(&config.to_toml_string().unwrap()).unwrap();
+    assert_eq!(reparsed.stack_depth, config.stack_depth);
+    assert_eq!(reparsed.allowed_spot_types, config.allowed_spot_types);
+    assert_eq!(
+        reparsed.defense_depth_call_ranges,
+        config.defense_depth_call_ranges
+    );
+    assert_eq!(
+        reparsed.vs_3bet_depth_raise_ranges,
+        config.vs_3bet_depth_raise_ranges
+    );
+    assert_eq!(
+        reparsed.squeeze_depth_call_ranges,
+        config.squeeze_depth_call_ranges
+    );
+}
+
+#[test]
+fn test_fixed_stack_depth_needs_spots_at_that_depth() {
+    let at = |generic: &str| {
+        parse_config_str(&format!("{}\n[generic]\n{}\n", CALL_RAISE_CONFIG, generic))
+    };
+    let err = at("stack_depth = 25").unwrap_err().to_string();
+    assert!(err.contains("no range table has that depth"), "{}", err);
+    let err = at("stack_depth = 40\nallowed_spot_types = [\"Open_CO\"]")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("isn't at the 40bb stack_depth"), "{}", err);
+    assert!(at("stack_depth = 40\nallowed_spot_types = [\"Vs3Bet_CO_BTN_40\"]").is_ok());
+}
@@ -0,0 +1,625 @@
+use preflop_trainer_core::{
+    AnswerResult, Card, CumulativeStats, Game, GameConfig, Hand, HandType, Position, Rank,
+    SessionStats, SpeedRoundReport, SpotType, StatBucket, StatTotals, Suit, TrainerSession,
+    UserAction, answer_credit,
+};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+fn hand(rank1: Rank, suit1: Suit, rank2: Rank, suit2: Suit) -> Hand {
+    Hand {
+        card1: Card {
+            rank: rank1,
+            suit: suit1,
+        },
+        card2: Card {
+            rank: rank2,
+            suit: suit2,
+        },
+    }
+}
+
+fn temp_stats_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!(
+        "preflop_trainer_{}_{}.toml",
+        name,
+        std::process::id()
+    ))
+}
+
+fn btn_open() -> SpotType {
+    SpotType::open(Position::BTN)
+}
+
+fn bb_vs_co() -> SpotType {
+    SpotType::bb_defense(Position::CO)
+}
+
+#[test]
+fn test_cumulative_stats_record_and_merge() {
+    let aces = hand(Rank::Ace, Suit::Spades, Rank::Ace, Suit::Hearts);
+    let suited = hand(Rank::King, Suit::Clubs, Rank::Queen, Suit::Clubs);
+
+    let mut first = CumulativeStats::default();
+    first.record(btn_open(), aces, AnswerResult::Correct);
+    first.record(bb_vs_co(), suited, AnswerResult::FrequencyMistake);
+
+    let mut second = CumulativeStats::default();
+    second.record(btn_open(), suited, AnswerResult::Wrong);
+
+    first.merge(&second);
+    assert_eq!(first.overall.answered, 3);
+    assert_eq!(first.overall.score, 1.5);
+    assert_eq!(first.by_position["BTN"].answered, 2);
+    assert_eq!(first.by_position["BTN"].score, 1.0);
+    assert_eq!(first.by_position["BB"].score, 0.5);
+    assert_eq!(first.by_hand_type["pair"].answered, 1);
+    assert_eq!(first.by_hand_type["suited"].answered, 2);
+    assert_eq!(first.by_spot["Open_BTN"].answered, 2);
+    assert_eq!(first.by_spot["BBDefense_CO"].score, 0.5);
+    assert_eq!(first.overall.accuracy(), 50.0);
+}
+
+#[test]
+fn test_cumulative_stats_save_load_round_trip() {
+    let path = temp_stats_path("round_trip");
+    let mut stats = CumulativeStats::default();
+    stats.record(
+        btn_open(),
+        hand(Rank::Ace, Suit::Spades, Rank::Ace, Suit::Hearts),
+        AnswerResult::Correct,
+    );
+    stats.save(&path).unwrap();
+    assert_eq!(CumulativeStats::load(&path).unwrap(), stats);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_missing_stats_load_as_empty() {
+    let path = temp_stats_path("missing");
+    assert_eq!(
+        CumulativeStats::load(&path).unwrap(),
+        CumulativeStats::default()
+    );
+}
+
+#[test]
+fn test_corrupt_stats_fail_to_load_and_are_not_overwritten() {
+    let path = temp_stats_path("corrupt");
+    std::fs::write(&path, "overall = not toml").unwrap();
+    let err = CumulativeStats::load(&path).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    let config = GameConfig::default();
+    let game = Game::new_with_seed(config.clone(), 1);
+    assert!(TrainerSession::with_lifetime_stats(game, config, path.clone()).is_err());
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "overall = not toml"
+    );
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_summary_format() {
+    let mut stats = CumulativeStats::default();
+    stats.overall.answered = 12340;
+    stats.overall.score = 10365.6;
+    assert_eq!(stats.summary(), "84% over 12,340 hands");
+}
+
+#[test]
+fn test_session_finish_merges_into_lifetime_file() {
+    let path = temp_stats_path("session");
+    let mut config = GameConfig::default();
+    config
+        .unopened_raise_ranges
+        .insert(Position::BTN, "AA".parse().unwrap());
+    config.allowed_spot_types = vec![btn_open()];
+    let aces = hand(Rank::Ace, Suit::Spades, Rank::Ace, Suit::Hearts);
+
+    for _ in 0..2 {
+        let game = Game::new_with_seed(config.clone(), 1);
+        let mut session =
+            TrainerSession::with_lifetime_stats(game, config.clone(), path.clone()).unwrap();
+        assert_eq!(
+            session.answer(btn_open(), aces, UserAction::Raise, 0),
+            AnswerResult::Correct
+        );
+        assert_eq!(session.stats().totals.overall.answered, 1);
+        session.finish().unwrap();
+    }
+
+    let lifetime = CumulativeStats::load(&path).unwrap();
+    assert_eq!(lifetime.overall.answered, 2);
+    let expected_totals = recorded([AnswerResult::Correct; 2]);
+    let expected_by_position = BTreeMap::from([("BTN".to_string(), expected_totals)]);
+    assert_eq!(lifetime.by_position, expected_by_position);
+    assert_eq!(
+        lifetime.totals(StatBucket::Spot(btn_open())),
+        expected_totals
+    );
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_strict_vs_lenient_accuracy() {
+    let sequence = [
+        AnswerResult::Correct,
+        AnswerResult::FrequencyMistake,
+        AnswerResult::Wrong,
+        AnswerResult::FrequencyMistake,
+    ];
+
+    let lenient: f32 = sequence.iter().map(|&r| answer_credit(r, false)).sum();
+    let strict: f32 = sequence.iter().map(|&r| answer_credit(r, true)).sum();
+    assert_eq!(lenient, 2.0);
+    assert_eq!(strict, 1.0);
+
+    let mut totals = StatTotals::default();
+    for result in sequence {
+        totals.record(result);
+    }
+    assert_eq!(totals.frequency_mistakes, 2);
+    assert_eq!(totals.accuracy(), 50.0);
+    assert_eq!(totals.strict_accuracy(), 25.0);
+}
+
+#[test]
+fn test_speed_round_rates() {
+    let report = SpeedRoundReport {
+        answered: 30,
+        score: 24.0,
+        elapsed: Duration::from_secs(45),
+    };
+    assert_eq!(report.hands_per_minute(), 40.0);
+    assert_eq!(report.accuracy(), 80.0);
+    assert_eq!(report.score_per_minute(), 32.0);
+    assert_eq!(
+        report.summary(),
+        "30 hands in 45s: 40.0 hands/min, 80% accuracy"
+    );
+
+    let empty = SpeedRoundReport::default();
+    assert_eq!(empty.hands_per_minute(), 0.0);
+    assert_eq!(empty.accuracy(), 0.0);
+}
+
+#[test]
+fn test_speed_round_counts_session_answers() {
+    let mut config = GameConfig::default();
+    config
+        .unopened_raise_ranges
+        .insert(Position::BTN, "AA".parse().unwrap());
+    config.allowed_spot_types = vec![btn_open()];
+    let aces = hand(Rank::Ace, Suit::Spades, Rank::Ace, Suit::Hearts);
+    let game = Game::new_with_seed(config.clone(), 1);
+    let mut session = TrainerSession::new(game, config);
+
+    assert_eq!(session.speed_round_remaining(), None);
+    session.answer(btn_open(), aces, UserAction::Raise, 0);
+    session.speed_round(Duration::from_secs(60));
+    assert!(!session.speed_round_over());
+    session.answer(btn_open(), aces, UserAction::Raise, 0);
+    session.answer(btn_open(), aces, UserAction::Fold, 0);
+
+    let report = session.end_speed_round().unwrap();
+    assert_eq!(report.answered, 2);
+    assert_eq!(report.score, 1.0);
+    assert!(report.elapsed <= Duration::from_secs(60));
+    assert_eq!(session.end_speed_round(), None);
+    assert_eq!(session.stats().totals.overall.answered, 3);
+}
+
+#[test]
+fn test_confusion_matrix_tallies_sequence() {
+    let mut config = GameConfig::default();
+    config
+        .unopened_raise_ranges
+        .insert(Position::BTN, "AA,KK:0.5".parse().unwrap());
+    config.allowed_spot_types = vec![btn_open()];
+    let aces = hand(Rank::Ace, Suit::Spades, Rank::Ace, Suit::Hearts);
+    let kings = hand(Rank::King, Suit::Spades, Rank::King, Suit::Hearts);
+    let game = Game::new_with_seed(config.clone(), 1);
+    let mut session = TrainerSession::new(game, config);
+
+    // KK raises below 50 on the RNG and folds from 50 up
+    session.answer(btn_open(), aces, UserAction::Raise, 80);
+    session.answer(btn_open(), aces, UserAction::Fold, 10);
+    session.answer(btn_open(), kings, UserAction::Fold, 20);
+    session.answer(btn_open(), kings, UserAction::Fold, 70);
+    session.answer(btn_open(), kings, UserAction::Raise, 49);
+
+    let stats = session.stats();
+    assert_eq!(
+        stats.confusion_count(UserAction::Raise, UserAction::Raise),
+        2
+    );
+    assert_eq!(
+        stats.confusion_count(UserAction::Fold, UserAction::Raise),
+        2
+    );
+    assert_eq!(stats.confusion_count(UserAction::Fold, UserAction::Fold), 1);
+    assert_eq!(
+        stats.confusion_count(UserAction::Raise, UserAction::Fold),
+        0
+    );
+    assert_eq!(stats.confusion.values().sum::<u32>(), 5);
+
+    let report = stats.confusion_report();
+    assert_eq!(report.len(), 4);
+    assert_eq!(report[3], format!("{:<17}{:>7}{:>7}{:>7}", "Fold", 2, 0, 1));
+    assert_eq!(
+        SessionStats::default().confusion_count(UserAction::Call, UserAction::Call),
+        0
+    );
+}
+
+// Totals of these answers, recorded in order
+fn recorded(results: impl IntoIterator<Item = AnswerResult>) -> StatTotals {
+    let mut totals = StatTotals::default();
+    for result in results {
+        totals.record(result);
+    }
+    totals
+}
+
+// `answered` answers, every tenth one wrong from `wrong_per_ten` on, e.g. 9 right and
+// 1 wrong in every ten with `wrong_per_ten` 1
+fn mixed(answered: usize, wrong_per_ten: usize) -> StatTotals {
+    recorded((0..answered).map(|i| {
+        if i % 10 < 10 - wrong_per_ten {
+            AnswerResult::Correct
+        } else {
+            AnswerResult::Wrong
+        }
+    }))
+}
+
+#[test]
+fn test_mastery_shrinks_small_samples() {
+    // No answers sit at the 50% prior, one lucky hand barely moves it
+    assert_eq!(StatTotals::default().mastery(), 50.0);
+    let one = recorded([AnswerResult::Correct]);
+    assert!((one.mastery() - 6.0 / 11.0 * 100.0).abs() < 1e-4);
+    assert!(!recorded([AnswerResult::Correct; 3]).is_mastered());
+
+    // A perfect run still needs the minimum sample size
+    let nineteen = recorded([AnswerResult::Correct; 19]);
+    assert!(nineteen.mastery() >= 80.0);
+    assert!(!nineteen.is_mastered());
+    assert!(recorded([AnswerResult::Correct; 20]).is_mastered());
+}
+
+#[test]
+fn test_mastery_settles_on_recent_accuracy_for_large_samples() {
+    // Decayed, 1000 answers weigh about as much as the last 50; at 90% that's
+    // (45 + 5) / (50 + 10)
+    let large = mixed(1000, 1);
+    assert!((large.mastery() - 50.0 / 60.0 * 100.0).abs() < 1.0);
+    assert!(large.is_mastered());
+    assert!(!mixed(1000, 3).is_mastered());
+}
+
+#[test]
+fn test_mastery_follows_recent_results() {
+    // Mostly right overall, but the last 30 all wrong
+    let slipping = recorded(
+        std::iter::repeat_n(AnswerResult::Correct, 200)
+            .chain(std::iter::repeat_n(AnswerResult::Wrong, 30)),
+    );
+    assert!(slipping.accuracy() > 85.0);
+    assert!(slipping.mastery() < 60.0);
+    assert!(!slipping.is_mastered());
+
+    // Half wrong overall, but right ever since
+    let improved = recorded(
+        std::iter::repeat_n(AnswerResult::Wrong, 100)
+            .chain(std::iter::repeat_n(AnswerResult::Correct, 100)),
+    );
+    assert_eq!(improved.accuracy(), 50.0);
+    assert!(improved.is_mastered());
+}
+
+#[test]
+fn test_merge_treats_the_other_totals_as_newer() {
+    let older = [AnswerResult::Correct; 30];
+    let newer = [
+        AnswerResult::Wrong,
+        AnswerResult::FrequencyMistake,
+        AnswerResult::Wrong,
+    ];
+    let mut merged = recorded(older);
+    merged.merge(&recorded(newer));
+    let in_order = recorded(older.into_iter().chain(newer));
+    assert_eq!(merged.answered, in_order.answered);
+    assert_eq!(merged.score, in_order.score);
+    assert!((merged.recent_answered - in_order.recent_answered).abs() < 1e-4);
+    assert!((merged.recent_score - in_order.recent_score).abs() < 1e-4);
+}
+
+#[test]
+fn test_session_mastery_by_bucket() {
+    let mut config = GameConfig::default();
+    config
+        .unopened_raise_ranges
+        .insert(Position::BTN, "AA".parse().unwrap());
+    config.allowed_spot_types = vec![btn_open()];
+    let aces = hand(Rank::Ace, Suit::Spades, Rank::Ace, Suit::Hearts);
+    let game = Game::new_with_seed(config.clone(), 1);
+    let mut session = TrainerSession::new(game, config);
+    for _ in 0..40 {
+        session.answer(btn_open(), aces, UserAction::Raise, 0);
+    }
+
+    let stats = session.stats();
+    assert!(stats.is_mastered(StatBucket::Position(Position::BTN)));
+    assert!(stats.is_mastered(StatBucket::HandType(HandType::Pair)));
+    assert_eq!(stats.mastery(StatBucket::Position(Position::CO)), 50.0);
+    assert!(!stats.is_mastered(StatBucket::HandType(HandType::Suited)));
+    assert!(stats.is_mastered(StatBucket::Spot(btn_open())));
+    assert_eq!(stats.mastery(StatBucket::Spot(bb_vs_co())), 50.0);
+    assert_eq!(
+        stats.mastery(StatBucket::Overall),
+        stats.mastery(StatBucket::Position(Position::BTN))
+    );
+}
+
+#[test]
+fn test_session_breakdown_by_position_spot_and_hand() {
+    let mut stats = SessionStats::default();
+    let aces = hand(Rank::Ace, Suit::Spades, Rank::Ace, Suit::Hearts);
+    let ace_jack = hand(Rank::Ace, Suit::Spades, Rank::Jack, Suit::Hearts);
+    let seven_six = hand(Rank::Seven, Suit::Clubs, Rank::Six, Suit::Clubs);
+    let utg_open = SpotType::open(Position::UTG);
+    for (spot_type, hand, result) in [
+        (btn_open(), aces, AnswerResult::Correct),
+        (btn_open(), ace_jack, AnswerResult::FrequencyMistake),
+        (btn_open(), ace_jack, AnswerResult::Correct),
+        (btn_open(), seven_six, AnswerResult::Wrong),
+        (bb_vs_co(), ace_jack, AnswerResult::FrequencyMistake),
+        (bb_vs_co(), seven_six, AnswerResult::Correct),
+        (utg_open, aces, AnswerResult::Correct),
+    ] {
+        // The chosen and correct actions and the RNG value only feed the confusion
+        // matrix and the answer log
+        stats.record(
+            spot_type,
+            hand,
+            UserAction::Raise,
+            UserAction::Raise,
+            result,
+            0,
+        );
+    }
+
+    // A frequency mistake is half credit: BTN scores 2.5 of 4
+    assert_eq!(
+        stats.accuracy_by_position(),
+        [
+            (Position::UTG, 100.0),
+            (Position::BTN, 62.5),
+            (Position::BB, 75.0)
+        ]
+    );
+    assert_eq!(
+        stats.accuracy_by_spot_type(),
+        [(bb_vs_co(), 75.0), (btn_open(), 62.5), (utg_open, 100.0)]
+    );
+
+    // 76s lost a full point and AJo two halves, so the lower accuracy goes first; AA
+    // was never missed
+    let most_missed: Vec<(String, u32, f32)> = stats
+        .most_missed_hands(5)
+        .into_iter()
+        .map(|(hand_notation, totals)| (hand_notation.to_string(), totals.answered, totals.score))
+        .collect();
+    assert_eq!(
+        most_missed,
+        [("76s".to_string(), 2, 1.0), ("AJo".to_string(), 3, 2.0)]
+    );
+    assert_eq!(stats.most_missed_hands(1).len(), 1);
+
+    let report = stats.breakdown_report();
+    assert_eq!(
+        report,
+        [
+            format!("{}: 75% (2 hands)", bb_vs_co()),
+            format!("{}: 62% (4 hands)", btn_open()),
+            format!("{}: 100% (1 hand)", utg_open),
+            "Most missed: 76s, AJo".to_string(),
+        ]
+    );
+    assert!(SessionStats::default().breakdown_report().is_empty());
+}
+
+#[test]
+fn test_session_json_round_trips_and_resumes() {
+    let session_path = temp_stats_path("resume_session").with_extension("json");
+    let lifetime_path = temp_stats_path("resume_lifetime");
+    let mut config = GameConfig::default();
+    config
+        .unopened_raise_ranges
+        .insert(Position::BTN, "AA,KK:0.5".parse().unwrap());
+    config.allowed_spot_types = vec![btn_open(), bb_vs_co()];
+    let aces = hand(Rank::Ace, Suit::Spades, Rank::Ace, Suit::Hearts);
+    let kings = hand(Rank::King, Suit::Spades, Rank::King, Suit::Hearts);
+
+    let game = Game::new_with_seed(config.clone(), 1);
+    let mut session =
+        TrainerSession::with_lifetime_stats(game, config.clone(), lifetime_path.clone()).unwrap();
+    session.answer(btn_open(), aces, UserAction::Raise, 0);
+    session.answer(btn_open(), kings, UserAction::Raise, 80);
+    session.answer(bb_vs_co(), kings, UserAction::Fold, 10);
+    session.stats().save_json(&session_path).unwrap();
+    let saved = session.stats().clone();
+    session.finish().unwrap();
+
+    let loaded = SessionStats::load_json(&session_path).unwrap();
+    assert_eq!(loaded, saved);
+    assert_eq!(loaded.breakdown_report(), saved.breakdown_report());
+
+    // The BB defense spot is gone from this config, so its per-spot stats, its answers
+    // and the BB position totals are skipped
+    let mut resumed_config = config.clone();
+    resumed_config.allowed_spot_types = vec![btn_open()];
+    let mut resumed = loaded.clone();
+    assert_eq!(resumed.retain_config_spots(&resumed_config), [bb_vs_co()]);
+    assert_eq!(resumed.accuracy_by_spot_type(), [(btn_open(), 75.0)]);
+    assert_eq!(resumed.totals.overall.answered, 3);
+    assert_eq!(
+        resumed.totals.by_position.keys().collect::<Vec<_>>(),
+        ["BTN"]
+    );
+    assert_eq!(resumed.answers.len(), 2);
+    assert!(
+        resumed
+            .answers
+            .iter()
+            .all(|answer| answer.spot_type == btn_open())
+    );
+
+    let game = Game::new_with_seed(resumed_config.clone(), 2);
+    let mut session =
+        TrainerSession::with_lifetime_stats(game, resumed_config, lifetime_path.clone()).unwrap();
+    session.resume(resumed);
+    session.answer(btn_open(), aces, UserAction::Raise, 0);
+    assert_eq!(session.stats().totals.overall.answered, 4);
+    // Only the new answer is added to the lifetime stats
+    assert_eq!(session.finish().unwrap().overall.answered, 4);
+    assert_eq!(
+        CumulativeStats::load(&lifetime_path)
+            .unwrap()
+            .overall
+            .answered,
+        4
+    );
+
+    std::fs::write(&session_path, "{ not json").unwrap();
+    assert!(SessionStats::load_json(&session_path).is_err());
+    std::fs::remove_file(&session_path).unwrap();
+    std::fs::remove_file(&lifetime_path).unwrap();
+    assert!(SessionStats::load_json(&session_path).is_err());
+}
+
+#[test]
+fn test_session_csv_export() {
+    let mut config = GameConfig::default();
+    config
+        .bb_defense_call_ranges
+        .insert((Position::CO, None), "KTs".parse().unwrap());
+    config.allowed_spot_types = vec![bb_vs_co()];
+    let king_ten = hand(Rank::King, Suit::Clubs, Rank::Ten, Suit::Clubs);
+    let game = Game::new_with_seed(config.clone(), 1);
+    let mut session = TrainerSession::new(game, config);
+    session.answer(bb_vs_co(), king_ten, UserAction::Fold, 41);
+
+    let csv = session.stats().to_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "spot,positions,hand,action,correct_action,result,rng",
+            "BB vs CO Open,CO BB,KTs,Fold,Call,Wrong,41",
+        ]
+    );
+    assert!(csv.ends_with('\n'));
+    assert_eq!(
+        SessionStats::default().to_csv(),
+        "spot,positions,hand,action,correct_action,result,rng\n"
+    );
+}
+
+#[test]
+fn test_session_breakdown_csv_export() {
+    let mut stats = SessionStats::default();
+    let aces = hand(Rank::Ace, Suit::Spades, Rank::Ace, Suit::Hearts);
+    let king_ten = hand(Rank::King, Suit::Clubs, Rank::Ten, Suit::Clubs);
+    for (spot_type, hand, result) in [
+        (btn_open(), aces, AnswerResult::Correct),
+        (bb_vs_co(), king_ten, AnswerResult::Wrong),
+        (bb_vs_co(), aces, AnswerResult::FrequencyMistake),
+    ] {
+        stats.record(
+            spot_type,
+            hand,
+            UserAction::Raise,
+            UserAction::Raise,
+            result,
+            0,
+        );
+    }
+
+    let csv = stats.breakdown_to_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "kind,name,answered,score,accuracy",
+            "spot,BB vs CO Open,2,0.5,25",
+            "spot,Open from Button,1,1,100",
+            "hand,KTs,1,0,0",
+            "hand,AA,2,1.5,75",
+        ]
+    );
+    assert_eq!(
+        SessionStats::default().breakdown_to_csv(),
+        "kind,name,answered,score,accuracy\n"
+    );
+}
+
+#[test]
+fn test_clear_stats_keeps_lifetime_answers() {
+    let lifetime_path = temp_stats_path("clear_stats_lifetime");
+    let config = GameConfig {
+        allowed_spot_types: vec![btn_open()],
+        ..Default::default()
+    };
+    let aces = hand(Rank::Ace, Suit::Spades, Rank::Ace, Suit::Hearts);
+    let game = Game::new_with_seed(config.clone(), 1);
+    let mut session =
+        TrainerSession::with_lifetime_stats(game, config, lifetime_path.clone()).unwrap();
+    session.answer(btn_open(), aces, UserAction::Fold, 0);
+    session.answer(btn_open(), aces, UserAction::Fold, 0);
+
+    session.clear_stats();
+    assert_eq!(session.stats(), &SessionStats::default());
+    session.answer(btn_open(), aces, UserAction::Fold, 0);
+    assert_eq!(session.stats().totals.overall.answered, 1);
+    assert_eq!(session.finish().unwrap().overall.answered, 3);
+    std::fs::remove_file(&lifetime_path).unwrap();
+}
+
+#[test]
+fn test_auto_focus_deals_only_unmastered_spots() {
+    let mut config = GameConfig::default();
+    config
+        .unopened_raise_ranges
+        .insert(Position::BTN, "AA".parse().unwrap());
+    config
+        .bb_defense_raise_ranges
+        .insert((Position::CO, None), "AA".parse().unwrap());
+    config.allowed_spot_types = vec![btn_open(), bb_vs_co()];
+    let aces = hand(Rank::Ace, Suit::Spades, Rank::Ace, Suit::Hearts);
+    let game = Game::new_with_seed(config.clone(), 3);
+    let mut session = TrainerSession::new(game, config);
+    for _ in 0..30 {
+        session.answer(btn_open(), aces, UserAction::Raise, 0);
+    }
+    assert!(session.is_mastered(StatBucket::Spot(btn_open())));
+    assert_eq!(session.unmastered_spot_types(), vec![bb_vs_co()]);
+
+    session.set_auto_focus(true);
+    for _ in 0..30 {
+        assert_eq!(session.next_spot().unwrap().0, bb_vs_co());
+    }
+
+    // Once every spot is mastered, all of them are dealt again
+    for _ in 0..30 {
+        session.answer(bb_vs_co(), aces, UserAction::Raise, 0);
+    }
+    assert!(session.unmastered_spot_types().is_empty());
+    let served: Vec<SpotType> = (0..30).map(|_| session.next_spot().unwrap().0).collect();
+    assert!(served.contains(&btn_open()));
+    assert!(served.contains(&bb_vs_co()));
+}
@@ -0,0 +1,38 @@
+use preflop_trainer_core::{GameConfig, Position, SpotType, parse_config_str, stress_test};
+
+#[test]
+fn test_stress_test_default_config_is_consistent() {
+    let config = parse_config_str(include_str!("../../../ranges.toml.example")).unwrap();
+    let report = stress_test(&config, 42, 2000);
+    assert_eq!(report.spots_generated, 2000);
+    assert!(
+        report.unreachable_spot_types.is_empty(),
+        "Unreachable spot types: {:?}",
+        report.unreachable_spot_types
+    );
+    assert!(
+        report.inconsistencies.is_empty(),
+        "Inconsistencies: {:?}",
+        report.inconsistencies
+    );
+    assert!(report.is_ok());
+}
+
+#[test]
+fn test_stress_test_reports_empty_config() {
+    let report = stress_test(&GameConfig::default(), 42, 10);
+    assert!(!report.is_ok());
+    assert_eq!(report.spots_generated, 0);
+    assert_eq!(report.inconsistencies.len(), 1);
+}
+
+#[test]
+fn test_stress_test_flags_unreachable_spot_types() {
+    let mut config = parse_config_str(include_str!("../../../ranges.toml.example")).unwrap();
+    config.allowed_spot_types = vec![SpotType::open(Position::BTN)];
+    let report = stress_test(&config, 7, 0);
+    assert_eq!(
+        report.unreachable_spot_types,
+        vec![SpotType::open(Position::BTN)]
+    );
+}
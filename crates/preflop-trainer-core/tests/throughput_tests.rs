@@ -0,0 +1,108 @@
+use preflop_trainer_core::{Game, GameConfig, parse_config_str};
+use std::time::Instant;
+
+// Throughput floors for the spot generators, in spots per second, with the shipped
+// example config. Baseline measured on a single x86-64 core:
+//
+//   generator                       debug      release
+//   generate_random_notation        ~280,000   ~6,500,000
+//   generate_random_spot            ~4,400     ~47,000
+//   generate_random_spot_dual_rng   ~3,900     ~52,000
+//
+// The floors sit about 8x below that so a loaded CI machine still passes, while an
+// accidental quadratic in the selection or dealing path does not. Timing makes these
+// tests slow and machine-dependent, so they're ignored by default; run them with
+//
+//   cargo test --release --test throughput_tests -- --ignored
+const MIN_SPOTS_PER_SEC_DEBUG: f64 = 500.0;
+const MIN_SPOTS_PER_SEC_RELEASE: f64 = 6_000.0;
+// Picking a notation skips dealing, so its floors are higher
+const MIN_NOTATIONS_PER_SEC_DEBUG: f64 = 35_000.0;
+const MIN_NOTATIONS_PER_SEC_RELEASE: f64 = 800_000.0;
+const SPOTS: u32 = 2_000;
+
+fn example_config() -> GameConfig {
+    parse_config_str(include_str!("../../../ranges.toml.example")).unwrap()
+}
+
+fn min_spots_per_sec() -> f64 {
+    if cfg!(debug_assertions) {
+        MIN_SPOTS_PER_SEC_DEBUG
+    } else {
+        MIN_SPOTS_PER_SEC_RELEASE
+    }
+}
+
+fn min_notations_per_sec() -> f64 {
+    if cfg!(debug_assertions) {
+        MIN_NOTATIONS_PER_SEC_DEBUG
+    } else {
+        MIN_NOTATIONS_PER_SEC_RELEASE
+    }
+}
+
+fn spots_per_sec(mut generate: impl FnMut()) -> f64 {
+    let start = Instant::now();
+    for _ in 0..SPOTS {
+        generate();
+    }
+    SPOTS as f64 / start.elapsed().as_secs_f64()
+}
+
+#[test]
+#[ignore = "timing test; run with --ignored, ideally with --release"]
+fn test_generate_random_notation_throughput() {
+    for combo_weighting in [false, true] {
+        let mut config = example_config();
+        config.combo_weighting = combo_weighting;
+        let mut game = Game::new_with_seed(config, 1);
+
+        let rate = spots_per_sec(|| {
+            game.generate_random_notation().unwrap();
+        });
+        assert!(
+            rate >= min_notations_per_sec(),
+            "generate_random_notation (combo_weighting = {}): {:.0} spots/s, expected at least {:.0}",
+            combo_weighting,
+            rate,
+            min_notations_per_sec()
+        );
+    }
+}
+
+#[test]
+#[ignore = "timing test; run with --ignored, ideally with --release"]
+fn test_generate_random_spot_throughput() {
+    for combo_weighting in [false, true] {
+        let mut config = example_config();
+        config.combo_weighting = combo_weighting;
+        let mut game = Game::new_with_seed(config, 1);
+
+        let rate = spots_per_sec(|| {
+            game.generate_random_spot().unwrap();
+        });
+        assert!(
+            rate >= min_spots_per_sec(),
+            "generate_random_spot (combo_weighting = {}): {:.0} spots/s, expected at least {:.0}",
+            combo_weighting,
+            rate,
+            min_spots_per_sec()
+        );
+    }
+}
+
+#[test]
+#[ignore = "timing test; run with --ignored, ideally with --release"]
+fn test_generate_random_spot_dual_rng_throughput() {
+    let mut game = Game::new_with_seed(example_config(), 1);
+
+    let rate = spots_per_sec(|| {
+        game.generate_random_spot_dual_rng().unwrap();
+    });
+    assert!(
+        rate >= min_spots_per_sec(),
+        "generate_random_spot_dual_rng: {:.0} spots/s, expected at least {:.0}",
+        rate,
+        min_spots_per_sec()
+    );
+}
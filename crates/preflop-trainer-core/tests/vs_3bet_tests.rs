@@ -0,0 +1,56 @@
+use preflop_trainer_core::{
+    AnswerResult, Hand, SpotType, UserAction, check_answer, get_action_frequencies,
+    parse_config_str,
+};
+use std::str::FromStr;
+
+#[test]
+fn test_vs_3bet_needs_the_3bettor_behind_the_opener() {
+    assert!(
+        SpotType::from_str("Vs3Bet_CO_BTN")
+            .unwrap()
+            .is_facing_raise()
+    );
+    assert!(SpotType::from_str("Vs3Bet_BTN_CO").is_err());
+    assert!(SpotType::from_str("Vs3Bet_BB_SB").is_err());
+    assert!(
+        parse_config_str(
+            "[unopened_raise.CO]\nrange = \"AA\"\n\n[vs_3bet.BTN_UTG]\ncall_range = \"\"\nraise_range = \"AA\"\n"
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_vs_3bet_uses_stacked_frequencies() {
+    let config = parse_config_str(
+        "[unopened_raise.CO]\nrange = \"22+,A2s+\"\n\n[vs_3bet.CO_BTN]\ncall_range = \"JJ,TT,AQs:0.6\"\nraise_range = \"QQ+,AKs,AQs:0.3\"\n",
+    )
+    .unwrap();
+    let co_vs_btn = SpotType::from_str("Vs3Bet_CO_BTN").unwrap();
+    let hand = "AhQh".parse::<Hand>().unwrap();
+    let (raise, call, fold) = get_action_frequencies(&config, co_vs_btn, hand);
+    assert_eq!((raise, call), (0.3, 0.6));
+    assert!((fold - 0.1).abs() < 1e-6);
+
+    // 0-29 4-bet, 30-89 call, 90-99 fold
+    for (rng, correct) in [
+        (0, UserAction::Raise),
+        (29, UserAction::Raise),
+        (30, UserAction::Call),
+        (89, UserAction::Call),
+        (90, UserAction::Fold),
+        (99, UserAction::Fold),
+    ] {
+        assert_eq!(
+            check_answer(&config, co_vs_btn, hand, correct, rng),
+            AnswerResult::Correct,
+            "rng {}",
+            rng
+        );
+    }
+    assert_eq!(
+        check_answer(&config, co_vs_btn, hand, UserAction::Fold, 10),
+        AnswerResult::FrequencyMistake
+    );
+}
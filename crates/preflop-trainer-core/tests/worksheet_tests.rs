@@ -0,0 +1,82 @@
+use preflop_trainer_core::{
+    Game, GameConfig, Hand, Position, SpotType, export_worksheet, recommended_play,
+};
+
+fn worksheet_config() -> GameConfig {
+    let mut config = GameConfig::default();
+    config
+        .unopened_raise_ranges
+        .insert(Position::BTN, "22+,A2s+,KQo:0.5".parse().unwrap());
+    config.bb_defense_call_ranges.insert(
+        (Position::CO, None),
+        "QQ+:0.75,JJ,TT,99,88,77,66,55,44,33,22,A2s+"
+            .parse()
+            .unwrap(),
+    );
+    config
+        .bb_defense_raise_ranges
+        .insert((Position::CO, None), "QQ+:0.25".parse().unwrap());
+    config.allowed_spot_types = vec![
+        SpotType::open(Position::BTN),
+        SpotType::bb_defense(Position::CO),
+    ];
+    config
+}
+
+#[test]
+fn test_worksheet_has_questions_and_matching_answer_key() {
+    let config = worksheet_config();
+    let mut game = Game::new_with_seed(config.clone(), 7);
+    let spots: Vec<(SpotType, Hand)> = (0..12)
+        .map(|_| {
+            let (spot_type, hand, _) = game.generate_random_spot().unwrap();
+            (spot_type, hand)
+        })
+        .collect();
+
+    let worksheet = export_worksheet(&config, &spots);
+    let (questions, answers) = worksheet.split_once("## Answer Key").unwrap();
+
+    let question_lines: Vec<&str> = questions
+        .lines()
+        .filter(|line| line.contains("Answer: ________"))
+        .collect();
+    assert_eq!(question_lines.len(), spots.len());
+
+    let answer_lines: Vec<&str> = answers.lines().filter(|line| !line.is_empty()).collect();
+    assert_eq!(answer_lines.len(), spots.len());
+    for (i, (spot_type, hand)) in spots.iter().enumerate() {
+        assert!(question_lines[i].starts_with(&format!("{}. {}: ", i + 1, spot_type)));
+        assert_eq!(
+            answer_lines[i],
+            format!(
+                "{}. {}",
+                i + 1,
+                recommended_play(&config, *spot_type, *hand)
+            )
+        );
+    }
+}
+
+#[test]
+fn test_recommended_play_pure_and_mixed() {
+    let config = worksheet_config();
+    let btn_open = SpotType::open(Position::BTN);
+    let co_defense = SpotType::bb_defense(Position::CO);
+    let hand = |notation: &str| {
+        let hn = notation.parse().unwrap();
+        Game::new(config.clone()).deal_notation_exact(hn).unwrap()
+    };
+
+    assert_eq!(recommended_play(&config, btn_open, hand("AA")), "Raise");
+    assert_eq!(recommended_play(&config, btn_open, hand("72o")), "Fold");
+    assert_eq!(
+        recommended_play(&config, btn_open, hand("KQo")),
+        "Raise 50%, Fold 50%"
+    );
+    assert_eq!(recommended_play(&config, co_defense, hand("A5s")), "Call");
+    assert_eq!(
+        recommended_play(&config, co_defense, hand("KK")),
+        "Raise 25%, Call 75%"
+    );
+}
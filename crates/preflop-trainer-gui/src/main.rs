@@ -3,8 +3,12 @@ use iced::{
     alignment::{self, Horizontal},
     border::Border,
     executor, theme,
-    widget::{Button, Svg, column, container, row, text},
+    widget::{Button, Column, Svg, checkbox, column, container, row, scrollable, text},
 };
+use std::collections::{HashMap, VecDeque};
+
+const HISTORY_LEN: usize = 10;
+const SPEED_ROUND_SECS: u64 = 60;
 // Embed the `assets/cards` directory so the binary can render cards without external assets.
 
 // `include_dir!` paths are relative to the crate root (where Cargo.toml is),
@@ -28,8 +32,112 @@ static SUIT_S_SVG: &[u8] = include_bytes!(concat!(
     "/../../assets/cards/suit_s.svg"
 ));
 
+// Embedded SVG for a suit. Match directly on the suit enum so we reliably use embedded bytes.
+fn suit_svg_bytes(suit: preflop_trainer_core::Suit) -> &'static [u8] {
+    match suit {
+        preflop_trainer_core::Suit::Clubs => SUIT_C_SVG,
+        preflop_trainer_core::Suit::Diamonds => SUIT_D_SVG,
+        preflop_trainer_core::Suit::Hearts => SUIT_H_SVG,
+        preflop_trainer_core::Suit::Spades => SUIT_S_SVG,
+    }
+}
+
+// Only hand bytes to the SVG backend when they look like an SVG document; otherwise
+// the card would silently render without a suit.
+fn suit_svg_handle(suit: preflop_trainer_core::Suit) -> Option<iced::widget::svg::Handle> {
+    let bytes = suit_svg_bytes(suit);
+    let is_svg = std::str::from_utf8(bytes).is_ok_and(|svg| svg.contains("<svg"));
+    is_svg.then(|| iced::widget::svg::Handle::from_memory(bytes.to_vec()))
+}
+
+// The suit pip of a card: the embedded SVG, or a Unicode glyph if it can't be used.
+fn suit_element<'a>(
+    suit: preflop_trainer_core::Suit,
+    color: Color,
+    width: f32,
+    height: f32,
+) -> Element<'a, Message> {
+    match suit_svg_handle(suit) {
+        Some(handle) => Svg::new(handle)
+            .width(Length::Fixed(width))
+            .height(Length::Fixed(height))
+            .style(theme::Svg::Custom(Box::new(SuitTint(color))))
+            .into(),
+        None => text(suit.to_symbol())
+            .size(height as u16)
+            .horizontal_alignment(Horizontal::Center)
+            .style(theme::Text::Color(color))
+            .into(),
+    }
+}
+
+// The embedded SVGs carry their own light-card fill; on the dark palette they're drawn
+// in the lightened suit color instead.
+struct SuitTint(Color);
+
+impl iced::widget::svg::StyleSheet for SuitTint {
+    type Style = Theme;
+
+    fn appearance(&self, style: &Self::Style) -> iced::widget::svg::Appearance {
+        iced::widget::svg::Appearance {
+            color: style.extended_palette().is_dark.then_some(self.0),
+        }
+    }
+}
+
+// Color of a card's rank and pip under the configured theme. On dark cards every suit
+// is lifted towards white so black spades and clubs stay legible.
+fn card_suit_color(
+    theme: &preflop_trainer_core::ThemeConfig,
+    suit: preflop_trainer_core::Suit,
+    dark: bool,
+) -> Color {
+    let rgb = theme.suit_color(suit);
+    let color = Color::from_rgb8(rgb.r, rgb.g, rgb.b);
+    if dark {
+        let lift = |channel: f32| 0.45 + 0.55 * channel;
+        Color::from_rgb(lift(color.r), lift(color.g), lift(color.b))
+    } else {
+        color
+    }
+}
+
+// GUI preferences kept across restarts, in gui.toml next to the lifetime stats.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+struct GuiSettings {
+    #[serde(default)]
+    dark: bool,
+    // Spots switched off on the settings screen. Spots added to the config later start
+    // out enabled.
+    #[serde(default)]
+    disabled_spot_types: Vec<preflop_trainer_core::SpotType>,
+}
+
+impl GuiSettings {
+    fn default_path() -> Option<std::path::PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("preflop-trainer").join("gui.toml"))
+    }
+
+    // A missing or unreadable file gives the defaults, like the lifetime stats.
+    fn load(path: &std::path::Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, path: &std::path::Path) -> std::io::Result<()> {
+        let contents = toml::to_string(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, contents)
+    }
+}
+
 pub fn main() -> iced::Result {
-    PreflopTrainerGui::run(iced::Settings {
+    TrainerApp::run(iced::Settings {
         window: iced::window::Settings {
             size: iced::Size::new(600.0, 720.0), // Increased height for feedback
             resizable: false,
@@ -41,15 +149,174 @@ pub fn main() -> iced::Result {
 
 #[derive(Debug, Clone)]
 struct PreflopTrainerGui {
-    game: preflop_trainer_core::Game,
+    session: preflop_trainer_core::TrainerSession,
     current_spot_type: preflop_trainer_core::SpotType,
     current_hand: preflop_trainer_core::Hand,
     mixed_strategy_rng_value: u8,
     config: preflop_trainer_core::GameConfig,
     previous_hand_info: Option<PreviousHandInfo>,
+    // The last HISTORY_LEN answered hands, newest first
+    history: VecDeque<PreviousHandInfo>,
+    wrong_only: bool,
     correct_answers: f32,
     total_questions: u32,
     game_ended: bool,
+    editor: Option<RangeEditor>,
+    // Hiding the RNG until after the answer keeps it from tipping off mixed decisions
+    show_rng: bool,
+    show_spot_description: bool,
+    // Result of the last finished speed round, shown on the game over screen
+    speed_round_report: Option<preflop_trainer_core::SpeedRoundReport>,
+    // Why no further spot could be dealt, shown on the game over screen
+    spot_error: Option<String>,
+    dark: bool,
+    // Where the theme and spot choices are saved; None keeps them for this run only
+    settings_path: Option<std::path::PathBuf>,
+    screen: Screen,
+    // Spots from the config's allowed_spot_types that aren't drilled. `config` keeps
+    // them all; the session deals from the rest.
+    disabled_spot_types: Vec<preflop_trainer_core::SpotType>,
+}
+
+// Which screen the trainer shows besides the game over screen and the range editor
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+enum Screen {
+    #[default]
+    Play,
+    Settings,
+}
+
+// Text for the RNG line on the table.
+fn rng_label(rng_value: u8, show_rng: bool) -> String {
+    if show_rng {
+        format!("RNG: {}", rng_value)
+    } else {
+        "RNG: hidden".to_string()
+    }
+}
+
+// The bet chip on the villain's seat: the open (with its size when the spot has one)
+// in defense and squeeze spots, the 3-bet when hero opened. None for every other seat.
+fn seat_action_badge(
+    spot_type: preflop_trainer_core::SpotType,
+    seat_label: &str,
+) -> Option<String> {
+    let (villain_position, action) = match spot_type {
+        preflop_trainer_core::SpotType::Open { .. }
+        | preflop_trainer_core::SpotType::PushFold { .. }
+        | preflop_trainer_core::SpotType::SbLimp => {
+            return None;
+        }
+        preflop_trainer_core::SpotType::BBDefense {
+            opener_position,
+            open_size: Some(open_size),
+            ..
+        } => (opener_position, format!("Raise {}", open_size)),
+        preflop_trainer_core::SpotType::BBDefense {
+            opener_position, ..
+        }
+        | preflop_trainer_core::SpotType::Defense {
+            opener_position, ..
+        }
+        | preflop_trainer_core::SpotType::Squeeze {
+            opener_position, ..
+        } => (opener_position, "Raise".to_string()),
+        preflop_trainer_core::SpotType::Vs3Bet {
+            threebettor_position,
+            ..
+        } => (threebettor_position, "3-Bet".to_string()),
+    };
+    (villain_position.to_string() == seat_label).then_some(action)
+}
+
+// What went wrong loading ranges.toml, worded for the player
+fn config_error_message(err: &preflop_trainer_core::ConfigError) -> String {
+    match err {
+        preflop_trainer_core::ConfigError::Io(e) => format!("Couldn't read ranges.toml: {}", e),
+        preflop_trainer_core::ConfigError::TomlParse(e) => {
+            format!("ranges.toml isn't valid TOML: {}", e)
+        }
+        preflop_trainer_core::ConfigError::TomlSerialize(e) => {
+            format!("Couldn't write the ranges as TOML: {}", e)
+        }
+        preflop_trainer_core::ConfigError::UnknownPosition(position) => {
+            let known: Vec<&str> = preflop_trainer_core::Position::VALUES
+                .into_iter()
+                .map(preflop_trainer_core::position_config_str)
+                .collect();
+            let (last, rest) = known.split_last().expect("Position::VALUES isn't empty");
+            format!(
+                "ranges.toml names an unknown position: {} (use {} or {})",
+                position,
+                rest.join(", "),
+                last
+            )
+        }
+        preflop_trainer_core::ConfigError::UnknownSpotType(msg) => {
+            format!(
+                "ranges.toml has an unknown spot type in allowed_spot_types: {}",
+                msg
+            )
+        }
+        preflop_trainer_core::ConfigError::InvalidRange(e) => {
+            format!("ranges.toml has a range that doesn't parse: {}", e)
+        }
+        preflop_trainer_core::ConfigError::Invalid(msg) => {
+            format!("ranges.toml is invalid: {}", msg)
+        }
+    }
+}
+
+// The seat labels around the table, in preflop order, matching `Position`'s Display
+fn seat_labels(table_size: preflop_trainer_core::TableSize) -> Vec<String> {
+    table_size
+        .positions()
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+// Seats shrink so a full 9-max table still fits the 600px window
+fn seat_width(seat_count: usize) -> f32 {
+    let spacing = 10.0 * (seat_count.saturating_sub(1)) as f32;
+    ((560.0 - spacing) / seat_count.max(1) as f32).min(80.0)
+}
+
+// State of the "build your range" editor: the frequency of every cell that is in range.
+#[derive(Debug, Clone, Default)]
+struct RangeEditor {
+    range: preflop_trainer_core::RangeTable,
+    status: Option<String>,
+}
+
+impl RangeEditor {
+    // Next frequency for a cell: off -> 0 -> 0.5 -> 1.0 -> off. A cell at 0 is listed
+    // as never played ("72o:0") rather than left out. Frequencies imported from
+    // elsewhere snap up to the next step.
+    fn next_frequency(current: Option<f32>) -> Option<f32> {
+        match current {
+            None => Some(0.0),
+            Some(frequency) if frequency < 0.5 => Some(0.5),
+            Some(frequency) if frequency < 1.0 => Some(1.0),
+            Some(_) => None,
+        }
+    }
+
+    fn cycle_cell(&mut self, hand_notation: preflop_trainer_core::HandNotation) {
+        match Self::next_frequency(self.range.get(hand_notation)) {
+            Some(frequency) => {
+                self.range.insert(hand_notation, frequency);
+            }
+            None => {
+                self.range.remove(hand_notation);
+            }
+        }
+        self.status = None;
+    }
+
+    fn range_string(&self) -> String {
+        preflop_trainer_core::to_range_string(&self.range)
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -57,8 +324,7 @@ struct PreviousHandInfo {
     hand: preflop_trainer_core::Hand,
     spot_type: preflop_trainer_core::SpotType,
     user_action: preflop_trainer_core::UserAction,
-    rng_value: u8,
-    result: preflop_trainer_core::AnswerResult,
+    feedback: preflop_trainer_core::AnswerFeedback,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -66,91 +332,171 @@ enum Message {
     Raise,
     Fold,
     Call,
+    Limp,
     EndGame,
+    ToggleEditor,
+    CycleCell(preflop_trainer_core::HandNotation),
+    CopyEditorRange,
+    SaveEditorRange,
+    ToggleRng,
+    ToggleSpotDescription,
+    ToggleWrongOnly,
+    StartSpeedRound,
+    SpeedRoundTick,
+    ToggleTheme,
+    ToggleSettings,
+    ToggleSpot(preflop_trainer_core::SpotType),
+    Undo,
+}
+
+// The trainer, or why it couldn't start, e.g. a ranges.toml that doesn't load
+#[derive(Debug)]
+enum TrainerApp {
+    Training(Box<PreflopTrainerGui>),
+    Failed(String),
 }
 
-impl Application for PreflopTrainerGui {
+impl Application for TrainerApp {
     type Message = Message;
     type Theme = Theme;
     type Executor = executor::Default;
     type Flags = ();
 
     fn new(_flags: ()) -> (Self, Command<Self::Message>) {
-        let config =
-            preflop_trainer_core::load_config().expect("Failed to load or parse ranges.toml");
-
-        let mut game = preflop_trainer_core::Game::new(config.clone());
-        let (spot_type, hand, rng_value) = game
-            .generate_random_spot()
-            .expect("Failed to generate initial spot");
-
-        (
-            Self {
-                game,
-                current_spot_type: spot_type,
-                current_hand: hand,
-                mixed_strategy_rng_value: rng_value,
-                config,
-                previous_hand_info: None,
-                correct_answers: 0.0,
-                total_questions: 0,
-                game_ended: false,
-            },
-            Command::none(),
-        )
+        let app = match PreflopTrainerGui::start() {
+            Ok(gui) => TrainerApp::Training(Box::new(gui)),
+            Err(message) => TrainerApp::Failed(message),
+        };
+        (app, Command::none())
     }
 
     fn title(&self) -> String {
         String::from("Preflop Trainer GUI")
     }
 
+    fn theme(&self) -> Theme {
+        match self {
+            TrainerApp::Training(gui) => gui.theme(),
+            TrainerApp::Failed(_) => Theme::Light,
+        }
+    }
+
     fn update(&mut self, message: Self::Message) -> Command<Self::Message> {
+        match self {
+            TrainerApp::Training(gui) => gui.update(message),
+            TrainerApp::Failed(_) => Command::none(),
+        }
+    }
+
+    fn subscription(&self) -> iced::Subscription<Self::Message> {
+        match self {
+            TrainerApp::Training(gui) => gui.subscription(),
+            TrainerApp::Failed(_) => iced::Subscription::none(),
+        }
+    }
+
+    fn view(&self) -> Element<'_, Self::Message> {
+        match self {
+            TrainerApp::Training(gui) => gui.view(),
+            TrainerApp::Failed(message) => container(
+                column![
+                    text("Can't start the trainer").size(30),
+                    text(message).size(18),
+                    text("Fix ranges.toml and start the trainer again.").size(16),
+                ]
+                .spacing(20)
+                .align_items(alignment::Horizontal::Center.into()),
+            )
+            .padding(20)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .into(),
+        }
+    }
+}
+
+impl PreflopTrainerGui {
+    // Loads ranges.toml and the saved GUI settings and deals the first spot, or says
+    // what went wrong so the window can show it
+    fn start() -> Result<Self, String> {
+        let config = preflop_trainer_core::load_config().map_err(|e| config_error_message(&e))?;
+        let mut gui =
+            Self::from_config(config).map_err(|e| format!("Can't start training: {}", e))?;
+        gui.settings_path = GuiSettings::default_path();
+        if let Some(path) = &gui.settings_path {
+            let settings = GuiSettings::load(path);
+            gui.dark = settings.dark;
+            gui.set_disabled_spot_types(settings.disabled_spot_types);
+        }
+        Ok(gui)
+    }
+
+    fn theme(&self) -> Theme {
+        if self.dark { Theme::Dark } else { Theme::Light }
+    }
+
+    fn update(&mut self, message: Message) -> Command<Message> {
         if self.game_ended && message != Message::EndGame {
             return Command::none();
         }
 
         match message {
-            Message::Raise | Message::Fold | Message::Call => {
+            Message::Raise | Message::Fold | Message::Call | Message::Limp
+                if self.editor.is_some() || self.screen == Screen::Settings => {}
+            Message::Raise | Message::Fold | Message::Call | Message::Limp => {
                 let user_action = match message {
                     Message::Raise => preflop_trainer_core::UserAction::Raise,
                     Message::Fold => preflop_trainer_core::UserAction::Fold,
                     Message::Call => preflop_trainer_core::UserAction::Call,
+                    Message::Limp => preflop_trainer_core::UserAction::Limp,
                     _ => unreachable!(),
                 };
 
-                let result = preflop_trainer_core::check_answer(
-                    &self.config,
+                let feedback = self.session.answer_detailed(
                     self.current_spot_type,
                     self.current_hand,
                     user_action,
                     self.mixed_strategy_rng_value,
                 );
 
-                self.previous_hand_info = Some(PreviousHandInfo {
+                let info = PreviousHandInfo {
                     hand: self.current_hand,
                     spot_type: self.current_spot_type,
                     user_action,
-                    rng_value: self.mixed_strategy_rng_value,
-                    result,
-                });
+                    feedback,
+                };
+                self.previous_hand_info = Some(info);
+                self.history.push_front(info);
+                self.history.truncate(HISTORY_LEN);
 
                 self.total_questions += 1;
-                match result {
-                    preflop_trainer_core::AnswerResult::Correct => self.correct_answers += 1.0,
-                    preflop_trainer_core::AnswerResult::FrequencyMistake => {
-                        self.correct_answers += 0.5
-                    }
-                    preflop_trainer_core::AnswerResult::Wrong => {}
-                }
+                self.correct_answers += preflop_trainer_core::answer_credit(
+                    feedback.result,
+                    self.config.strict_scoring,
+                );
 
                 // Immediately generate the NEXT hand
-                let (spot_type, hand, rng_value) = self
-                    .game
-                    .generate_random_spot()
-                    .expect("Failed to generate next spot");
-                self.current_spot_type = spot_type;
-                self.current_hand = hand;
-                self.mixed_strategy_rng_value = rng_value;
+                self.deal_next_spot();
+            }
+
+            Message::Undo => {
+                if let Some(info) = self.previous_hand_info
+                    && self.session.undo_last()
+                {
+                    // Put the answered spot back rather than the one dealt after it
+                    self.current_spot_type = info.spot_type;
+                    self.current_hand = info.hand;
+                    self.mixed_strategy_rng_value = info.feedback.rng_value;
+                    self.total_questions -= 1;
+                    self.correct_answers -= preflop_trainer_core::answer_credit(
+                        info.feedback.result,
+                        self.config.strict_scoring,
+                    );
+                    self.history.pop_front();
+                    self.previous_hand_info = self.history.front().copied();
+                }
             }
 
             Message::EndGame => {
@@ -159,24 +505,123 @@ impl Application for PreflopTrainerGui {
                     self.game_ended = false;
                     self.total_questions = 0;
                     self.correct_answers = 0.0;
-                    let (spot_type, hand, rng_value) = self
-                        .game
-                        .generate_random_spot()
-                        .expect("Failed to generate next spot");
-                    self.current_spot_type = spot_type;
-                    self.current_hand = hand;
-                    self.mixed_strategy_rng_value = rng_value;
                     self.previous_hand_info = None;
+                    self.history.clear();
+                    self.speed_round_report = None;
+                    self.spot_error = None;
+                    self.session.clear_stats();
+                    self.deal_next_spot();
                 } else {
                     // End the game
                     self.game_ended = true;
+                    self.speed_round_report = self.session.end_speed_round();
+                }
+            }
+
+            Message::StartSpeedRound => {
+                self.session
+                    .speed_round(std::time::Duration::from_secs(SPEED_ROUND_SECS));
+            }
+
+            Message::SpeedRoundTick => {
+                if self.session.speed_round_over() {
+                    self.game_ended = true;
+                    self.speed_round_report = self.session.end_speed_round();
+                }
+            }
+
+            Message::ToggleEditor => {
+                self.editor = match self.editor {
+                    Some(_) => None,
+                    None => Some(RangeEditor::default()),
+                };
+            }
+
+            Message::ToggleRng => {
+                self.show_rng = !self.show_rng;
+            }
+
+            Message::ToggleWrongOnly => {
+                self.wrong_only = !self.wrong_only;
+            }
+
+            Message::ToggleSpotDescription => {
+                self.show_spot_description = !self.show_spot_description;
+            }
+
+            Message::ToggleTheme => {
+                self.dark = !self.dark;
+                self.save_settings();
+            }
+
+            Message::ToggleSettings => {
+                self.screen = match self.screen {
+                    Screen::Play => Screen::Settings,
+                    Screen::Settings => Screen::Play,
+                };
+            }
+
+            Message::ToggleSpot(spot_type) => {
+                let mut disabled = self.disabled_spot_types.clone();
+                match disabled.iter().position(|&s| s == spot_type) {
+                    Some(index) => {
+                        disabled.remove(index);
+                    }
+                    None => disabled.push(spot_type),
+                }
+                if self.set_disabled_spot_types(disabled) {
+                    self.save_settings();
+                }
+            }
+
+            Message::CycleCell(hand_notation) => {
+                if let Some(editor) = &mut self.editor {
+                    editor.cycle_cell(hand_notation);
+                }
+            }
+
+            Message::CopyEditorRange => {
+                if let Some(editor) = &mut self.editor {
+                    editor.status = Some("Range copied to clipboard.".to_string());
+                    return iced::clipboard::write(editor.range_string());
+                }
+            }
+
+            Message::SaveEditorRange => {
+                if let Some(editor) = &mut self.editor {
+                    let result = preflop_trainer_core::find_or_create_config().and_then(|path| {
+                        let export_path = path.with_file_name("edited_range.txt");
+                        std::fs::write(&export_path, editor.range_string())?;
+                        Ok(export_path)
+                    });
+                    editor.status = Some(match result {
+                        Ok(path) => format!("Range saved to {}", path.display()),
+                        Err(e) => format!("Failed to save range: {}", e),
+                    });
                 }
             }
         }
         Command::none()
     }
 
-    fn view(&self) -> Element<'_, Self::Message> {
+    fn subscription(&self) -> iced::Subscription<Message> {
+        if self.session.speed_round_remaining().is_some() {
+            iced::time::every(std::time::Duration::from_millis(250))
+                .map(|_| Message::SpeedRoundTick)
+        } else {
+            iced::Subscription::none()
+        }
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        if let Some(editor) = &self.editor {
+            return self.view_editor(editor);
+        }
+
+        if self.screen == Screen::Settings {
+            return self.view_settings();
+        }
+
         if self.game_ended {
             let percentage = if self.total_questions > 0 {
                 (self.correct_answers / self.total_questions as f32) * 100.0
@@ -184,16 +629,27 @@ impl Application for PreflopTrainerGui {
                 0.0
             };
 
-            return column![
+            let mut game_over = column![
                 text("Game Over!").size(50),
                 text(format!("Total Questions: {}", self.total_questions)).size(30),
                 text(format!("Correct Answers: {}", self.correct_answers)).size(30),
                 text(format!("Score: {:.2}%", percentage)).size(30),
-                Button::new(text("Play Again").size(25)).on_press(Message::EndGame),
-            ]
-            .spacing(20)
-            .align_items(alignment::Horizontal::Center.into())
-            .into();
+            ];
+            if let Some(report) = &self.speed_round_report {
+                game_over =
+                    game_over.push(text(format!("Speed round: {}", report.summary())).size(20));
+            }
+            if let Some(error) = &self.spot_error {
+                game_over = game_over.push(text(format!("No more spots: {}", error)).size(20));
+            }
+            game_over = game_over
+                .push(self.view_breakdown())
+                .push(Button::new(text("Play Again").size(25)).on_press(Message::EndGame))
+                .spacing(20)
+                .padding(20)
+                .width(Length::Fill)
+                .align_items(alignment::Horizontal::Center.into());
+            return scrollable(game_over).into();
         }
 
         let render_card =
@@ -205,12 +661,7 @@ impl Application for PreflopTrainerGui {
                 let card_height = 100.0 * size_multiplier;
                 let padding_val = (5.0 * size_multiplier) as u16;
 
-                let suit_color = match card.suit {
-                    preflop_trainer_core::Suit::Clubs => Color::from_rgb(0.0, 0.5, 0.0),
-                    preflop_trainer_core::Suit::Diamonds => Color::from_rgb(0.0, 0.0, 1.0),
-                    preflop_trainer_core::Suit::Hearts => Color::from_rgb(1.0, 0.0, 0.0),
-                    preflop_trainer_core::Suit::Spades => Color::from_rgb(0.0, 0.0, 0.0),
-                };
+                let suit_color = card_suit_color(&self.config.theme, card.suit, self.dark);
 
                 container(
                     column![
@@ -218,26 +669,7 @@ impl Application for PreflopTrainerGui {
                             .size(rank_size)
                             .horizontal_alignment(Horizontal::Center)
                             .style(theme::Text::Color(suit_color)),
-                        {
-                            // Match directly on the suit enum so we reliably use embedded bytes.
-                            let handle = match card.suit {
-                                preflop_trainer_core::Suit::Clubs => {
-                                    iced::widget::svg::Handle::from_memory(SUIT_C_SVG.to_vec())
-                                }
-                                preflop_trainer_core::Suit::Diamonds => {
-                                    iced::widget::svg::Handle::from_memory(SUIT_D_SVG.to_vec())
-                                }
-                                preflop_trainer_core::Suit::Hearts => {
-                                    iced::widget::svg::Handle::from_memory(SUIT_H_SVG.to_vec())
-                                }
-                                preflop_trainer_core::Suit::Spades => {
-                                    iced::widget::svg::Handle::from_memory(SUIT_S_SVG.to_vec())
-                                }
-                            };
-                            Svg::new(handle)
-                        }
-                        .width(Length::Fixed(suit_svg_width))
-                        .height(Length::Fixed(suit_svg_height)),
+                        suit_element(card.suit, suit_color, suit_svg_width, suit_svg_height),
                     ]
                     .align_items(alignment::Horizontal::Center.into())
                     .padding(padding_val),
@@ -252,18 +684,61 @@ impl Application for PreflopTrainerGui {
                 .into()
             };
 
-        let position_labels = ["UTG", "MP", "CO", "Button", "Small Blind", "Big Blind"];
+        let position_labels = seat_labels(self.config.table_size);
+        let seat_width = seat_width(position_labels.len());
+        let seat_text_size = if seat_width < 80.0 { 12 } else { 16 };
         let mut positions_layout = row![].spacing(10).width(Length::Fill);
 
-        let (user_pos_str, opener_pos_str_option) = match &self.current_spot_type {
-            preflop_trainer_core::SpotType::Open { position } => (format!("{}", position), None),
-            preflop_trainer_core::SpotType::BBDefense { opener_position } => (
+        let (user_pos_str, opener_pos_str_option, open_size_option) = match &self.current_spot_type
+        {
+            preflop_trainer_core::SpotType::Open { position, .. }
+            | preflop_trainer_core::SpotType::PushFold { position, .. } => {
+                (format!("{}", position), None, None)
+            }
+            preflop_trainer_core::SpotType::SbLimp => (
+                format!("{}", preflop_trainer_core::Position::SB),
+                None,
+                None,
+            ),
+            preflop_trainer_core::SpotType::BBDefense {
+                opener_position,
+                open_size,
+                ..
+            } => (
+                "Big Blind".to_string(),
+                Some(format!("{}", opener_position)),
+                *open_size,
+            ),
+            // In a squeeze, the opener is highlighted; the caller's seat isn't shown
+            preflop_trainer_core::SpotType::Squeeze {
+                opener_position, ..
+            } => (
                 "Big Blind".to_string(),
                 Some(format!("{}", opener_position)),
+                None,
+            ),
+            preflop_trainer_core::SpotType::Defense {
+                hero_position,
+                opener_position,
+                ..
+            } => (
+                format!("{}", hero_position),
+                Some(format!("{}", opener_position)),
+                None,
+            ),
+            // Hero is the opener; highlight the 3-bettor as the villain
+            preflop_trainer_core::SpotType::Vs3Bet {
+                opener_position,
+                threebettor_position,
+                ..
+            } => (
+                format!("{}", opener_position),
+                Some(format!("{}", threebettor_position)),
+                None,
             ),
         };
 
-        for &pos_label in position_labels.iter() {
+        for pos_label in position_labels.iter().map(String::as_str) {
             let style_type = if pos_label == user_pos_str.as_str() {
                 ContainerStyleType::SeatUser
             } else if let Some(opener_str) = &opener_pos_str_option {
@@ -276,9 +751,24 @@ impl Application for PreflopTrainerGui {
                 ContainerStyleType::SeatNormal
             };
 
-            let seat_content = container(text(pos_label))
-                .width(Length::Fixed(80.0))
-                .height(Length::Fixed(40.0))
+            let mut seat_label = column![
+                text(pos_label)
+                    .size(seat_text_size)
+                    .horizontal_alignment(Horizontal::Center)
+            ]
+            .spacing(2)
+            .align_items(alignment::Horizontal::Center.into());
+            if let Some(badge) = seat_action_badge(self.current_spot_type, pos_label) {
+                seat_label =
+                    seat_label.push(container(text(badge).size(12)).padding([1, 6]).style(
+                        theme::Container::Custom(Box::new(MyContainerStyle::new(
+                            ContainerStyleType::ActionChip,
+                        ))),
+                    ));
+            }
+            let seat_content = container(seat_label)
+                .width(Length::Fixed(seat_width))
+                .height(Length::Fixed(50.0))
                 .center_x()
                 .center_y()
                 .style(theme::Container::Custom(Box::new(MyContainerStyle::new(
@@ -287,30 +777,42 @@ impl Application for PreflopTrainerGui {
             positions_layout = positions_layout.push(seat_content);
         }
 
-        let poker_table = container(
-            column![
-                positions_layout,
+        let mut table_content = column![positions_layout]
+            .spacing(20)
+            .align_items(alignment::Horizontal::Center.into());
+        if let Some(open_size) = open_size_option {
+            table_content = table_content.push(text(format!("Open size: {}", open_size)).size(18));
+        }
+        if let Some(stack_depth) = self.current_spot_type.stack_depth() {
+            table_content = table_content.push(text(format!("Stack: {}", stack_depth)).size(18));
+        }
+        table_content = table_content
+            .push(
                 row![
                     render_card(&self.current_hand.card1, 1.0),
                     render_card(&self.current_hand.card2, 1.0),
                 ]
                 .spacing(10)
                 .align_items(alignment::Vertical::Center.into()),
-                text(format!("RNG: {}", self.mixed_strategy_rng_value)).size(20),
-            ]
-            .spacing(20)
-            .align_items(alignment::Horizontal::Center.into()),
-        )
-        .width(Length::Fixed(600.0))
-        .height(Length::Fixed(300.0))
-        .center_x()
-        .center_y()
-        .style(theme::Container::Custom(Box::new(MyContainerStyle::new(
-            ContainerStyleType::Table,
-        ))));
+            )
+            .push(text(rng_label(self.mixed_strategy_rng_value, self.show_rng)).size(20));
 
+        let poker_table = container(table_content)
+            .width(Length::Fixed(600.0))
+            .height(Length::Fixed(300.0))
+            .center_x()
+            .center_y()
+            .style(theme::Container::Custom(Box::new(MyContainerStyle::new(
+                ContainerStyleType::Table,
+            ))));
+
+        // A raise in a push/fold spot is an all-in
+        let raise_label = match self.current_spot_type {
+            preflop_trainer_core::SpotType::PushFold { .. } => "Shove",
+            _ => "Raise",
+        };
         let raise_button = Button::new(
-            text("Raise")
+            text(raise_label)
                 .size(25)
                 .horizontal_alignment(Horizontal::Center),
         )
@@ -333,43 +835,54 @@ impl Application for PreflopTrainerGui {
         .on_press(Message::Call)
         .width(Length::Fixed(120.0))
         .padding(10);
+        let limp_button = Button::new(
+            text("Limp")
+                .size(25)
+                .horizontal_alignment(Horizontal::Center),
+        )
+        .on_press(Message::Limp)
+        .width(Length::Fixed(120.0))
+        .padding(10);
 
         let mut action_buttons = row![]
             .spacing(10)
             .align_items(alignment::Vertical::Center.into());
-        match self.current_spot_type {
-            preflop_trainer_core::SpotType::Open { .. } => {
-                action_buttons = action_buttons.push(raise_button).push(fold_button);
-            }
-            preflop_trainer_core::SpotType::BBDefense { .. } => {
-                action_buttons = action_buttons
-                    .push(raise_button)
-                    .push(call_button)
-                    .push(fold_button);
-            }
+        if self.current_spot_type.is_facing_raise() {
+            action_buttons = action_buttons
+                .push(raise_button)
+                .push(call_button)
+                .push(fold_button);
+        } else if self.current_spot_type == preflop_trainer_core::SpotType::SbLimp {
+            action_buttons = action_buttons
+                .push(raise_button)
+                .push(limp_button)
+                .push(fold_button);
+        } else {
+            action_buttons = action_buttons.push(raise_button).push(fold_button);
         }
 
         let mut main_content = column![poker_table, action_buttons]
             .spacing(20)
             .align_items(alignment::Horizontal::Center.into());
 
-        if let Some(info) = &self.previous_hand_info {
-            let (raise_freq, call_freq, fold_freq) = preflop_trainer_core::get_action_frequencies(
-                &self.config,
-                info.spot_type,
-                info.hand,
+        if self.show_spot_description {
+            main_content = main_content.push(
+                container(text(self.current_spot_type.description()).size(16))
+                    .width(Length::Fixed(600.0))
+                    .center_x(),
             );
+        }
 
-            let raise_threshold = (raise_freq * 100.0) as u8;
-            let call_threshold = raise_threshold.saturating_add((call_freq * 100.0) as u8);
+        if let Some(info) = &self.previous_hand_info {
+            let preflop_trainer_core::AnswerFeedback {
+                raise_freq,
+                call_freq,
+                fold_freq,
+                correct_action: correct_action_for_rng,
+                ..
+            } = info.feedback;
 
-            let correct_action_for_rng = if info.rng_value < raise_threshold {
-                preflop_trainer_core::UserAction::Raise
-            } else if info.rng_value < call_threshold {
-                preflop_trainer_core::UserAction::Call
-            } else {
-                preflop_trainer_core::UserAction::Fold
-            };
+            let passive_action = info.spot_type.passive_action();
 
             let render_feedback_button =
                 |action: preflop_trainer_core::UserAction, percentage: f32| {
@@ -377,7 +890,7 @@ impl Application for PreflopTrainerGui {
                         MyContainerStyle::new(ContainerStyleType::Feedback(FeedbackStyle::Neutral));
 
                     if info.user_action == action {
-                        style.style = match info.result {
+                        style.style = match info.feedback.result {
                             preflop_trainer_core::AnswerResult::Correct => {
                                 ContainerStyleType::Feedback(FeedbackStyle::Correct)
                             }
@@ -400,6 +913,7 @@ impl Application for PreflopTrainerGui {
                     let action_text = match action {
                         preflop_trainer_core::UserAction::Raise => "Raise",
                         preflop_trainer_core::UserAction::Call => "Call",
+                        preflop_trainer_core::UserAction::Limp => "Limp",
                         preflop_trainer_core::UserAction::Fold => "Fold",
                     };
 
@@ -424,36 +938,424 @@ impl Application for PreflopTrainerGui {
                     ContainerStyleType::Separator,
                 ))));
 
-            let previous_hand_summary = row![
+            let mut previous_hand_summary = row![
                 text("Previous Hand:").size(18),
                 text(format!("{}", info.spot_type)).size(18),
                 render_card(&info.hand.card1, 0.7),
                 render_card(&info.hand.card2, 0.7),
-            ]
-            .spacing(10)
-            .align_items(alignment::Vertical::Center.into());
+            ];
+            if !self.show_rng {
+                // Reveal the RNG once the hand has been answered
+                previous_hand_summary = previous_hand_summary
+                    .push(text(rng_label(info.feedback.rng_value, true)).size(18));
+            }
+            if self.session.can_undo() {
+                previous_hand_summary = previous_hand_summary
+                    .push(Button::new(text("Undo").size(16)).on_press(Message::Undo));
+            }
+            let previous_hand_summary = previous_hand_summary
+                .spacing(10)
+                .align_items(alignment::Vertical::Center.into());
 
             let feedback_row = row![
                 render_feedback_button(preflop_trainer_core::UserAction::Raise, raise_freq),
-                render_feedback_button(preflop_trainer_core::UserAction::Call, call_freq),
+                render_feedback_button(passive_action, call_freq),
                 render_feedback_button(preflop_trainer_core::UserAction::Fold, fold_freq),
             ]
             .spacing(10);
 
-            main_content = main_content.push(
-                column![separator, previous_hand_summary, feedback_row]
+            let range_grid = view_spot_range_grid(
+                &self.config,
+                info.spot_type,
+                preflop_trainer_core::HandNotation::from_hand(info.hand),
+                self.dark,
+            );
+
+            let mut previous_hand_column =
+                column![separator, previous_hand_summary, feedback_row, range_grid]
                     .spacing(10)
-                    .align_items(alignment::Horizontal::Center.into()),
+                    .align_items(alignment::Horizontal::Center.into());
+            if let Some(feedback) =
+                preflop_trainer_core::villain_range(&self.config, info.spot_type)
+                    .and_then(|villain| preflop_trainer_core::blocker_feedback(info.hand, villain))
+            {
+                previous_hand_column = previous_hand_column.push(text(feedback).size(16));
+            }
+            if let Some(note) = self
+                .config
+                .note_for(preflop_trainer_core::HandNotation::from_hand(info.hand))
+            {
+                previous_hand_column =
+                    previous_hand_column.push(text(format!("Note: {}", note)).size(16));
+            }
+
+            main_content = main_content.push(previous_hand_column);
+        }
+
+        let control_buttons = row![
+            Button::new(text("End Game").size(20)).on_press(Message::EndGame),
+            Button::new(text("Range Editor").size(20)).on_press(Message::ToggleEditor),
+            Button::new(
+                text(if self.show_rng {
+                    "Hide RNG"
+                } else {
+                    "Show RNG"
+                })
+                .size(20)
+            )
+            .on_press(Message::ToggleRng),
+            Button::new(text("Explain Spot").size(20)).on_press(Message::ToggleSpotDescription),
+        ]
+        .spacing(10);
+        let control_buttons = match self.session.speed_round_remaining() {
+            Some(remaining) => {
+                control_buttons.push(text(format!("{}s left", remaining.as_secs())).size(20))
+            }
+            None => control_buttons
+                .push(Button::new(text("Speed Round").size(20)).on_press(Message::StartSpeedRound)),
+        };
+
+        if !self.history.is_empty() {
+            main_content = main_content.push(self.view_history());
+        }
+
+        main_content = main_content.push(control_buttons).push(
+            row![
+                Button::new(text(if self.dark { "Light Mode" } else { "Dark Mode" }).size(16))
+                    .on_press(Message::ToggleTheme),
+                Button::new(text("Spots").size(16)).on_press(Message::ToggleSettings),
+            ]
+            .spacing(10),
+        );
+
+        // The range grid under the feedback doesn't fit the fixed window with the rest
+        scrollable(container(main_content).width(Length::Fill).center_x()).into()
+    }
+}
+
+impl PreflopTrainerGui {
+    fn from_config(
+        config: preflop_trainer_core::GameConfig,
+    ) -> Result<Self, preflop_trainer_core::CoreError> {
+        let mut session = preflop_trainer_core::TrainerSession::new(
+            preflop_trainer_core::Game::new(config.clone()),
+            config.clone(),
+        );
+        let (spot_type, hand, rng_value) = session.next_spot()?;
+
+        Ok(Self {
+            session,
+            current_spot_type: spot_type,
+            current_hand: hand,
+            mixed_strategy_rng_value: rng_value,
+            config,
+            previous_hand_info: None,
+            history: VecDeque::new(),
+            wrong_only: false,
+            correct_answers: 0.0,
+            total_questions: 0,
+            game_ended: false,
+            editor: None,
+            show_rng: true,
+            show_spot_description: false,
+            speed_round_report: None,
+            spot_error: None,
+            dark: false,
+            settings_path: None,
+            screen: Screen::Play,
+            disabled_spot_types: Vec::new(),
+        })
+    }
+
+    // Deals only the spots not in `disabled` from now on, moving on from the current
+    // spot if it's one of them. Returns false and changes nothing when that would leave
+    // no spot to deal. Disabled spots the config doesn't serve are dropped.
+    fn set_disabled_spot_types(
+        &mut self,
+        mut disabled: Vec<preflop_trainer_core::SpotType>,
+    ) -> bool {
+        disabled.retain(|spot_type| self.config.allowed_spot_types.contains(spot_type));
+        let mut config = self.config.clone();
+        config
+            .allowed_spot_types
+            .retain(|spot_type| !disabled.contains(spot_type));
+        if config.allowed_spot_types.is_empty() {
+            return false;
+        }
+        self.session.set_config(config);
+        self.disabled_spot_types = disabled;
+        if self.disabled_spot_types.contains(&self.current_spot_type) {
+            self.deal_next_spot();
+        }
+        true
+    }
+
+    fn save_settings(&self) {
+        let settings = GuiSettings {
+            dark: self.dark,
+            disabled_spot_types: self.disabled_spot_types.clone(),
+        };
+        if let Some(path) = &self.settings_path
+            && let Err(e) = settings.save(path)
+        {
+            eprintln!("Couldn't save settings to {}: {}", path.display(), e);
+        }
+    }
+
+    fn view_settings(&self) -> Element<'_, Message> {
+        let mut spots = Column::new().spacing(6);
+        for &spot_type in &self.config.allowed_spot_types {
+            let enabled = !self.disabled_spot_types.contains(&spot_type);
+            spots = spots.push(
+                checkbox(spot_type.to_string(), enabled)
+                    .on_toggle(move |_| Message::ToggleSpot(spot_type))
+                    .text_size(16),
+            );
+        }
+
+        column![
+            text("Spots to Drill").size(30),
+            text("At least one spot stays enabled.").size(14),
+            scrollable(spots).height(Length::Fixed(520.0)),
+            Button::new(text("Back").size(20)).on_press(Message::ToggleSettings),
+        ]
+        .spacing(10)
+        .padding(10)
+        .align_items(alignment::Horizontal::Center.into())
+        .into()
+    }
+
+    // Moves on to the next spot, or ends the game if the session can't deal one.
+    fn deal_next_spot(&mut self) {
+        match self.session.next_spot() {
+            Ok((spot_type, hand, rng_value)) => {
+                self.current_spot_type = spot_type;
+                self.current_hand = hand;
+                self.mixed_strategy_rng_value = rng_value;
+            }
+            Err(e) => {
+                self.spot_error = Some(e.to_string());
+                self.game_ended = true;
+            }
+        }
+    }
+
+    // History entries to list, newest first; with `wrong_only` just the misses.
+    fn visible_history(&self) -> Vec<&PreviousHandInfo> {
+        self.history
+            .iter()
+            .filter(|info| {
+                !self.wrong_only
+                    || info.feedback.result != preflop_trainer_core::AnswerResult::Correct
+            })
+            .collect()
+    }
+
+    // Accuracy per spot answered this game, then the hands that lost the most credit.
+    fn view_breakdown(&self) -> Element<'_, Message> {
+        let stats = self.session.stats();
+        let cell = |content: String, width: f32| text(content).size(16).width(Length::Fixed(width));
+
+        let mut spots = Column::new().spacing(4).push(row![
+            cell("Spot".to_string(), 260.0),
+            cell("Hands".to_string(), 80.0),
+            cell("Accuracy".to_string(), 90.0),
+            cell("Mastered".to_string(), 90.0),
+        ]);
+        for (spot_type, accuracy) in stats.accuracy_by_spot_type() {
+            let mastered = stats.is_mastered(preflop_trainer_core::StatBucket::Spot(spot_type));
+            spots = spots.push(row![
+                cell(spot_type.to_string(), 260.0),
+                cell(stats.by_spot_type[&spot_type].answered.to_string(), 80.0),
+                cell(format!("{:.0}%", accuracy), 90.0),
+                cell(if mastered { "Yes" } else { "" }.to_string(), 90.0),
+            ]);
+        }
+
+        let mut missed = Column::new().spacing(4).push(row![
+            cell("Most missed".to_string(), 260.0),
+            cell("Hands".to_string(), 80.0),
+            cell("Accuracy".to_string(), 90.0),
+        ]);
+        let missed_hands = stats.most_missed_hands(preflop_trainer_core::MOST_MISSED_HANDS_SHOWN);
+        if missed_hands.is_empty() {
+            missed = missed.push(cell("None, well played!".to_string(), 430.0));
+        }
+        for (hand_notation, totals) in missed_hands {
+            missed = missed.push(row![
+                cell(hand_notation.to_string(), 260.0),
+                cell(totals.answered.to_string(), 80.0),
+                cell(format!("{:.0}%", totals.accuracy()), 90.0),
+            ]);
+        }
+
+        column![spots, missed].spacing(20).into()
+    }
+
+    fn view_history(&self) -> Element<'_, Message> {
+        let mut entries = Column::new().spacing(2);
+        for info in self.visible_history() {
+            let result_text = match info.feedback.result {
+                preflop_trainer_core::AnswerResult::Correct => "Correct",
+                preflop_trainer_core::AnswerResult::Wrong => "Wrong",
+                preflop_trainer_core::AnswerResult::FrequencyMistake => "Frequency mistake",
+            };
+            entries = entries.push(
+                text(format!(
+                    "{}  {}  {:?}  {}",
+                    info.spot_type,
+                    preflop_trainer_core::HandNotation::from_hand(info.hand),
+                    info.user_action,
+                    result_text
+                ))
+                .size(14),
             );
         }
 
-        let control_buttons =
-            row![Button::new(text("End Game").size(20)).on_press(Message::EndGame),].spacing(20);
+        column![
+            row![
+                text("History").size(18),
+                Button::new(
+                    text(if self.wrong_only {
+                        "Show all"
+                    } else {
+                        "Mistakes only"
+                    })
+                    .size(14)
+                )
+                .on_press(Message::ToggleWrongOnly),
+            ]
+            .spacing(10)
+            .align_items(alignment::Vertical::Center.into()),
+            scrollable(entries).height(Length::Fixed(60.0)),
+        ]
+        .spacing(5)
+        .width(Length::Fixed(600.0))
+        .into()
+    }
+
+    fn view_editor<'a>(&self, editor: &'a RangeEditor) -> Element<'a, Message> {
+        let mut grid = Column::new().spacing(2);
+        for row_idx in 0..preflop_trainer_core::Rank::VALUES.len() {
+            let mut grid_row = row![].spacing(2);
+            for col_idx in 0..preflop_trainer_core::Rank::VALUES.len() {
+                let hand_notation =
+                    preflop_trainer_core::HandNotation::from_grid_position(row_idx, col_idx);
+                let frequency = editor.range.frequency(hand_notation);
+                // Cells listed at 0 are marked, since they color like the ones left out
+                let label = match editor.range.get(hand_notation) {
+                    Some(0.0) => format!("{}:0", hand_notation),
+                    _ => hand_notation.to_string(),
+                };
+                let cell = container(text(label).size(12))
+                    .width(Length::Fixed(40.0))
+                    .height(Length::Fixed(28.0))
+                    .center_x()
+                    .center_y()
+                    .style(theme::Container::Custom(Box::new(MyContainerStyle::new(
+                        ContainerStyleType::GridCell(frequency),
+                    ))));
+                grid_row = grid_row.push(
+                    Button::new(cell)
+                        .padding(0)
+                        .style(theme::Button::Text)
+                        .on_press(Message::CycleCell(hand_notation)),
+                );
+            }
+            grid = grid.push(grid_row);
+        }
+
+        let range_string = editor.range_string();
+        let mut content = column![
+            text("Range Editor").size(30),
+            text("Click a hand to cycle it through 0%, 50%, 100% and out of range.").size(14),
+            row![grid, view_grid_legend()].spacing(10),
+            text(if range_string.is_empty() {
+                "(empty range)".to_string()
+            } else {
+                range_string
+            })
+            .size(14),
+            row![
+                Button::new(text("Copy").size(20)).on_press(Message::CopyEditorRange),
+                Button::new(text("Save").size(20)).on_press(Message::SaveEditorRange),
+                Button::new(text("Back").size(20)).on_press(Message::ToggleEditor),
+            ]
+            .spacing(20),
+        ]
+        .spacing(10)
+        .padding(10)
+        .align_items(alignment::Horizontal::Center.into());
+
+        if let Some(status) = &editor.status {
+            content = content.push(text(status).size(14));
+        }
+
+        content.into()
+    }
+}
+
+// The spot's whole range after an answer, each cell shaded by its raise, call and fold
+// frequencies and the hand just played outlined, to show where it sits in the chart.
+fn view_spot_range_grid(
+    config: &preflop_trainer_core::GameConfig,
+    spot_type: preflop_trainer_core::SpotType,
+    played: preflop_trainer_core::HandNotation,
+    dark: bool,
+) -> Element<'static, Message> {
+    let frequencies: HashMap<_, _> =
+        preflop_trainer_core::all_action_frequencies(config, spot_type)
+            .into_iter()
+            .collect();
+    let mut grid = Column::new().spacing(1);
+    for row_idx in 0..preflop_trainer_core::Rank::VALUES.len() {
+        let mut grid_row = row![].spacing(1);
+        for col_idx in 0..preflop_trainer_core::Rank::VALUES.len() {
+            let hand_notation =
+                preflop_trainer_core::HandNotation::from_grid_position(row_idx, col_idx);
+            let frequency = frequencies[&hand_notation];
+            let mut style = MyContainerStyle::new(ContainerStyleType::RangeCell {
+                raise: frequency.raise,
+                call: frequency.call,
+            });
+            if hand_notation == played {
+                style.border_color = if dark { Color::WHITE } else { Color::BLACK };
+                style.border_width = 2.0;
+            }
+            grid_row = grid_row.push(
+                container(text(hand_notation.to_string()).size(10))
+                    .width(Length::Fixed(36.0))
+                    .height(Length::Fixed(18.0))
+                    .center_x()
+                    .center_y()
+                    .style(theme::Container::Custom(Box::new(style))),
+            );
+        }
+        grid = grid.push(grid_row);
+    }
+    grid.into()
+}
 
-        main_content = main_content.push(control_buttons);
+// Sample frequencies shown in the grid legend
+const LEGEND_FREQUENCIES: [f32; 5] = [0.0, 0.25, 0.5, 0.75, 1.0];
 
-        main_content.into()
+// Color key for the grid cells, drawn with the same `GridCell` style as the grid.
+fn view_grid_legend() -> Element<'static, Message> {
+    let mut legend = Column::new().spacing(4);
+    for &frequency in LEGEND_FREQUENCIES.iter() {
+        let swatch = container(text(""))
+            .width(Length::Fixed(20.0))
+            .height(Length::Fixed(14.0))
+            .style(theme::Container::Custom(Box::new(MyContainerStyle::new(
+                ContainerStyleType::GridCell(frequency),
+            ))));
+        legend = legend.push(
+            row![swatch, text(format!("{:.0}%", frequency * 100.0)).size(12)]
+                .spacing(5)
+                .align_items(alignment::Vertical::Center.into()),
+        );
     }
+    legend.into()
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -469,10 +1371,15 @@ enum ContainerStyleType {
     SeatNormal,
     SeatUser,
     SeatOpener,
+    // Bet chip on the villain's seat
+    ActionChip,
     Card,
     Table,
     Feedback(FeedbackStyle),
     Separator,
+    GridCell(f32),
+    // A hand in the range grid shown after an answer
+    RangeCell { raise: f32, call: f32 },
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -495,7 +1402,8 @@ impl MyContainerStyle {
 impl container::StyleSheet for MyContainerStyle {
     type Style = Theme;
 
-    fn appearance(&self, _theme: &Self::Style) -> container::Appearance {
+    fn appearance(&self, theme: &Self::Style) -> container::Appearance {
+        let dark = theme.extended_palette().is_dark;
         let mut appearance = container::Appearance {
             border: Border {
                 color: self.border_color,
@@ -506,24 +1414,576 @@ impl container::StyleSheet for MyContainerStyle {
         };
 
         let background = match self.style {
+            ContainerStyleType::SeatNormal if dark => Some(Color::from_rgb(0.25, 0.25, 0.28)),
             ContainerStyleType::SeatNormal => Some(Color::from_rgb(0.4, 0.4, 0.4)),
+            ContainerStyleType::SeatUser if dark => {
+                appearance.text_color = Some(Color::BLACK);
+                Some(Color::from_rgb(0.8, 0.7, 0.1))
+            }
             ContainerStyleType::SeatUser => Some(Color::from_rgb(1.0, 1.0, 0.0)),
+            ContainerStyleType::SeatOpener if dark => {
+                appearance.text_color = Some(Color::BLACK);
+                Some(Color::from_rgb(0.8, 0.5, 0.1))
+            }
             ContainerStyleType::SeatOpener => Some(Color::from_rgb(1.0, 0.65, 0.0)),
+            ContainerStyleType::ActionChip => {
+                appearance.border.radius = 10.0.into();
+                appearance.text_color = Some(Color::WHITE);
+                Some(Color::from_rgb(0.75, 0.1, 0.1))
+            }
+            ContainerStyleType::Card if dark => Some(Color::from_rgb(0.18, 0.18, 0.2)),
             ContainerStyleType::Card => Some(Color::WHITE),
             ContainerStyleType::Table => {
                 appearance.border.radius = 20.0.into();
-                Some(Color::from_rgb(0.2, 0.5, 0.3))
+                if dark {
+                    Some(Color::from_rgb(0.08, 0.25, 0.15))
+                } else {
+                    Some(Color::from_rgb(0.2, 0.5, 0.3))
+                }
+            }
+            ContainerStyleType::Feedback(feedback_style) => {
+                Some(feedback_color(feedback_style, dark))
             }
-            ContainerStyleType::Feedback(feedback_style) => match feedback_style {
-                FeedbackStyle::Correct => Some(Color::from_rgb(0.7, 1.0, 0.7)),
-                FeedbackStyle::Wrong => Some(Color::from_rgb(1.0, 0.7, 0.7)),
-                FeedbackStyle::Ok => Some(Color::from_rgb(1.0, 0.9, 0.7)),
-                FeedbackStyle::Neutral => Some(Color::from_rgb(0.9, 0.9, 0.9)),
-            },
             ContainerStyleType::Separator => Some(Color::from_rgb(0.5, 0.5, 0.5)),
+            ContainerStyleType::RangeCell { raise, call } => {
+                Some(range_cell_color(raise, call, dark))
+            }
+            ContainerStyleType::GridCell(frequency) => {
+                if frequency <= 0.0 {
+                    if dark {
+                        Some(Color::from_rgb(0.25, 0.25, 0.25))
+                    } else {
+                        Some(Color::from_rgb(0.85, 0.85, 0.85))
+                    }
+                } else {
+                    // Blend from the "Ok" feedback shade at low frequencies to the
+                    // "Correct" green at 100%.
+                    let f = frequency.min(1.0);
+                    let ok = feedback_color(FeedbackStyle::Ok, dark);
+                    let correct = feedback_color(FeedbackStyle::Correct, dark);
+                    let blend = |from: f32, to: f32| from + (to - from) * f;
+                    Some(Color::from_rgb(
+                        blend(ok.r, correct.r),
+                        blend(ok.g, correct.g),
+                        blend(ok.b, correct.b),
+                    ))
+                }
+            }
         };
 
         appearance.background = background.map(Background::Color);
         appearance
     }
 }
+
+fn feedback_color(feedback_style: FeedbackStyle, dark: bool) -> Color {
+    match (feedback_style, dark) {
+        (FeedbackStyle::Correct, false) => Color::from_rgb(0.7, 1.0, 0.7),
+        (FeedbackStyle::Wrong, false) => Color::from_rgb(1.0, 0.7, 0.7),
+        (FeedbackStyle::Ok, false) => Color::from_rgb(1.0, 0.9, 0.7),
+        (FeedbackStyle::Neutral, false) => Color::from_rgb(0.9, 0.9, 0.9),
+        (FeedbackStyle::Correct, true) => Color::from_rgb(0.15, 0.4, 0.15),
+        (FeedbackStyle::Wrong, true) => Color::from_rgb(0.45, 0.15, 0.15),
+        (FeedbackStyle::Ok, true) => Color::from_rgb(0.45, 0.35, 0.1),
+        (FeedbackStyle::Neutral, true) => Color::from_rgb(0.3, 0.3, 0.3),
+    }
+}
+
+// The feedback shades mixed by the hand's strategy: "Correct" green for the raise share,
+// the "Ok" shade for the call (or limp) share and the neutral grey for the fold share.
+fn range_cell_color(raise: f32, call: f32, dark: bool) -> Color {
+    let fold = (1.0 - raise - call).max(0.0);
+    let [raise_color, call_color, fold_color] = [
+        FeedbackStyle::Correct,
+        FeedbackStyle::Ok,
+        FeedbackStyle::Neutral,
+    ]
+    .map(|feedback_style| feedback_color(feedback_style, dark));
+    let mix = |channel: fn(Color) -> f32| {
+        raise * channel(raise_color) + call * channel(call_color) + fold * channel(fold_color)
+    };
+    Color::from_rgb(mix(|c| c.r), mix(|c| c.g), mix(|c| c.b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Open UTG with an empty range: every hand is a 100% fold
+    fn fold_everything_gui() -> PreflopTrainerGui {
+        PreflopTrainerGui::from_config(preflop_trainer_core::GameConfig {
+            allowed_spot_types: vec![preflop_trainer_core::SpotType::open(
+                preflop_trainer_core::Position::UTG,
+            )],
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_wrong_only_filters_history() {
+        let mut gui = fold_everything_gui();
+        let _ = gui.update(Message::Raise);
+        let _ = gui.update(Message::Fold);
+        let _ = gui.update(Message::Raise);
+        assert_eq!(gui.visible_history().len(), 3);
+
+        let _ = gui.update(Message::ToggleWrongOnly);
+        let visible = gui.visible_history();
+        assert_eq!(visible.len(), 2);
+        assert!(
+            visible
+                .iter()
+                .all(|info| info.feedback.result == preflop_trainer_core::AnswerResult::Wrong)
+        );
+
+        let _ = gui.update(Message::ToggleWrongOnly);
+        assert_eq!(gui.visible_history().len(), 3);
+    }
+
+    #[test]
+    fn test_history_keeps_last_hands_only() {
+        let mut gui = fold_everything_gui();
+        for _ in 0..HISTORY_LEN + 5 {
+            let _ = gui.update(Message::Fold);
+        }
+        assert_eq!(gui.history.len(), HISTORY_LEN);
+    }
+
+    #[test]
+    fn test_spot_errors_end_the_game_instead_of_panicking() {
+        let no_spots = preflop_trainer_core::GameConfig {
+            allowed_spot_types: Vec::new(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            PreflopTrainerGui::from_config(no_spots),
+            Err(preflop_trainer_core::CoreError::SpotGeneration(_))
+        ));
+
+        // A session that runs out of spots after one more
+        let mut gui = fold_everything_gui();
+        let spot = gui.session.next_spot().unwrap();
+        gui.session = preflop_trainer_core::TrainerSession::new(
+            preflop_trainer_core::Game::new_scripted(gui.config.clone(), vec![spot]),
+            gui.config.clone(),
+        );
+        let _ = gui.update(Message::Fold);
+        assert!(!gui.game_ended);
+        let _ = gui.update(Message::Fold);
+        assert!(gui.game_ended);
+        assert_eq!(gui.total_questions, 2);
+        assert!(gui.spot_error.as_deref().unwrap().contains("scripted"));
+    }
+
+    #[test]
+    fn test_speed_round_reported_at_game_over() {
+        let mut gui = fold_everything_gui();
+        let _ = gui.update(Message::Fold);
+        let _ = gui.update(Message::StartSpeedRound);
+        assert!(gui.session.speed_round_remaining().is_some());
+        let _ = gui.update(Message::Fold);
+        let _ = gui.update(Message::Raise);
+        let _ = gui.update(Message::SpeedRoundTick);
+        assert!(!gui.game_ended);
+
+        let _ = gui.update(Message::EndGame);
+        let report = gui.speed_round_report.unwrap();
+        assert_eq!(report.answered, 2);
+        assert_eq!(report.score, 1.0);
+        assert_eq!(gui.session.speed_round_remaining(), None);
+    }
+
+    #[test]
+    fn test_play_again_clears_the_breakdown() {
+        let mut gui = fold_everything_gui();
+        let _ = gui.update(Message::Raise);
+        let _ = gui.update(Message::Fold);
+        let _ = gui.update(Message::EndGame);
+        assert_eq!(gui.session.stats().totals.overall.answered, 2);
+        assert!(!gui.session.stats().by_spot_type.is_empty());
+
+        let _ = gui.update(Message::EndGame);
+        assert!(!gui.game_ended);
+        assert_eq!(
+            gui.session.stats(),
+            &preflop_trainer_core::SessionStats::default()
+        );
+    }
+
+    #[test]
+    fn test_custom_suit_colors_reach_card_color() {
+        let mut theme = preflop_trainer_core::ThemeConfig::default();
+        assert_eq!(
+            card_suit_color(&theme, preflop_trainer_core::Suit::Diamonds, false),
+            Color::from_rgb8(0, 0, 255)
+        );
+
+        theme.suit_colors.insert(
+            preflop_trainer_core::Suit::Diamonds,
+            preflop_trainer_core::RgbColor::new(255, 128, 0),
+        );
+        theme.card_colors = preflop_trainer_core::CardColorScheme::TwoColor;
+        assert_eq!(
+            card_suit_color(&theme, preflop_trainer_core::Suit::Diamonds, false),
+            Color::from_rgb8(255, 128, 0)
+        );
+        assert_eq!(
+            card_suit_color(&theme, preflop_trainer_core::Suit::Clubs, false),
+            Color::from_rgb8(0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_opener_seat_has_action_badge_in_defense_spots() {
+        let seats = seat_labels(preflop_trainer_core::TableSize::SixMax);
+        let badges = |spot_type| -> Vec<Option<String>> {
+            seats
+                .iter()
+                .map(|seat| seat_action_badge(spot_type, seat))
+                .collect()
+        };
+
+        let defense = badges(preflop_trainer_core::SpotType::bb_defense(
+            preflop_trainer_core::Position::CO,
+        ));
+        assert_eq!(defense[2].as_deref(), Some("Raise"));
+        assert_eq!(defense.iter().flatten().count(), 1);
+
+        let sized = badges(preflop_trainer_core::SpotType::BBDefense {
+            opener_position: preflop_trainer_core::Position::BTN,
+            open_size: Some("2.5".parse().unwrap()),
+            stack_depth: None,
+        });
+        assert_eq!(sized[3].as_deref(), Some("Raise 2.5bb"));
+        assert_eq!(sized.iter().flatten().count(), 1);
+
+        let open = badges(preflop_trainer_core::SpotType::open(
+            preflop_trainer_core::Position::BTN,
+        ));
+        assert!(open.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_config_error_message_names_the_problem() {
+        let err = preflop_trainer_core::parse_config_str("[unopened_raise.XX]\nrange = \"AA\"\n")
+            .unwrap_err();
+        assert_eq!(
+            config_error_message(&err),
+            "ranges.toml names an unknown position: XX (use UTG, UTG1, UTG2, LJ, MP, HJ, CO, BTN, SB or BB)"
+        );
+
+        let err = preflop_trainer_core::parse_config_str("[unopened_raise.BTN]\nrange = \"AZs\"\n")
+            .unwrap_err();
+        assert!(config_error_message(&err).contains("AZs"));
+    }
+
+    #[test]
+    fn test_startup_error_is_shown_in_the_window() {
+        let mut app = TrainerApp::Failed("ranges.toml isn't valid TOML".to_string());
+        drop(app.view());
+        let _ = app.update(Message::Raise);
+        assert!(matches!(&app, TrainerApp::Failed(message) if message.contains("valid TOML")));
+    }
+
+    #[test]
+    fn test_nine_max_table_has_nine_seats_that_fit() {
+        assert_eq!(
+            seat_labels(preflop_trainer_core::TableSize::SixMax),
+            ["UTG", "MP", "CO", "Button", "Small Blind", "Big Blind"]
+        );
+        assert_eq!(
+            seat_labels(preflop_trainer_core::TableSize::HeadsUp),
+            ["Small Blind", "Big Blind"]
+        );
+        let seats = seat_labels(preflop_trainer_core::TableSize::NineMax);
+        assert_eq!(seats.len(), 9);
+        assert_eq!(&seats[..5], ["UTG", "UTG+1", "UTG+2", "Lojack", "Hijack"]);
+        assert_eq!(seat_width(6), 80.0);
+        assert!(9.0 * seat_width(9) + 80.0 <= 600.0);
+
+        // The hijack opener gets the bet chip at a 9-max table
+        let badges: Vec<Option<String>> = seats
+            .iter()
+            .map(|seat| {
+                seat_action_badge(
+                    preflop_trainer_core::SpotType::bb_defense(preflop_trainer_core::Position::HJ),
+                    seat,
+                )
+            })
+            .collect();
+        assert_eq!(badges[4].as_deref(), Some("Raise"));
+        assert_eq!(badges.iter().flatten().count(), 1);
+    }
+
+    #[test]
+    fn test_open_spot_highlight_matches_scoring() {
+        let mut config =
+            preflop_trainer_core::parse_config_str(include_str!("../../../ranges.toml.example"))
+                .unwrap();
+        for favors in [
+            preflop_trainer_core::BoundaryFavors::Passive,
+            preflop_trainer_core::BoundaryFavors::Aggressive,
+        ] {
+            config.boundary_favors = favors;
+            for position in [
+                preflop_trainer_core::Position::UTG,
+                preflop_trainer_core::Position::CO,
+                preflop_trainer_core::Position::BTN,
+                preflop_trainer_core::Position::SB,
+            ] {
+                let spot_type = preflop_trainer_core::SpotType::open(position);
+                for notation in preflop_trainer_core::get_all_possible_hand_notations() {
+                    let hand = notation.combos()[0];
+                    for rng in 0..100 {
+                        let highlighted =
+                            preflop_trainer_core::get_correct_action(&config, spot_type, hand, rng);
+                        assert_ne!(highlighted, preflop_trainer_core::UserAction::Call);
+                        assert_eq!(
+                            preflop_trainer_core::check_answer(
+                                &config,
+                                spot_type,
+                                hand,
+                                highlighted,
+                                rng
+                            ),
+                            preflop_trainer_core::AnswerResult::Correct,
+                            "{} {} rng {}",
+                            spot_type,
+                            notation,
+                            rng
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rng_label_hides_value() {
+        assert_eq!(rng_label(23, true), "RNG: 23");
+        assert_eq!(rng_label(23, false), "RNG: hidden");
+    }
+
+    #[test]
+    fn test_grid_legend_builds_for_default_theme() {
+        let _legend = view_grid_legend();
+
+        let theme = Theme::default();
+        let backgrounds: Vec<Option<Background>> = LEGEND_FREQUENCIES
+            .iter()
+            .map(|&frequency| {
+                container::StyleSheet::appearance(
+                    &MyContainerStyle::new(ContainerStyleType::GridCell(frequency)),
+                    &theme,
+                )
+                .background
+            })
+            .collect();
+        assert!(backgrounds.iter().all(Option::is_some));
+        for (i, background) in backgrounds.iter().enumerate() {
+            assert!(!backgrounds[i + 1..].contains(background));
+        }
+    }
+
+    #[test]
+    fn test_range_cell_color_matches_feedback_shades() {
+        let theme = Theme::default();
+        let background = |style| {
+            container::StyleSheet::appearance(&MyContainerStyle::new(style), &theme).background
+        };
+        assert_eq!(
+            Some(Background::Color(range_cell_color(1.0, 0.0, false))),
+            background(ContainerStyleType::Feedback(FeedbackStyle::Correct))
+        );
+        assert_eq!(
+            Some(Background::Color(range_cell_color(0.0, 1.0, false))),
+            background(ContainerStyleType::Feedback(FeedbackStyle::Ok))
+        );
+        assert_eq!(
+            Some(Background::Color(range_cell_color(0.0, 0.0, false))),
+            background(ContainerStyleType::Feedback(FeedbackStyle::Neutral))
+        );
+        let mixed = range_cell_color(0.5, 0.0, false);
+        assert!(mixed.r > 0.7 && mixed.r < 0.9);
+
+        // Builds for every spot the example config serves
+        let config =
+            preflop_trainer_core::parse_config_str(include_str!("../../../ranges.toml.example"))
+                .unwrap();
+        for &spot_type in &config.allowed_spot_types {
+            let _grid = view_spot_range_grid(&config, spot_type, "AKo".parse().unwrap(), false);
+        }
+    }
+
+    #[test]
+    fn test_toggle_theme_switches_to_dark_palette() {
+        let mut gui = fold_everything_gui();
+        assert_eq!(gui.theme(), Theme::Light);
+        let card = |theme: &Theme| {
+            container::StyleSheet::appearance(
+                &MyContainerStyle::new(ContainerStyleType::Card),
+                theme,
+            )
+            .background
+        };
+        let light_card = card(&gui.theme());
+
+        let _ = gui.update(Message::ToggleTheme);
+        assert!(gui.dark);
+        assert_eq!(gui.theme(), Theme::Dark);
+        assert_ne!(card(&gui.theme()), light_card);
+        let _ = gui.update(Message::ToggleTheme);
+        assert_eq!(gui.theme(), Theme::Light);
+
+        // Black spades are lifted off the dark card background
+        let theme = preflop_trainer_core::ThemeConfig::default();
+        let spade = card_suit_color(&theme, preflop_trainer_core::Suit::Spades, true);
+        assert!(spade.r > 0.4 && spade.g > 0.4 && spade.b > 0.4);
+        assert_eq!(
+            card_suit_color(&theme, preflop_trainer_core::Suit::Spades, false),
+            Color::BLACK
+        );
+    }
+
+    #[test]
+    fn test_theme_choice_persists() {
+        let path = std::env::temp_dir().join(format!(
+            "preflop_trainer_gui_settings_{}.toml",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(GuiSettings::load(&path), GuiSettings::default());
+
+        let mut gui = fold_everything_gui();
+        gui.settings_path = Some(path.clone());
+        let _ = gui.update(Message::ToggleTheme);
+        assert!(GuiSettings::load(&path).dark);
+        let _ = gui.update(Message::ToggleTheme);
+        assert!(!GuiSettings::load(&path).dark);
+
+        std::fs::write(&path, "dark = \"maybe\"").unwrap();
+        assert_eq!(GuiSettings::load(&path), GuiSettings::default());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_disabled_spots_are_not_dealt() {
+        let config =
+            preflop_trainer_core::parse_config_str(include_str!("../../../ranges.toml.example"))
+                .unwrap();
+        let btn_open = preflop_trainer_core::SpotType::open(preflop_trainer_core::Position::BTN);
+        let mut gui = PreflopTrainerGui::from_config(config.clone()).unwrap();
+        let _ = gui.update(Message::ToggleSettings);
+        assert_eq!(gui.screen, Screen::Settings);
+        // Answers are ignored while choosing spots
+        let _ = gui.update(Message::Fold);
+        assert_eq!(gui.total_questions, 0);
+
+        for &spot_type in &config.allowed_spot_types {
+            if spot_type != btn_open {
+                let _ = gui.update(Message::ToggleSpot(spot_type));
+            }
+        }
+        assert_eq!(
+            gui.disabled_spot_types.len(),
+            config.allowed_spot_types.len() - 1
+        );
+        assert_eq!(gui.current_spot_type, btn_open);
+        // The last enabled spot can't be switched off
+        let _ = gui.update(Message::ToggleSpot(btn_open));
+        assert!(!gui.disabled_spot_types.contains(&btn_open));
+
+        let _ = gui.update(Message::ToggleSettings);
+        for _ in 0..30 {
+            let _ = gui.update(Message::Fold);
+            assert_eq!(gui.current_spot_type, btn_open);
+        }
+        // The full config is kept, so spots can be switched back on
+        assert_eq!(gui.config.allowed_spot_types, config.allowed_spot_types);
+    }
+
+    #[test]
+    fn test_spot_selection_persists() {
+        let path = std::env::temp_dir().join(format!(
+            "preflop_trainer_gui_spots_{}.toml",
+            std::process::id()
+        ));
+        let config =
+            preflop_trainer_core::parse_config_str(include_str!("../../../ranges.toml.example"))
+                .unwrap();
+        let utg_open = config.allowed_spot_types[0];
+        let mut gui = PreflopTrainerGui::from_config(config.clone()).unwrap();
+        gui.settings_path = Some(path.clone());
+        let _ = gui.update(Message::ToggleSpot(utg_open));
+        assert_eq!(GuiSettings::load(&path).disabled_spot_types, [utg_open]);
+        let _ = gui.update(Message::ToggleTheme);
+        assert_eq!(
+            GuiSettings::load(&path),
+            GuiSettings {
+                dark: true,
+                disabled_spot_types: vec![utg_open],
+            }
+        );
+
+        // Restored on the next start, ignoring spots the config no longer serves
+        let mut restarted = PreflopTrainerGui::from_config(config).unwrap();
+        let mut disabled = GuiSettings::load(&path).disabled_spot_types;
+        disabled.push(preflop_trainer_core::SpotType::SbLimp);
+        assert!(restarted.set_disabled_spot_types(disabled));
+        assert_eq!(restarted.disabled_spot_types, [utg_open]);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_undo_restores_the_answered_spot() {
+        let mut gui = fold_everything_gui();
+        let _ = gui.update(Message::Fold);
+        let first = (gui.current_spot_type, gui.current_hand);
+        let first_rng = gui.mixed_strategy_rng_value;
+        let _ = gui.update(Message::Raise);
+        let next = (gui.current_spot_type, gui.current_hand);
+        assert_eq!(gui.total_questions, 2);
+        assert_eq!(gui.correct_answers, 1.0);
+
+        let _ = gui.update(Message::Undo);
+        assert_eq!((gui.current_spot_type, gui.current_hand), first);
+        assert_eq!(gui.mixed_strategy_rng_value, first_rng);
+        assert_eq!(gui.total_questions, 1);
+        assert_eq!(gui.correct_answers, 1.0);
+        assert_eq!(gui.history.len(), 1);
+        assert_eq!(
+            gui.previous_hand_info.map(|info| info.user_action),
+            Some(preflop_trainer_core::UserAction::Fold)
+        );
+        assert_eq!(gui.session.stats().totals.overall.answered, 1);
+
+        // Only one level: a second undo does nothing
+        let _ = gui.update(Message::Undo);
+        assert_eq!(gui.total_questions, 1);
+
+        // Answering again deals the same next spot as before the undo
+        let _ = gui.update(Message::Fold);
+        assert_eq!((gui.current_spot_type, gui.current_hand), next);
+        assert_eq!(gui.correct_answers, 2.0);
+    }
+
+    #[test]
+    fn test_range_editor_cycles_cells_through_an_explicit_zero() {
+        let hand_notation: preflop_trainer_core::HandNotation = "72o".parse().unwrap();
+        let mut editor = RangeEditor::default();
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            editor.cycle_cell(hand_notation);
+            seen.push(editor.range.get(hand_notation));
+        }
+        assert_eq!(seen, [Some(0.0), Some(0.5), Some(1.0), None]);
+
+        editor.cycle_cell(hand_notation);
+        assert_eq!(editor.range_string(), "72o:0");
+
+        // Imported frequencies snap up to the next step
+        assert_eq!(RangeEditor::next_frequency(Some(0.25)), Some(0.5));
+        assert_eq!(RangeEditor::next_frequency(Some(0.75)), Some(1.0));
+    }
+}